@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use characteristics::Characteristics;
+use id::{Id, get_id};
+use mana_cost::ManaCost;
+
+/// How the faces of a card with more than one fit together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardLayout {
+    /// A card with only one face.
+    Normal,
+
+    /// A double-faced card that's cast or played with its front face up, and
+    /// can transform into its back face on the battlefield (rule 712).
+    Transform,
+
+    /// A double-faced card that can be cast or played with either face up
+    /// (rule 712.12).
+    ModalDoubleFaced,
+
+    /// Two half-cards printed side by side, like Fire // Ice. Either half can
+    /// be cast, or both together if the card has fuse (rules 709 and 702.102).
+    Split {
+        fuse: bool,
+    },
+
+    /// A permanent card with an instant or sorcery Adventure printed inside
+    /// it, which can be cast instead (rule 715).
+    Adventure,
+
+    /// A card with a second set of characteristics printed upside-down, which
+    /// it has while it's a flipped permanent (rule 710).
+    Flip,
+}
+
+/// One face of a card with more than one. The second face is the back face of
+/// a double-faced card, the right half of a split card, the Adventure of an
+/// adventurer card, or the flipped half of a flip card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Face {
+    Front,
+    Back,
+}
+
+/// The printed definition of a card: everything that's the same about every
+/// copy of it, like its name and rules text.
+///
+/// Objects representing the card point back at its definition instead of
+/// carrying their own copy.
+#[derive(Debug, Clone)]
+pub struct CardDefinition {
+    pub id: Id,
+
+    /// The card's characteristics when no particular face applies. That's the
+    /// front face, or both halves combined for split cards (rule 709.4).
+    pub characteristics: Characteristics,
+
+    pub layout: CardLayout,
+
+    /// The front and back faces of a card with more than one, or nothing for a
+    /// normal card.
+    pub faces: Vec<Characteristics>,
+}
+
+impl CardDefinition {
+    /// The characteristics of one of the card's faces. Normal cards only have
+    /// a front face.
+    pub fn face(&self, face: Face) -> Option<&Characteristics> {
+        if self.faces.is_empty() {
+            return match face {
+                Face::Front => Some(&self.characteristics),
+                Face::Back => None,
+            };
+        }
+
+        match face {
+            Face::Front => self.faces.first(),
+            Face::Back => self.faces.get(1),
+        }
+    }
+
+    /// Whether the card can be cast with the given face up, where `None` means
+    /// without choosing a face. For split cards with fuse, that means casting
+    /// both halves together.
+    pub fn can_cast_face(&self, face: Option<Face>) -> bool {
+        match (self.layout, face) {
+            (_, Some(Face::Front)) => true,
+            (CardLayout::Split { fuse }, None) => fuse,
+            (_, None) => true,
+            (CardLayout::ModalDoubleFaced, Some(Face::Back))
+            | (CardLayout::Split { .. }, Some(Face::Back))
+            | (CardLayout::Adventure, Some(Face::Back)) => true,
+            (_, Some(Face::Back)) => false,
+        }
+    }
+
+    /// Whether the card can be played as a land with the given face up. Only
+    /// modal double-faced cards can be played with their back face up.
+    pub fn can_play_face(&self, face: Option<Face>) -> bool {
+        match face {
+            None | Some(Face::Front) => true,
+            Some(Face::Back) => self.layout == CardLayout::ModalDoubleFaced,
+        }
+    }
+}
+
+/// Combines the two halves of a split card into the characteristics it has
+/// outside of the stack (rule 709.4). Its name, mana cost, types, and text are
+/// all of both halves' put together.
+fn combine_split_halves(left: &Characteristics, right: &Characteristics) -> Characteristics {
+    fn union<T: Clone + PartialEq>(left: &[T], right: &[T]) -> Vec<T> {
+        let mut combined = left.to_vec();
+
+        for value in right {
+            if !combined.contains(value) {
+                combined.push(value.clone());
+            }
+        }
+
+        combined
+    }
+
+    let mana_cost = match (&left.mana_cost, &right.mana_cost) {
+        (None, None) => None,
+        (left_cost, right_cost) => {
+            let mut symbols = Vec::new();
+            symbols.extend(left_cost.iter().flat_map(|cost| cost.symbols.iter().cloned()));
+            symbols.extend(right_cost.iter().flat_map(|cost| cost.symbols.iter().cloned()));
+
+            Some(ManaCost { symbols })
+        },
+    };
+
+    let mut abilities = left.abilities.clone();
+    abilities.extend(right.abilities.iter().map(|(&id, ability)| (id, ability.clone())));
+
+    Characteristics {
+        name: format!("{} // {}", left.name, right.name),
+        mana_cost,
+        color_indicator: union(&left.color_indicator, &right.color_indicator),
+        supertypes: union(&left.supertypes, &right.supertypes),
+        card_types: union(&left.card_types, &right.card_types),
+        subtypes: union(&left.subtypes, &right.subtypes),
+        rules_text: format!("{}\n//\n{}", left.rules_text, right.rules_text),
+        abilities,
+        ..Characteristics::default()
+    }
+}
+
+/// A pool of card definitions, like every card that's legal in a format.
+///
+/// Registries don't change once a game is going, so `Game` holds one behind an
+/// `Arc`. That way, cloning a `Game` (like `view_as_player` does) doesn't copy
+/// any card text, and many games can share the same registry.
+#[derive(Debug, Clone, Default)]
+pub struct CardRegistry {
+    definitions: HashMap<Id, CardDefinition>,
+}
+
+impl CardRegistry {
+    pub fn new() -> CardRegistry {
+        CardRegistry::default()
+    }
+
+    /// Adds a new card definition to the registry, returning its ID.
+    pub fn add(&mut self, characteristics: Characteristics) -> Id {
+        self.insert(CardDefinition {
+            id: get_id(),
+            characteristics,
+            layout: CardLayout::Normal,
+            faces: Vec::new(),
+        })
+    }
+
+    /// Adds a new definition for a card with two faces, returning its ID.
+    /// Adding a `CardLayout::Normal` card this way ignores its back face.
+    pub fn add_multi_faced(&mut self, layout: CardLayout, front: Characteristics, back: Characteristics) -> Id {
+        if layout == CardLayout::Normal {
+            return self.add(front);
+        }
+
+        let characteristics = match layout {
+            CardLayout::Split { .. } => combine_split_halves(&front, &back),
+            _ => front.clone(),
+        };
+
+        self.insert(CardDefinition {
+            id: get_id(),
+            characteristics,
+            layout,
+            faces: vec![front, back],
+        })
+    }
+
+    fn insert(&mut self, definition: CardDefinition) -> Id {
+        let definition_id = definition.id;
+
+        self.definitions.insert(definition_id, definition);
+
+        definition_id
+    }
+
+    pub fn get(&self, definition_id: Id) -> Option<&CardDefinition> {
+        self.definitions.get(&definition_id)
+    }
+
+    pub fn get_mut(&mut self, definition_id: Id) -> Option<&mut CardDefinition> {
+        self.definitions.get_mut(&definition_id)
+    }
+
+    /// Finds a card definition by its card's name, or the name of one of its
+    /// faces.
+    pub fn find_by_name(&self, name: &str) -> Option<&CardDefinition> {
+        self.definitions.values()
+            .find(|definition| {
+                definition.characteristics.name == name
+                    || definition.faces.iter().any(|face| face.name == name)
+            })
+    }
+
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+}
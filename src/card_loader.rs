@@ -0,0 +1,734 @@
+//! Loads card definitions from TOML files, so that cards can be added without
+//! recompiling the engine.
+//!
+//! Each file holds any number of `[[card]]` tables:
+//!
+//! ```toml
+//! [[card]]
+//! name = "Llanowar Elves"
+//! mana_cost = "{G}"
+//! types = ["Creature"]
+//! subtypes = ["Elf", "Druid"]
+//! rules_text = "{T}: Add {G}."
+//! power = 1
+//! toughness = 1
+//!
+//! [[card.abilities]]
+//! kind = "mana"
+//! cost = "{T}"
+//! produces = ["G"]
+//! ```
+//!
+//! Abilities are written in the engine's ability vocabulary rather than parsed
+//! from rules text. `kind` is one of:
+//!
+//! - `mana`, which needs a list of the mana symbols it `produces`.
+//! - `activated`, which needs a list of `effects`, like
+//!   `{ kind = "draw_cards", count = 1 }`, `{ kind = "gain_life", amount = 3 }`,
+//!   or `{ kind = "damage_each_opponent", amount = 2 }`.
+//! - `spell`, which is the list of `effects` an instant or sorcery has as it
+//!   resolves.
+//! - `triggered`, which has `effects` and a `trigger`, either `you_cast_spell`
+//!   or `beginning_of_your_step` along with a `step` like `upkeep`.
+//! - `static`, which applies its `effects` to every permanent matching
+//!   `affected`, like `{ types = ["Creature"], you_control = true }`, or every
+//!   permanent if it's left out. Its effects are continuous effects, like
+//!   `{ kind = "modify_power_toughness", power = 1, toughness = 1 }` or
+//!   `{ kind = "count_power_toughness", counted = { types = ["Land"] } }`.
+//! - `replacement`, which has a `replacement` like `enters_tapped`, or
+//!   `exile_instead` along with what it's `affected`.
+//!
+//! Costs are written like `{1}, {T}` or `{T}, Sacrifice this artifact`.
+//!
+//! ```toml
+//! [[card]]
+//! name = "Glorious Anthem"
+//! mana_cost = "{1}{W}{W}"
+//! types = ["Enchantment"]
+//!
+//! [[card.abilities]]
+//! kind = "static"
+//! affected = { types = ["Creature"], you_control = true }
+//! effects = [{ kind = "modify_power_toughness", power = 1, toughness = 1 }]
+//! ```
+//!
+//! Power, toughness, loyalty, and defense are either numbers or text involving
+//! `*` or `X`, like `"*"`, `"1+*"`, or `"*-1"`.
+//!
+//! Cards with two faces have a `layout`, one of `transform`, `modal_dfc`,
+//! `split`, `adventure`, or `flip`, and describe their second face in a
+//! `[card.back]` table. Split cards with fuse also set `fuse = true`.
+//!
+//! ```toml
+//! [[card]]
+//! name = "Fire"
+//! layout = "split"
+//! mana_cost = "{1}{R}"
+//! types = ["Instant"]
+//!
+//! [card.back]
+//! name = "Ice"
+//! mana_cost = "{1}{U}"
+//! types = ["Instant"]
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use toml;
+use toml::Spanned;
+
+use card_definition::{CardLayout, CardRegistry};
+use characteristics::{CardType, Characteristics, StatValue, Supertype};
+use continuous_effect::ContinuousEffectKind;
+use effect::Effect;
+use game::GamePhase;
+use id::{Id, get_id};
+use mana::{Color, ManaType};
+use mana_cost::{ManaCost, ManaCostParseError};
+use object::{Ability, ActivatedAbility, ActivationCost, ManaAbility, ObjectFilter, StaticAbility, Trigger, TriggeredAbility};
+use replacement_effect::ReplacementEffectKind;
+
+/// The different problems a card file can have.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CardLoadErrorKind {
+    /// The file couldn't be read.
+    Io(String),
+
+    /// The file isn't valid TOML, or doesn't have the shape of a card file,
+    /// like a card with a key we don't know about.
+    Format(String),
+
+    /// A value we don't know the meaning of, like an unknown card type or
+    /// ability kind.
+    UnknownKeyword(String),
+
+    /// A mana cost or activation cost that couldn't be parsed.
+    MalformedCost {
+        cost: String,
+        error: ManaCostParseError,
+    },
+
+    /// Anything else wrong with a card, like a mana ability that makes no
+    /// mana.
+    Invalid(&'static str),
+}
+
+impl fmt::Display for CardLoadErrorKind {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CardLoadErrorKind::Io(ref message) => write!(formatter, "unable to read file: {}", message),
+            CardLoadErrorKind::Format(ref message) => write!(formatter, "{}", message),
+            CardLoadErrorKind::UnknownKeyword(ref keyword) => write!(formatter, "unknown keyword `{}`", keyword),
+            CardLoadErrorKind::MalformedCost { ref cost, ref error } => write!(formatter, "{} in cost `{}`", error, cost),
+            CardLoadErrorKind::Invalid(message) => write!(formatter, "{}", message),
+        }
+    }
+}
+
+/// An error loading a card file, and where in the file it happened.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardLoadError {
+    pub file_name: String,
+
+    /// The line the error is on, counting from 1. Errors that don't belong to
+    /// any particular line, like being unable to read the file, are on line 0.
+    pub line: usize,
+
+    /// The column the error is at, counting from 1.
+    pub column: usize,
+
+    pub kind: CardLoadErrorKind,
+}
+
+impl fmt::Display for CardLoadError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}:{}:{}: {}", self.file_name, self.line, self.column, self.kind)
+    }
+}
+
+/// Loads every card in the file at the given path into the registry, returning
+/// the IDs of their new definitions.
+pub fn load_card_file(registry: &mut CardRegistry, path: &Path) -> Result<Vec<Id>, CardLoadError> {
+    let file_name = path.display().to_string();
+
+    let source = fs::read_to_string(path)
+        .map_err(|error| CardLoadError {
+            file_name: file_name.clone(),
+            line: 0,
+            column: 0,
+            kind: CardLoadErrorKind::Io(error.to_string()),
+        })?;
+
+    load_cards(registry, &file_name, &source)
+}
+
+/// Loads every card in the given TOML source into the registry, returning the
+/// IDs of their new definitions. `file_name` is only used for error messages.
+///
+/// Either every card in the source is loaded, or none are.
+pub fn load_cards(registry: &mut CardRegistry, file_name: &str, source: &str) -> Result<Vec<Id>, CardLoadError> {
+    let loader = Loader {
+        file_name,
+        source,
+    };
+
+    let file: CardFile = toml::from_str(source)
+        .map_err(|error| {
+            let kind = CardLoadErrorKind::Format(error.message().to_string());
+
+            loader.error(error.span().map(|span| span.start), kind)
+        })?;
+
+    let definitions = file.card.iter()
+        .map(|card| loader.definition(card))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(definitions.into_iter()
+        .map(|(layout, front, back)| match back {
+            Some(back) => registry.add_multi_faced(layout, front, back),
+            None => registry.add(front),
+        })
+        .collect())
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CardFile {
+    #[serde(default)]
+    card: Vec<CardEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CardEntry {
+    name: String,
+    mana_cost: Option<Spanned<String>>,
+    #[serde(default)]
+    color_indicator: Vec<Spanned<String>>,
+    #[serde(default)]
+    supertypes: Vec<Spanned<String>>,
+    #[serde(default)]
+    types: Vec<Spanned<String>>,
+    #[serde(default)]
+    subtypes: Vec<String>,
+    #[serde(default)]
+    rules_text: String,
+    #[serde(default)]
+    abilities: Vec<Spanned<AbilityEntry>>,
+    power: Option<Spanned<StatEntry>>,
+    toughness: Option<Spanned<StatEntry>>,
+    loyalty: Option<Spanned<StatEntry>>,
+    defense: Option<Spanned<StatEntry>>,
+    layout: Option<Spanned<String>>,
+    #[serde(default)]
+    fuse: bool,
+    back: Option<Spanned<Box<CardEntry>>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AbilityEntry {
+    kind: Spanned<String>,
+    cost: Option<Spanned<String>>,
+    #[serde(default)]
+    produces: Vec<Spanned<String>>,
+    #[serde(default)]
+    effects: Vec<Spanned<EffectEntry>>,
+    trigger: Option<Spanned<String>>,
+    step: Option<Spanned<String>>,
+    affected: Option<FilterEntry>,
+    replacement: Option<Spanned<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EffectEntry {
+    kind: Spanned<String>,
+    count: Option<usize>,
+    amount: Option<usize>,
+    power: Option<i32>,
+    toughness: Option<i32>,
+    #[serde(default)]
+    types: Vec<Spanned<String>>,
+    #[serde(default)]
+    subtypes: Vec<String>,
+    subtype: Option<String>,
+    #[serde(default)]
+    colors: Vec<Spanned<String>>,
+    counted: Option<FilterEntry>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FilterEntry {
+    #[serde(default)]
+    types: Vec<Spanned<String>>,
+    #[serde(default)]
+    subtypes: Vec<String>,
+    #[serde(default)]
+    excluded_supertypes: Vec<Spanned<String>>,
+    #[serde(default)]
+    excluded_subtypes: Vec<String>,
+    #[serde(default)]
+    you_control: bool,
+    #[serde(default)]
+    other: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StatEntry {
+    Number(i32),
+    Text(String),
+}
+
+/// Turns the raw contents of a card file into characteristics, keeping track
+/// of the source so errors can point at the right line.
+struct Loader<'a> {
+    file_name: &'a str,
+    source: &'a str,
+}
+
+impl<'a> Loader<'a> {
+    fn error(&self, offset: Option<usize>, kind: CardLoadErrorKind) -> CardLoadError {
+        let (line, column) = match offset {
+            Some(offset) => {
+                let before = &self.source[..offset.min(self.source.len())];
+                let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+
+                (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+            },
+            None => (0, 0),
+        };
+
+        CardLoadError {
+            file_name: self.file_name.to_string(),
+            line,
+            column,
+            kind,
+        }
+    }
+
+    fn error_at(&self, span: Range<usize>, kind: CardLoadErrorKind) -> CardLoadError {
+        self.error(Some(span.start), kind)
+    }
+
+    fn unknown_keyword(&self, keyword: &Spanned<String>) -> CardLoadError {
+        self.error_at(keyword.span(), CardLoadErrorKind::UnknownKeyword(keyword.get_ref().clone()))
+    }
+
+    fn malformed_cost(&self, cost: &Spanned<String>, error: ManaCostParseError) -> CardLoadError {
+        self.error_at(cost.span(), CardLoadErrorKind::MalformedCost {
+            cost: cost.get_ref().clone(),
+            error,
+        })
+    }
+
+    /// Reads a card's layout and the characteristics of each of its faces.
+    fn definition(&self, card: &CardEntry) -> Result<(CardLayout, Characteristics, Option<Characteristics>), CardLoadError> {
+        let layout = match card.layout {
+            Some(ref layout) => match layout.get_ref().as_str() {
+                "normal" => CardLayout::Normal,
+                "transform" => CardLayout::Transform,
+                "modal_dfc" => CardLayout::ModalDoubleFaced,
+                "split" => CardLayout::Split { fuse: card.fuse },
+                "adventure" => CardLayout::Adventure,
+                "flip" => CardLayout::Flip,
+                _ => return Err(self.unknown_keyword(layout)),
+            },
+            None => CardLayout::Normal,
+        };
+
+        if card.fuse && !matches!(layout, CardLayout::Split { .. }) {
+            return Err(self.error(card.layout.as_ref().map(|layout| layout.span().start), CardLoadErrorKind::Invalid("only split cards can have fuse")));
+        }
+
+        let back = match (layout, &card.back) {
+            (CardLayout::Normal, Some(back)) => {
+                return Err(self.error_at(back.span(), CardLoadErrorKind::Invalid("only cards with a layout can have a back face")));
+            },
+            (CardLayout::Normal, None) => None,
+            (_, Some(back)) => {
+                let face = back.get_ref();
+
+                if face.layout.is_some() || face.fuse || face.back.is_some() {
+                    return Err(self.error_at(back.span(), CardLoadErrorKind::Invalid("back faces can't have their own layout")));
+                }
+
+                Some(self.characteristics(face)?)
+            },
+            (_, None) => {
+                let span = card.layout.as_ref().map(|layout| layout.span().start);
+
+                return Err(self.error(span, CardLoadErrorKind::Invalid("cards with a layout need a back face")));
+            },
+        };
+
+        Ok((layout, self.characteristics(card)?, back))
+    }
+
+    fn characteristics(&self, card: &CardEntry) -> Result<Characteristics, CardLoadError> {
+        let mana_cost = match card.mana_cost {
+            Some(ref cost) => {
+                Some(ManaCost::parse(cost.get_ref()).map_err(|error| self.malformed_cost(cost, error))?)
+            },
+            None => None,
+        };
+
+        let color_indicator = self.colors(&card.color_indicator)?;
+        let supertypes = self.supertypes(&card.supertypes)?;
+        let card_types = self.card_types(&card.types)?;
+
+        let mut abilities = HashMap::new();
+
+        for ability in &card.abilities {
+            abilities.insert(get_id(), self.ability(ability)?);
+        }
+
+        Ok(Characteristics {
+            name: card.name.clone(),
+            mana_cost,
+            color_indicator,
+            color_override: None,
+            supertypes,
+            card_types,
+            subtypes: card.subtypes.clone(),
+            rules_text: card.rules_text.clone(),
+            abilities,
+            power: self.stat(&card.power)?,
+            toughness: self.stat(&card.toughness)?,
+            loyalty: self.stat(&card.loyalty)?,
+            defense: self.stat(&card.defense)?,
+        })
+    }
+
+    fn colors(&self, symbols: &[Spanned<String>]) -> Result<Vec<Color>, CardLoadError> {
+        symbols.iter()
+            .map(|symbol| Color::from_symbol(symbol.get_ref()).ok_or_else(|| self.unknown_keyword(symbol)))
+            .collect()
+    }
+
+    fn supertypes(&self, names: &[Spanned<String>]) -> Result<Vec<Supertype>, CardLoadError> {
+        names.iter()
+            .map(|name| Supertype::from_name(name.get_ref()).ok_or_else(|| self.unknown_keyword(name)))
+            .collect()
+    }
+
+    fn card_types(&self, names: &[Spanned<String>]) -> Result<Vec<CardType>, CardLoadError> {
+        names.iter()
+            .map(|name| CardType::from_name(name.get_ref()).ok_or_else(|| self.unknown_keyword(name)))
+            .collect()
+    }
+
+    fn filter(&self, entry: &FilterEntry) -> Result<ObjectFilter, CardLoadError> {
+        Ok(ObjectFilter {
+            card_types: self.card_types(&entry.types)?,
+            subtypes: entry.subtypes.clone(),
+            excluded_supertypes: self.supertypes(&entry.excluded_supertypes)?,
+            excluded_subtypes: entry.excluded_subtypes.clone(),
+            you_control: entry.you_control,
+            other: entry.other,
+        })
+    }
+
+    fn ability(&self, entry: &Spanned<AbilityEntry>) -> Result<Ability, CardLoadError> {
+        let ability = entry.get_ref();
+        let kind = ability.kind.get_ref().as_str();
+
+        let cost = match ability.cost {
+            Some(ref cost) => ActivationCost::parse(cost.get_ref()).map_err(|error| self.malformed_cost(cost, error))?,
+            None => ActivationCost::default(),
+        };
+
+        if ability.trigger.is_some() && kind != "triggered" {
+            return Err(self.error_at(entry.span(), CardLoadErrorKind::Invalid("only triggered abilities have a trigger")));
+        }
+
+        if ability.replacement.is_some() && kind != "replacement" {
+            return Err(self.error_at(entry.span(), CardLoadErrorKind::Invalid("only replacement abilities have a replacement")));
+        }
+
+        if ability.affected.is_some() && kind != "static" && kind != "replacement" {
+            return Err(self.error_at(entry.span(), CardLoadErrorKind::Invalid("only static and replacement abilities affect permanents")));
+        }
+
+        match kind {
+            "mana" => {
+                if ability.produces.is_empty() {
+                    return Err(self.error_at(entry.span(), CardLoadErrorKind::Invalid("mana abilities need to produce mana")));
+                }
+
+                if !ability.effects.is_empty() {
+                    return Err(self.error_at(entry.span(), CardLoadErrorKind::Invalid("mana abilities can't have effects")));
+                }
+
+                let produces = ability.produces.iter()
+                    .map(|symbol| ManaType::from_symbol(symbol.get_ref()).ok_or_else(|| self.unknown_keyword(symbol)))
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Ability::Mana(ManaAbility {
+                    cost,
+                    produces,
+                }))
+            },
+            "activated" => {
+                if !ability.produces.is_empty() {
+                    return Err(self.error_at(entry.span(), CardLoadErrorKind::Invalid("only mana abilities can produce mana")));
+                }
+
+                let effects = ability.effects.iter()
+                    .map(|effect| self.effect(effect))
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Ability::Activated(ActivatedAbility {
+                    cost,
+                    effects,
+                }))
+            },
+            "spell" => {
+                if !ability.produces.is_empty() {
+                    return Err(self.error_at(entry.span(), CardLoadErrorKind::Invalid("only mana abilities can produce mana")));
+                }
+
+                if ability.cost.is_some() {
+                    return Err(self.error_at(entry.span(), CardLoadErrorKind::Invalid("spell abilities don't have a cost")));
+                }
+
+                let effects = ability.effects.iter()
+                    .map(|effect| self.effect(effect))
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Ability::Spell(effects))
+            },
+            "triggered" | "static" | "replacement" if ability.cost.is_some() => {
+                Err(self.error_at(entry.span(), CardLoadErrorKind::Invalid("only activated abilities have a cost")))
+            },
+            "triggered" | "static" | "replacement" if !ability.produces.is_empty() => {
+                Err(self.error_at(entry.span(), CardLoadErrorKind::Invalid("only mana abilities can produce mana")))
+            },
+            "triggered" => {
+                let trigger = ability.trigger.as_ref()
+                    .ok_or_else(|| self.error_at(entry.span(), CardLoadErrorKind::Invalid("triggered abilities need a trigger")))?;
+
+                let trigger = match trigger.get_ref().as_str() {
+                    "you_cast_spell" => Trigger::YouCastSpell,
+                    "beginning_of_your_step" => {
+                        let step = ability.step.as_ref()
+                            .ok_or_else(|| self.error_at(trigger.span(), CardLoadErrorKind::Invalid("beginning_of_your_step needs a step")))?;
+
+                        Trigger::BeginningOfYourStep(self.step(step)?)
+                    },
+                    _ => return Err(self.unknown_keyword(trigger)),
+                };
+
+                let effects = ability.effects.iter()
+                    .map(|effect| self.effect(effect))
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Ability::Triggered(TriggeredAbility {
+                    trigger,
+                    effects,
+                }))
+            },
+            "static" => {
+                let affected = match ability.affected {
+                    Some(ref affected) => self.filter(affected)?,
+                    None => ObjectFilter::default(),
+                };
+
+                let effects = ability.effects.iter()
+                    .map(|effect| self.continuous_effect(effect))
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Ability::Static(StaticAbility {
+                    affected,
+                    effects,
+                }))
+            },
+            "replacement" => {
+                if !ability.effects.is_empty() {
+                    return Err(self.error_at(entry.span(), CardLoadErrorKind::Invalid("replacement abilities don't have effects")));
+                }
+
+                let replacement = ability.replacement.as_ref()
+                    .ok_or_else(|| self.error_at(entry.span(), CardLoadErrorKind::Invalid("replacement abilities need a replacement")))?;
+
+                let affected = || match ability.affected {
+                    Some(ref affected) => self.filter(affected),
+                    None => Err(self.error_at(replacement.span(), CardLoadErrorKind::Invalid("this replacement needs to say what it affects"))),
+                };
+
+                let kind = match replacement.get_ref().as_str() {
+                    "enters_tapped" => ReplacementEffectKind::EntersTapped,
+                    "permanents_enter_tapped" => ReplacementEffectKind::PermanentsEnterTapped { affected: affected()? },
+                    "exile_instead" => ReplacementEffectKind::ExileInstead { affected: affected()? },
+                    "double_counters" => ReplacementEffectKind::DoubleCounters { affected: affected()? },
+                    _ => return Err(self.unknown_keyword(replacement)),
+                };
+
+                Ok(Ability::Replacement(kind))
+            },
+            _ => Err(self.unknown_keyword(&ability.kind)),
+        }
+    }
+
+    fn step(&self, name: &Spanned<String>) -> Result<GamePhase, CardLoadError> {
+        match name.get_ref().as_str() {
+            "untap" => Ok(GamePhase::Untap),
+            "upkeep" => Ok(GamePhase::Upkeep),
+            "draw" => Ok(GamePhase::Draw),
+            "precombat_main" => Ok(GamePhase::PrecombatMain),
+            "beginning_of_combat" => Ok(GamePhase::BeginningOfCombat),
+            "declare_attackers" => Ok(GamePhase::DeclareAttackers),
+            "declare_blockers" => Ok(GamePhase::DeclareBlockers),
+            "combat_damage" => Ok(GamePhase::CombatDamage),
+            "end_of_combat" => Ok(GamePhase::EndOfCombat),
+            "postcombat_main" => Ok(GamePhase::PostcombatMain),
+            "end" => Ok(GamePhase::End),
+            "cleanup" => Ok(GamePhase::Cleanup),
+            _ => Err(self.unknown_keyword(name)),
+        }
+    }
+
+    fn effect(&self, entry: &Spanned<EffectEntry>) -> Result<Effect, CardLoadError> {
+        let effect = entry.get_ref();
+
+        match effect.kind.get_ref().as_str() {
+            "draw_cards" => {
+                let count = effect.count
+                    .ok_or_else(|| self.error_at(entry.span(), CardLoadErrorKind::Invalid("draw_cards needs a count")))?;
+
+                Ok(Effect::DrawCards {
+                    count,
+                })
+            },
+            "gain_life" => {
+                let amount = effect.amount
+                    .ok_or_else(|| self.error_at(entry.span(), CardLoadErrorKind::Invalid("gain_life needs an amount")))?;
+
+                Ok(Effect::GainLife {
+                    amount,
+                })
+            },
+            "damage_each_opponent" => {
+                let amount = effect.amount
+                    .ok_or_else(|| self.error_at(entry.span(), CardLoadErrorKind::Invalid("damage_each_opponent needs an amount")))?;
+
+                Ok(Effect::DamageEachOpponent {
+                    amount,
+                })
+            },
+            _ => Err(self.unknown_keyword(&effect.kind)),
+        }
+    }
+
+    /// Reads one of the continuous effects a static ability has.
+    fn continuous_effect(&self, entry: &Spanned<EffectEntry>) -> Result<ContinuousEffectKind, CardLoadError> {
+        let effect = entry.get_ref();
+
+        let power_toughness = |message| match (effect.power, effect.toughness) {
+            (Some(power), Some(toughness)) => Ok((power, toughness)),
+            _ => Err(self.error_at(entry.span(), CardLoadErrorKind::Invalid(message))),
+        };
+
+        match effect.kind.get_ref().as_str() {
+            "add_types" => Ok(ContinuousEffectKind::AddTypes {
+                card_types: self.card_types(&effect.types)?,
+                subtypes: effect.subtypes.clone(),
+            }),
+            "set_types" => Ok(ContinuousEffectKind::SetTypes {
+                card_types: self.card_types(&effect.types)?,
+                subtypes: effect.subtypes.clone(),
+            }),
+            "set_basic_land_type" => {
+                let subtype = effect.subtype.as_ref()
+                    .ok_or_else(|| self.error_at(entry.span(), CardLoadErrorKind::Invalid("set_basic_land_type needs a subtype")))?;
+
+                let mana_type = ManaType::from_basic_land_type(subtype)
+                    .ok_or_else(|| self.error_at(entry.span(), CardLoadErrorKind::UnknownKeyword(subtype.clone())))?;
+
+                Ok(ContinuousEffectKind::SetBasicLandType {
+                    subtype: subtype.clone(),
+                    ability_id: get_id(),
+                    mana_type,
+                })
+            },
+            "set_colors" => Ok(ContinuousEffectKind::SetColors {
+                colors: self.colors(&effect.colors)?,
+            }),
+            "add_colors" => Ok(ContinuousEffectKind::AddColors {
+                colors: self.colors(&effect.colors)?,
+            }),
+            "remove_all_abilities" => Ok(ContinuousEffectKind::RemoveAllAbilities),
+            "set_power_toughness" => {
+                let (power, toughness) = power_toughness("set_power_toughness needs a power and toughness")?;
+
+                Ok(ContinuousEffectKind::SetPowerToughness {
+                    power,
+                    toughness,
+                })
+            },
+            "count_power_toughness" => {
+                let counted = effect.counted.as_ref()
+                    .ok_or_else(|| self.error_at(entry.span(), CardLoadErrorKind::Invalid("count_power_toughness needs what it counted")))?;
+
+                Ok(ContinuousEffectKind::CountPowerToughness {
+                    counted: self.filter(counted)?,
+                })
+            },
+            "set_power_toughness_to_mana_value" => Ok(ContinuousEffectKind::SetPowerToughnessToManaValue),
+            "modify_power_toughness" => {
+                let (power, toughness) = power_toughness("modify_power_toughness needs a power and toughness")?;
+
+                Ok(ContinuousEffectKind::ModifyPowerToughness {
+                    power,
+                    toughness,
+                })
+            },
+            "switch_power_toughness" => Ok(ContinuousEffectKind::SwitchPowerToughness),
+            _ => Err(self.unknown_keyword(&effect.kind)),
+        }
+    }
+
+    fn stat(&self, entry: &Option<Spanned<StatEntry>>) -> Result<Option<StatValue>, CardLoadError> {
+        let entry = match *entry {
+            Some(ref entry) => entry,
+            None => return Ok(None),
+        };
+
+        let text = match *entry.get_ref() {
+            StatEntry::Number(value) => return Ok(Some(StatValue::Fixed(value))),
+            StatEntry::Text(ref text) => text,
+        };
+
+        let unknown = || self.error_at(entry.span(), CardLoadErrorKind::UnknownKeyword(text.clone()));
+
+        if let Ok(value) = text.parse() {
+            return Ok(Some(StatValue::Fixed(value)));
+        }
+
+        // `*`, `X`, `1+*`, `*+1`, `*-1`, and so on. Subtracted numbers are
+        // split off as negative terms.
+        let mut modifier = 0;
+        let mut has_variable = false;
+
+        for (index, term) in text.replace('-', "+-").split('+').map(|term| term.trim()).enumerate() {
+            match term {
+                "*" | "X" if !has_variable => has_variable = true,
+                // A leading `-`, like in `-1+*`.
+                "" if index == 0 && text.trim_start().starts_with('-') => {},
+                _ => modifier += term.parse::<i32>().map_err(|_| unknown())?,
+            }
+        }
+
+        if !has_variable {
+            return Err(unknown());
+        }
+
+        Ok(Some(StatValue::Variable(modifier)))
+    }
+}
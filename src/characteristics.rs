@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use id::Id;
+use mana::Color;
+use mana_cost::ManaCost;
+use object::Ability;
+
+/// The supertypes an object can have (rule 205.4).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Supertype {
+    Basic,
+    Legendary,
+    Ongoing,
+    Snow,
+    World,
+}
+
+impl Supertype {
+    /// Finds the supertype with the given name, like `Legendary`.
+    pub fn from_name(name: &str) -> Option<Supertype> {
+        match name {
+            "Basic" => Some(Supertype::Basic),
+            "Legendary" => Some(Supertype::Legendary),
+            "Ongoing" => Some(Supertype::Ongoing),
+            "Snow" => Some(Supertype::Snow),
+            "World" => Some(Supertype::World),
+            _ => None,
+        }
+    }
+}
+
+/// The card types an object can have (rule 300.1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CardType {
+    Artifact,
+    Battle,
+    Conspiracy,
+    Creature,
+    Dungeon,
+    Enchantment,
+    Instant,
+    Kindred,
+    Land,
+    Phenomenon,
+    Plane,
+    Planeswalker,
+    Scheme,
+    Sorcery,
+    Vanguard,
+}
+
+impl CardType {
+    /// Finds the card type with the given name, like `Creature`.
+    pub fn from_name(name: &str) -> Option<CardType> {
+        match name {
+            "Artifact" => Some(CardType::Artifact),
+            "Battle" => Some(CardType::Battle),
+            "Conspiracy" => Some(CardType::Conspiracy),
+            "Creature" => Some(CardType::Creature),
+            "Dungeon" => Some(CardType::Dungeon),
+            "Enchantment" => Some(CardType::Enchantment),
+            "Instant" => Some(CardType::Instant),
+            "Kindred" => Some(CardType::Kindred),
+            "Land" => Some(CardType::Land),
+            "Phenomenon" => Some(CardType::Phenomenon),
+            "Plane" => Some(CardType::Plane),
+            "Planeswalker" => Some(CardType::Planeswalker),
+            "Scheme" => Some(CardType::Scheme),
+            "Sorcery" => Some(CardType::Sorcery),
+            "Vanguard" => Some(CardType::Vanguard),
+            _ => None,
+        }
+    }
+
+    /// Whether objects of this type are permanents, meaning they go to the
+    /// battlefield as they resolve (rule 110.4).
+    pub fn is_permanent(&self) -> bool {
+        matches!(
+            *self,
+            CardType::Artifact
+                | CardType::Battle
+                | CardType::Creature
+                | CardType::Enchantment
+                | CardType::Land
+                | CardType::Planeswalker
+        )
+    }
+}
+
+/// The land types (rule 205.3i). The first five are the basic land types.
+pub const LAND_TYPES: &[&str] = &[
+    "Plains", "Island", "Swamp", "Mountain", "Forest",
+    "Cave", "Desert", "Gate", "Lair", "Locus", "Mine", "Power-Plant", "Sphere", "Tower", "Town", "Urza's",
+];
+
+/// A printed power, toughness, loyalty, or defense value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatValue {
+    Fixed(i32),
+
+    /// A value involving `*` or `X`, like Tarmogoyf's `*/1+*`. The number is
+    /// whatever is added to the `*`, usually 0.
+    Variable(i32),
+}
+
+impl StatValue {
+    /// The value this stat has on its own.
+    ///
+    /// `*` is defined by a characteristic-defining ability in layer 7a, and
+    /// counts as 0 wherever that ability doesn't apply.
+    pub fn value(&self) -> i32 {
+        match *self {
+            StatValue::Fixed(value) => value,
+            StatValue::Variable(modifier) => modifier,
+        }
+    }
+}
+
+/// The characteristics of an object (rule 109.3), which describe what a card,
+/// spell, or permanent is.
+///
+/// Every characteristic is available wherever the object is: a creature spell
+/// on the stack still has a power, even though power usually only matters on
+/// the battlefield. Characteristics an object doesn't have, like the power of
+/// a land, are empty.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Characteristics {
+    pub name: String,
+    pub mana_cost: Option<ManaCost>,
+
+    /// The colors of the object's color indicator, for objects whose colors
+    /// aren't given by their mana cost, like the back faces of transforming
+    /// cards (rule 204).
+    pub color_indicator: Vec<Color>,
+
+    /// The object's colors, if an effect has set them, like Painter's
+    /// Servant's. Takes the place of the colors from its mana cost and color
+    /// indicator (rule 105.3).
+    pub color_override: Option<Vec<Color>>,
+
+    pub supertypes: Vec<Supertype>,
+    pub card_types: Vec<CardType>,
+
+    /// Subtypes, like `Forest` or `Elf`. There are far too many to list, and
+    /// new ones are printed all the time, so they're kept as text.
+    pub subtypes: Vec<String>,
+
+    /// The object's rules text, as printed.
+    pub rules_text: String,
+
+    /// The abilities the object has, which are what its rules text means to
+    /// the engine.
+    pub abilities: HashMap<Id, Ability>,
+
+    pub power: Option<StatValue>,
+    pub toughness: Option<StatValue>,
+    pub loyalty: Option<StatValue>,
+    pub defense: Option<StatValue>,
+}
+
+impl Characteristics {
+    /// The characteristics of a face-down permanent: a 2/2 creature with no
+    /// name, mana cost, subtypes, or abilities (rule 708.2a).
+    pub fn face_down() -> Characteristics {
+        Characteristics {
+            card_types: vec![CardType::Creature],
+            power: Some(StatValue::Fixed(2)),
+            toughness: Some(StatValue::Fixed(2)),
+            ..Characteristics::default()
+        }
+    }
+
+    /// The object's colors, from the symbols in its mana cost and its color
+    /// indicator (rule 202.2), or from effects that change them, in WUBRG
+    /// order.
+    // TODO: Characteristic-defining abilities, like Transguild Courier's
+    pub fn colors(&self) -> Vec<Color> {
+        if let Some(ref colors) = self.color_override {
+            return Color::all().iter()
+                .cloned()
+                .filter(|color| colors.contains(color))
+                .collect();
+        }
+
+        Color::all().iter()
+            .cloned()
+            .filter(|&color| {
+                self.color_indicator.contains(&color)
+                    || self.mana_cost.as_ref().is_some_and(|cost| cost.has_color(color))
+            })
+            .collect()
+    }
+
+    pub fn has_supertype(&self, supertype: Supertype) -> bool {
+        self.supertypes.contains(&supertype)
+    }
+
+    pub fn has_card_type(&self, card_type: CardType) -> bool {
+        self.card_types.contains(&card_type)
+    }
+
+    pub fn has_subtype(&self, subtype: &str) -> bool {
+        self.subtypes.iter().any(|existing| existing == subtype)
+    }
+
+    /// Whether an object with these characteristics is a permanent, or would
+    /// become one as it resolves.
+    pub fn is_permanent(&self) -> bool {
+        self.card_types.iter().any(|card_type| card_type.is_permanent())
+    }
+}
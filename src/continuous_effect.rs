@@ -0,0 +1,350 @@
+use std::mem;
+
+use characteristics::{CardType, Characteristics, StatValue, LAND_TYPES};
+use id::Id;
+use mana::{Color, ManaType};
+use object::{Ability, ObjectFilter};
+use timestamp::Timestamp;
+
+/// How long a continuous effect lasts (rule 611.2). The game ends effects
+/// automatically once their duration is up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duration {
+    /// Ends during the cleanup step, like Threaten's.
+    UntilEndOfTurn,
+
+    /// Ends as the given player's next turn begins, like the effect of Gideon
+    /// Jura's first ability.
+    UntilYourNextTurn(Id),
+
+    /// Lasts for as long as the given object stays on the battlefield, like the
+    /// effect of Mind Control's static ability.
+    WhileOnBattlefield(Id),
+
+    /// Lasts for as long as the given player controls the given object, like
+    /// Olivia Voldaren stealing a Vampire.
+    WhileControlled {
+        controller: Id,
+        object_id: Id,
+    },
+
+    /// Never ends on its own, like Treachery stealing a creature.
+    Indefinite,
+}
+
+/// The layers continuous effects are applied in, from first to last
+/// (rule 613.1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Layer {
+    /// Layer 1: Copy effects.
+    Copy,
+
+    /// Layer 2: Control-changing effects.
+    Control,
+
+    /// Layer 3: Text-changing effects.
+    Text,
+
+    /// Layer 4: Type-changing effects.
+    Type,
+
+    /// Layer 5: Color-changing effects.
+    Color,
+
+    /// Layer 6: Effects that add or remove abilities.
+    Ability,
+
+    /// Layer 7a: Characteristic-defining abilities that define power and
+    /// toughness.
+    CharacteristicDefining,
+
+    /// Layer 7b: Effects that set power and toughness to specific values.
+    SetPowerToughness,
+
+    /// Layer 7c: Effects and counters that modify power and toughness.
+    ModifyPowerToughness,
+
+    /// Layer 7d: Effects that switch power and toughness.
+    SwitchPowerToughness,
+}
+
+impl Layer {
+    /// Every layer, in the order they're applied.
+    pub fn all() -> [Layer; 10] {
+        [
+            Layer::Copy,
+            Layer::Control,
+            Layer::Text,
+            Layer::Type,
+            Layer::Color,
+            Layer::Ability,
+            Layer::CharacteristicDefining,
+            Layer::SetPowerToughness,
+            Layer::ModifyPowerToughness,
+            Layer::SwitchPowerToughness,
+        ]
+    }
+}
+
+/// The ways continuous effects can change an object.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContinuousEffectKind {
+    /// The object becomes a copy of something else, with the given copiable
+    /// values, like a Clone (rule 707.2).
+    BecomeCopy {
+        characteristics: Box<Characteristics>,
+    },
+
+    /// The given player controls the object instead of its usual controller.
+    ChangeControl {
+        controller: Id,
+    },
+
+    /// Replaces every instance of a word in the object's rules text with
+    /// another, like with Mind Bend. The word is replaced in what the object's
+    /// abilities refer to as well, so an Aura with `Enchant Swamp` enchants
+    /// Islands instead.
+    ChangeText {
+        from: String,
+        to: String,
+    },
+
+    /// The object gets these card types and subtypes in addition to its own.
+    AddTypes {
+        card_types: Vec<CardType>,
+        subtypes: Vec<String>,
+    },
+
+    /// The object's card types and subtypes are replaced with these, like with
+    /// Spreading Seas. Supertypes are kept.
+    SetTypes {
+        card_types: Vec<CardType>,
+        subtypes: Vec<String>,
+    },
+
+    /// The land's land types are replaced with a basic land type, like with
+    /// Blood Moon. It loses the abilities from its rules text and gains the
+    /// basic land type's mana ability instead, which has the given ID on it
+    /// (rule 305.7).
+    SetBasicLandType {
+        subtype: String,
+        ability_id: Id,
+        mana_type: ManaType,
+    },
+
+    /// The object becomes exactly these colors. An empty list makes it
+    /// colorless.
+    SetColors {
+        colors: Vec<Color>,
+    },
+
+    /// The object gets these colors in addition to its own.
+    AddColors {
+        colors: Vec<Color>,
+    },
+
+    /// The object gains an ability, which has the given ID on it.
+    AddAbility {
+        ability_id: Id,
+        ability: Ability,
+    },
+
+    /// The object loses all of its abilities.
+    RemoveAllAbilities,
+
+    /// The object has the given base power and toughness, like with Turn to
+    /// Frog.
+    SetPowerToughness {
+        power: i32,
+        toughness: i32,
+    },
+
+    /// The object's power and toughness are each equal to the number of
+    /// permanents matching the filter, like Molimo, Maro-Sorcerer's `Molimo's
+    /// power and toughness are each equal to the number of lands you control.`
+    ///
+    /// This is a characteristic-defining ability, so it only defines the `*`
+    /// in the object's own power and toughness, whatever its static ability
+    /// says it affects, and works from every zone (rule 604.3). The game does
+    /// the counting as it applies the effect.
+    CountPowerToughness {
+        counted: ObjectFilter,
+    },
+
+    /// The object's base power and toughness are each equal to its mana value,
+    /// like with Opalescence.
+    SetPowerToughnessToManaValue,
+
+    /// The object gets +X/+Y, like with Giant Growth.
+    ModifyPowerToughness {
+        power: i32,
+        toughness: i32,
+    },
+
+    /// The object's power and toughness are switched, like with Twisted Image.
+    SwitchPowerToughness,
+}
+
+impl ContinuousEffectKind {
+    /// The player this effect gives control to, if it changes control.
+    pub fn new_controller(&self) -> Option<Id> {
+        match *self {
+            ContinuousEffectKind::ChangeControl { controller } => Some(controller),
+            _ => None,
+        }
+    }
+
+    /// The layer this kind of effect is applied in.
+    pub fn layer(&self) -> Layer {
+        match *self {
+            ContinuousEffectKind::BecomeCopy { .. } => Layer::Copy,
+            ContinuousEffectKind::ChangeControl { .. } => Layer::Control,
+            ContinuousEffectKind::ChangeText { .. } => Layer::Text,
+            ContinuousEffectKind::AddTypes { .. }
+            | ContinuousEffectKind::SetTypes { .. }
+            | ContinuousEffectKind::SetBasicLandType { .. } => Layer::Type,
+            ContinuousEffectKind::SetColors { .. }
+            | ContinuousEffectKind::AddColors { .. } => Layer::Color,
+            ContinuousEffectKind::AddAbility { .. }
+            | ContinuousEffectKind::RemoveAllAbilities => Layer::Ability,
+            ContinuousEffectKind::CountPowerToughness { .. } => Layer::CharacteristicDefining,
+            ContinuousEffectKind::SetPowerToughness { .. }
+            | ContinuousEffectKind::SetPowerToughnessToManaValue => Layer::SetPowerToughness,
+            ContinuousEffectKind::ModifyPowerToughness { .. } => Layer::ModifyPowerToughness,
+            ContinuousEffectKind::SwitchPowerToughness => Layer::SwitchPowerToughness,
+        }
+    }
+
+    /// Applies this effect to an object's characteristics. Control isn't a
+    /// characteristic, so control-changing effects don't do anything here.
+    pub fn apply(&self, characteristics: &mut Characteristics) {
+        match *self {
+            ContinuousEffectKind::BecomeCopy { characteristics: ref copied } => {
+                *characteristics = (**copied).clone();
+            },
+            ContinuousEffectKind::ChangeControl { .. } => {},
+            ContinuousEffectKind::ChangeText { ref from, ref to } => {
+                characteristics.rules_text = characteristics.rules_text.replace(from.as_str(), to);
+
+                for ability in characteristics.abilities.values_mut() {
+                    ability.change_text(from, to);
+                }
+            },
+            ContinuousEffectKind::AddTypes { ref card_types, ref subtypes } => {
+                for card_type in card_types {
+                    if !characteristics.card_types.contains(card_type) {
+                        characteristics.card_types.push(*card_type);
+                    }
+                }
+
+                for subtype in subtypes {
+                    if !characteristics.subtypes.contains(subtype) {
+                        characteristics.subtypes.push(subtype.clone());
+                    }
+                }
+            },
+            ContinuousEffectKind::SetTypes { ref card_types, ref subtypes } => {
+                characteristics.card_types = card_types.clone();
+                characteristics.subtypes = subtypes.clone();
+            },
+            ContinuousEffectKind::SetBasicLandType { ref subtype, ability_id, mana_type } => {
+                if !characteristics.has_card_type(CardType::Land) {
+                    return;
+                }
+
+                characteristics.subtypes.retain(|existing| !LAND_TYPES.contains(&existing.as_str()));
+                characteristics.subtypes.push(subtype.clone());
+
+                characteristics.abilities.clear();
+                characteristics.abilities.insert(ability_id, Ability::tap_for_mana(mana_type));
+            },
+            ContinuousEffectKind::SetColors { ref colors } => {
+                characteristics.color_override = Some(colors.clone());
+            },
+            ContinuousEffectKind::AddColors { ref colors } => {
+                let mut new_colors = characteristics.colors();
+                new_colors.extend(colors.iter().cloned());
+
+                characteristics.color_override = Some(new_colors);
+            },
+            ContinuousEffectKind::AddAbility { ability_id, ref ability } => {
+                characteristics.abilities.insert(ability_id, ability.clone());
+            },
+            ContinuousEffectKind::RemoveAllAbilities => {
+                characteristics.abilities.clear();
+            },
+            // Needs the rest of the game, so `Game` counts and applies it.
+            ContinuousEffectKind::CountPowerToughness { .. } => {},
+            ContinuousEffectKind::SetPowerToughness { power, toughness } => {
+                characteristics.power = Some(StatValue::Fixed(power));
+                characteristics.toughness = Some(StatValue::Fixed(toughness));
+            },
+            ContinuousEffectKind::SetPowerToughnessToManaValue => {
+                let mana_value = characteristics.mana_cost.as_ref()
+                    .map_or(0, |cost| cost.mana_value() as i32);
+
+                characteristics.power = Some(StatValue::Fixed(mana_value));
+                characteristics.toughness = Some(StatValue::Fixed(mana_value));
+            },
+            ContinuousEffectKind::ModifyPowerToughness { power, toughness } => {
+                characteristics.power = characteristics.power
+                    .map(|value| StatValue::Fixed(value.value() + power));
+                characteristics.toughness = characteristics.toughness
+                    .map(|value| StatValue::Fixed(value.value() + toughness));
+            },
+            ContinuousEffectKind::SwitchPowerToughness => {
+                mem::swap(&mut characteristics.power, &mut characteristics.toughness);
+            },
+        }
+    }
+
+    /// Replaces every instance of the word `from` in what this effect refers
+    /// to with `to`, for a text-changing effect applied to the ability that
+    /// generates it.
+    pub fn change_text(&mut self, from: &str, to: &str) {
+        match *self {
+            ContinuousEffectKind::AddTypes { ref mut subtypes, .. }
+            | ContinuousEffectKind::SetTypes { ref mut subtypes, .. } => {
+                for subtype in subtypes.iter_mut() {
+                    if subtype == from {
+                        *subtype = to.to_string();
+                    }
+                }
+            },
+            ContinuousEffectKind::SetBasicLandType { ref mut subtype, ref mut mana_type, .. } => {
+                // The new basic land type comes with its own mana ability.
+                if let (true, Some(new_mana_type)) = (subtype == from, ManaType::from_basic_land_type(to)) {
+                    *subtype = to.to_string();
+                    *mana_type = new_mana_type;
+                }
+            },
+            ContinuousEffectKind::AddAbility { ref mut ability, .. } => {
+                ability.change_text(from, to);
+            },
+            ContinuousEffectKind::CountPowerToughness { ref mut counted } => {
+                counted.change_text(from, to);
+            },
+            _ => {},
+        }
+    }
+}
+
+/// An effect that changes an object for a while, rather than happening once
+/// (rule 611).
+///
+/// Continuous effects don't change the objects they affect. Instead, the game
+/// keeps a list of them and applies them whenever it looks at an object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContinuousEffect {
+    pub id: Id,
+
+    /// The object the effect applies to.
+    pub object_id: Id,
+
+    /// When the effect began. Effects in the same layer are applied in
+    /// timestamp order, so the newest one wins (rule 613.7).
+    pub timestamp: Timestamp,
+
+    pub duration: Duration,
+    pub kind: ContinuousEffectKind,
+}
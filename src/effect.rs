@@ -0,0 +1,49 @@
+use continuous_effect::Duration;
+use game::DamageTarget;
+use id::Id;
+
+/// Describes one thing a spell or ability does as it resolves.
+///
+/// Effects are performed in order, on behalf of the controller of the spell or
+/// ability they belong to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effect {
+    /// The controller draws `count` cards.
+    DrawCards {
+        count: usize,
+    },
+
+    /// The controller gains `amount` life.
+    GainLife {
+        amount: usize,
+    },
+
+    /// The controller gets an emblem with the abilities of the given
+    /// definition, like with a planeswalker's ultimate ability.
+    CreateEmblem {
+        definition_id: Id,
+    },
+
+    /// The controller gains control of an object, like with Threaten.
+    // TODO: Choose the object as a target instead
+    GainControl {
+        object_id: Id,
+        duration: Duration,
+    },
+
+    /// The spell or ability's source deals `amount` damage to a player or
+    /// object, like with Shock.
+    // TODO: Choose the player or object as a target instead
+    DealDamage {
+        target: DamageTarget,
+        amount: usize,
+    },
+
+    /// The spell or ability's source deals `amount` damage to each of its
+    /// controller's opponents, like with Guttersnipe.
+    DamageEachOpponent {
+        amount: usize,
+    },
+
+    // TODO: Targets, and many, many more effects
+}
@@ -1,55 +1,296 @@
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
-use object::{Ability, Object, ObjectDetails};
+use effect::Effect;
+use card_definition::{CardLayout, CardRegistry, Face};
+use characteristics::{CardType, Characteristics, StatValue, Supertype};
+use continuous_effect::{ContinuousEffect, ContinuousEffectKind, Duration, Layer};
+use object::{Ability, ActivationCost, LastKnownInformation, Object, ObjectCounter, ObjectDetails, ObjectFilter, ObjectView, PermanentStatus, StaticAbility, Trigger};
 use id::{Id, get_id};
-use player::Player;
-use timestamp::get_timestamp;
+use mana::{ManaError, ManaPool, ManaType};
+use mana_cost::ManaCost;
+use payment::{ManaSource, PaymentPlan, SourceActivation, pays_exactly, solve_payment};
+use player::{LETHAL_POISON_COUNTERS, Player, PlayerCounter};
+use replacement_effect::{DamageFilter, ReplacementEffect, ReplacementEffectKind};
+use timestamp::{Timestamp, get_timestamp};
 use zone::{Zone, ZoneDetails};
 
-/// Represents the game's current phase
-#[derive(Debug, Clone, PartialEq)]
+/// Represents the game's current step, as defined in the game rules.
+///
+/// The rules split a turn into five phases, three of which are further split
+/// into steps. The engine only ever cares about the innermost of the two, so
+/// they're flattened into a single list here, in turn order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GamePhase {
+    // Beginning phase
     Untap,
-    Main,
+    Upkeep,
+    Draw,
+
+    PrecombatMain,
+
+    // Combat phase
+    BeginningOfCombat,
+    DeclareAttackers,
+    DeclareBlockers,
+    CombatDamage,
+    EndOfCombat,
 
-    // TODO: Other game phases
+    PostcombatMain,
+
+    // Ending phase
+    End,
+    Cleanup,
 }
 
 impl GamePhase {
     /// Find the next phase for the game, if there is one.
     ///
     /// Returning `None` signifies that the turn should advance.
+    ///
+    /// This is the usual order of steps. `Game` skips some of them depending on
+    /// what happens during the turn, like when nothing attacks.
     pub fn next(&self) -> Option<GamePhase> {
         match *self {
-            GamePhase::Untap => Some(GamePhase::Main),
-            GamePhase::Main => None,
+            GamePhase::Untap => Some(GamePhase::Upkeep),
+            GamePhase::Upkeep => Some(GamePhase::Draw),
+            GamePhase::Draw => Some(GamePhase::PrecombatMain),
+            GamePhase::PrecombatMain => Some(GamePhase::BeginningOfCombat),
+            GamePhase::BeginningOfCombat => Some(GamePhase::DeclareAttackers),
+            GamePhase::DeclareAttackers => Some(GamePhase::DeclareBlockers),
+            GamePhase::DeclareBlockers => Some(GamePhase::CombatDamage),
+            GamePhase::CombatDamage => Some(GamePhase::EndOfCombat),
+            GamePhase::EndOfCombat => Some(GamePhase::PostcombatMain),
+            GamePhase::PostcombatMain => Some(GamePhase::End),
+            GamePhase::End => Some(GamePhase::Cleanup),
+            GamePhase::Cleanup => None,
         }
     }
+
+    /// Whether players normally receive priority during this step.
+    ///
+    /// No player gets priority during the untap step (rule 502.4) or, unless
+    /// something triggers, the cleanup step (rule 514.3).
+    pub fn grants_priority(&self) -> bool {
+        !matches!(*self, GamePhase::Untap | GamePhase::Cleanup)
+    }
+
+    /// Whether this is one of the two main phases.
+    pub fn is_main_phase(&self) -> bool {
+        matches!(*self, GamePhase::PrecombatMain | GamePhase::PostcombatMain)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum GameStatus {
     NeedsPlayerAction,
 
-    // TODO: Represent winners and potential draw
-    Ended,
+    /// The game is waiting on a choice that the rules demand of a specific
+    /// player, outside of the normal flow of priority.
+    NeedsPlayerChoice(PlayerChoice),
+
+    /// The game is over, with the given result.
+    Ended(GameResult),
 
     // TODO: Represent unrecoverable internal error?
 }
 
+/// Describes how a finished game turned out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameResult {
+    /// The players who won the game. Empty if the game was a draw.
+    pub winners: Vec<Id>,
+
+    /// Every player who lost the game, in the order they left it.
+    pub losers: Vec<Id>,
+}
+
+impl GameResult {
+    /// Whether the game ended in a draw, like when every remaining player
+    /// loses at the same time (rule 104.4a).
+    pub fn is_draw(&self) -> bool {
+        self.winners.is_empty()
+    }
+}
+
+/// Describes a choice that the game is waiting on a player to make.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerChoice {
+    /// The player must discard `count` cards from their hand, like when
+    /// discarding down to their maximum hand size during the cleanup step.
+    ///
+    /// Answered with `PlayerAction::Discard`.
+    Discard {
+        player_id: Id,
+        count: usize,
+    },
+
+    /// The player controls two or more legendary permanents with the same
+    /// name, and must choose one of them to keep. The rest are put into their
+    /// owners' graveyards (rule 704.5j).
+    ///
+    /// Answered with `PlayerAction::KeepLegend`.
+    KeepLegend {
+        player_id: Id,
+        object_ids: Vec<Id>,
+    },
+
+    /// More than one replacement effect would change the same event, and the
+    /// affected player, or the controller of the affected object, must choose
+    /// which applies first (rule 616.1). The event waits until they do.
+    ///
+    /// Effects are identified by their ID if a spell or ability created them,
+    /// or by the ID of the ability that generates them otherwise.
+    ///
+    /// Answered with `PlayerAction::ChooseReplacement`.
+    ReplacementOrder {
+        player_id: Id,
+        effect_ids: Vec<Id>,
+    },
+
+    /// The active player declares which of their creatures attack, and which
+    /// player each one attacks, as the declare attackers step begins
+    /// (rule 508.1).
+    ///
+    /// Answered with `PlayerAction::DeclareAttackers`.
+    DeclareAttackers {
+        player_id: Id,
+    },
+
+    /// A player being attacked declares which of their creatures block, and
+    /// which attacking creature each one blocks, as the declare blockers step
+    /// begins (rule 509.1).
+    ///
+    /// Answered with `PlayerAction::DeclareBlockers`.
+    DeclareBlockers {
+        player_id: Id,
+    },
+
+    /// The player is casting the given spell or activating the given ability,
+    /// which is already on the stack, and couldn't pay its mana cost
+    /// automatically. They can activate mana abilities to pay for it with
+    /// `PlayerAction::ActivateManaAbility`, even though they don't have
+    /// priority (rule 605.3a), like a Treasure's that automatic payment
+    /// wouldn't sacrifice.
+    ///
+    /// `cost` is everything still to be paid: a spell's mana cost, or an
+    /// ability's whole activation cost. `cast_from` is the zone a spell was
+    /// cast from, which it goes back to if the player cancels, and is `None`
+    /// for an ability.
+    ///
+    /// Answered with `PlayerAction::FinishPayment` or
+    /// `PlayerAction::CancelPayment`.
+    PayMana {
+        player_id: Id,
+        object_id: Id,
+        cost: ActivationCost,
+        cast_from: Option<Id>,
+    },
+}
+
+impl PlayerChoice {
+    /// The player the game is waiting on to make the choice.
+    pub fn player_id(&self) -> Id {
+        match *self {
+            PlayerChoice::Discard { player_id, .. }
+            | PlayerChoice::KeepLegend { player_id, .. }
+            | PlayerChoice::ReplacementOrder { player_id, .. }
+            | PlayerChoice::DeclareAttackers { player_id }
+            | PlayerChoice::DeclareBlockers { player_id }
+            | PlayerChoice::PayMana { player_id, .. } => player_id,
+        }
+    }
+}
+
 /// Defines all of the actions that a player can take when they have
 #[derive(Debug, Clone)]
 pub enum PlayerAction {
     Concede,
     PassPriority,
+    /// Plays a land from the player's hand. `face` picks which face of a modal
+    /// double-faced card to play, and should be `None` for other cards.
     PlayLand {
         object_id: Id,
+        face: Option<Face>,
+    },
+
+    /// Casts a spell from the player's hand, paying its mana cost
+    /// automatically like `ActivateAbility` does. If that isn't possible, the
+    /// spell waits on the stack for the player to pay with a
+    /// `PlayerChoice::PayMana`.
+    ///
+    /// `face` picks which face of a multi-faced card to cast: the half of a
+    /// split card, the back face of a modal double-faced card, or an
+    /// adventurer's Adventure. `None` casts a card normally, or both halves of
+    /// a split card with fuse.
+    CastSpell {
+        object_id: Id,
+        face: Option<Face>,
     },
+    /// Activates an ability that isn't a mana ability, putting it on the
+    /// stack. Any mana in its cost is paid automatically, spending mana from
+    /// the player's mana pool before activating their mana sources. If that
+    /// isn't possible, the ability waits on the stack for the player to pay
+    /// with a `PlayerChoice::PayMana`.
     ActivateAbility {
         object_id: Id,
         ability_id: Id,
     },
 
+    /// Activates a mana ability, which resolves immediately and adds one mana
+    /// of the chosen type to the player's mana pool. Doesn't require priority
+    /// while the player is paying for a spell or ability with a
+    /// `PlayerChoice::PayMana`.
+    ActivateManaAbility {
+        object_id: Id,
+        ability_id: Id,
+        mana_type: ManaType,
+    },
+
+    /// Answers a `PlayerChoice::Discard`. Doesn't require priority.
+    Discard {
+        object_ids: Vec<Id>,
+    },
+
+    /// Answers a `PlayerChoice::KeepLegend` with the legend to keep. Doesn't
+    /// require priority.
+    KeepLegend {
+        object_id: Id,
+    },
+
+    /// Answers a `PlayerChoice::ReplacementOrder` with the effect to apply
+    /// first. Doesn't require priority.
+    ChooseReplacement {
+        effect_id: Id,
+    },
+
+    /// Answers a `PlayerChoice::DeclareAttackers` with each attacking creature
+    /// and the player it attacks. Creatures that aren't listed don't attack.
+    // TODO: Attacking planeswalkers and battles
+    DeclareAttackers {
+        attackers: Vec<(Id, Id)>,
+    },
+
+    /// Answers a `PlayerChoice::DeclareBlockers` with each blocking creature
+    /// and the attacking creature it blocks. Creatures that aren't listed
+    /// don't block.
+    DeclareBlockers {
+        blockers: Vec<(Id, Id)>,
+    },
+
+    /// Answers a `PlayerChoice::PayMana` by paying the rest of the cost,
+    /// automatically like `CastSpell` and `ActivateAbility` do, and finishing
+    /// casting the spell or activating the ability. Fails if the cost still
+    /// can't be paid, leaving the choice open.
+    FinishPayment,
+
+    /// Answers a `PlayerChoice::PayMana` by giving up on casting the spell,
+    /// returning it to the zone it was cast from, or on activating the
+    /// ability, removing it from the stack. The mana abilities activated while
+    /// paying are undone too.
+    CancelPayment,
+
     // TODO: Other possible player actions
 
     // Some potentially interesting actions:
@@ -63,12 +304,268 @@ pub enum PlayerActionError {
     ///
     /// TODO: Break this into more variants as needed
     NotAllowed(&'static str),
+
+    /// A player couldn't add or spend mana.
+    Mana(ManaError),
+}
+
+impl From<ManaError> for PlayerActionError {
+    fn from(error: ManaError) -> PlayerActionError {
+        PlayerActionError::Mana(error)
+    }
+}
+
+/// Something damage can be dealt to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageTarget {
+    Player(Id),
+
+    /// A permanent, like a creature or planeswalker.
+    Object(Id),
+}
+
+/// What an attacking creature is attacking, and what's blocking it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attack {
+    /// The player the creature is attacking.
+    pub defending_player: Id,
+
+    /// The creatures blocking it, in the order it assigns combat damage to
+    /// them.
+    pub blockers: Vec<Id>,
+
+    /// Whether the creature was blocked. It stays blocked even if every
+    /// creature blocking it is removed from combat (rule 509.1h).
+    pub blocked: bool,
 }
 
 /// Will be used to define mutations to the game state. Mutations are defined as
 /// objects so that effects can respond to and replace them.
-#[derive(Debug, Clone)]
+///
+/// Every mutation goes through `Game::apply_mutation`, which lets replacement
+/// effects change it first.
+#[derive(Debug, Clone, PartialEq)]
 pub enum GameMutation {
+    /// The player gains `amount` life.
+    GainLife {
+        player_id: Id,
+        amount: usize,
+    },
+
+    /// The player loses `amount` life. Paying life is also a loss of life
+    /// (rule 119.4), so it goes through this mutation as well.
+    LoseLife {
+        player_id: Id,
+        amount: usize,
+    },
+
+    /// The player gets `amount` counters of the given kind.
+    AddPlayerCounters {
+        player_id: Id,
+        kind: PlayerCounter,
+        amount: usize,
+    },
+
+    /// The player has up to `amount` counters of the given kind removed.
+    RemovePlayerCounters {
+        player_id: Id,
+        kind: PlayerCounter,
+        amount: usize,
+    },
+
+    /// The object gets `amount` counters of the given kind.
+    AddCounters {
+        object_id: Id,
+        kind: ObjectCounter,
+        amount: usize,
+    },
+
+    /// The object has up to `amount` counters of the given kind removed.
+    RemoveCounters {
+        object_id: Id,
+        kind: ObjectCounter,
+        amount: usize,
+    },
+
+    /// The player draws the top card of their library.
+    DrawCard {
+        player_id: Id,
+    },
+
+    /// The source deals `amount` damage to a player or object. `combat` says
+    /// whether it's combat damage.
+    DealDamage {
+        source_id: Id,
+        target: DamageTarget,
+        amount: usize,
+        combat: bool,
+    },
+
+    /// The object moves to another zone, becoming a new object there. `face`
+    /// is the face it has up in its new zone, and `tapped` says whether it
+    /// enters the battlefield tapped.
+    MoveObject {
+        object_id: Id,
+        zone_id: Id,
+        face: Option<Face>,
+        tapped: bool,
+    },
+}
+
+/// A triggered ability that has triggered, waiting to be put on the stack the
+/// next time a player would receive priority (rule 603.3).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingTrigger {
+    pub source_id: Id,
+    pub controller: Id,
+    pub effects: Vec<Effect>,
+}
+
+/// Every object's characteristics and controller, partway through applying
+/// continuous effects.
+#[derive(Debug, Clone)]
+pub(crate) struct LayerState {
+    characteristics: HashMap<Id, Characteristics>,
+    controllers: HashMap<Id, Id>,
+}
+
+impl LayerState {
+    /// Applies one part of an effect to each of the given objects.
+    fn apply(&mut self, kind: &ContinuousEffectKind, object_ids: &[Id]) {
+        for object_id in object_ids {
+            if let (Some(new_controller), Some(controller)) = (kind.new_controller(), self.controllers.get_mut(object_id)) {
+                *controller = new_controller;
+            }
+
+            if let Some(characteristics) = self.characteristics.get_mut(object_id) {
+                kind.apply(characteristics);
+            }
+        }
+    }
+}
+
+/// Turns an object's power, toughness, loyalty, and defense into fixed
+/// values, counting any `*` as 0.
+fn fix_stats(characteristics: &mut Characteristics) {
+    characteristics.power = characteristics.power
+        .map(|power| StatValue::Fixed(power.value()));
+    characteristics.toughness = characteristics.toughness
+        .map(|toughness| StatValue::Fixed(toughness.value()));
+    characteristics.loyalty = characteristics.loyalty
+        .map(|loyalty| StatValue::Fixed(loyalty.value()));
+    characteristics.defense = characteristics.defense
+        .map(|defense| StatValue::Fixed(defense.value()));
+}
+
+/// Defines the `*` in the given objects' power and toughness as the given
+/// value, for a characteristic-defining ability in layer 7a.
+fn define_power_toughness(state: &mut LayerState, object_ids: &[Id], value: i32) {
+    let define = |stat: Option<StatValue>| {
+        stat.map(|stat| match stat {
+            StatValue::Variable(modifier) => StatValue::Fixed(value + modifier),
+            fixed => fixed,
+        })
+    };
+
+    for object_id in object_ids {
+        if let Some(characteristics) = state.characteristics.get_mut(object_id) {
+            characteristics.power = define(characteristics.power);
+            characteristics.toughness = define(characteristics.toughness);
+        }
+    }
+}
+
+/// Identifies a continuous or replacement effect. Effects from abilities
+/// aren't kept in `Game::continuous_effects` or `Game::replacement_effects`,
+/// so they're identified by the ability that generates them instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum EffectKey {
+    Created(Id),
+    Static {
+        source_id: Id,
+        ability_id: Id,
+    },
+}
+
+impl EffectKey {
+    /// The ID players know the effect by: the effect's own ID, or the ID of
+    /// the ability that generates it.
+    fn id(&self) -> Id {
+        match *self {
+            EffectKey::Created(effect_id) => effect_id,
+            EffectKey::Static { ability_id, .. } => ability_id,
+        }
+    }
+}
+
+/// Which objects a continuous effect applies to.
+#[derive(Debug, Clone)]
+enum AffectedObjects {
+    /// A single object, like the creature Giant Growth was cast on.
+    Object(Id),
+
+    /// Every permanent a static ability describes. The whole ability is kept
+    /// so that its other parts can keep applying once it has started.
+    Static {
+        source_id: Id,
+        ability: StaticAbility,
+    },
+}
+
+/// One part of a continuous effect, waiting to be applied in its layer.
+#[derive(Debug, Clone)]
+struct PendingEffect {
+    key: EffectKey,
+    timestamp: Timestamp,
+    kind: ContinuousEffectKind,
+    affected: AffectedObjects,
+}
+
+/// A replacement effect that could change a mutation, whether it was created
+/// by a resolving spell or ability or comes from an object's ability.
+#[derive(Debug, Clone)]
+struct ActiveReplacement {
+    key: EffectKey,
+    source_id: Option<Id>,
+    controller: Id,
+    timestamp: Timestamp,
+    kind: ReplacementEffectKind,
+}
+
+/// A static ability's effect that has started applying, and so keeps applying
+/// to the same objects in later layers (rule 613.6).
+#[derive(Debug, Clone)]
+struct StartedEffect {
+    object_ids: Vec<Id>,
+    timestamp: Timestamp,
+    ability: StaticAbility,
+}
+
+/// A mutation partway through being changed by replacement effects, waiting
+/// for a player to choose which effect applies next. See
+/// `PlayerChoice::ReplacementOrder`.
+#[derive(Debug, Clone)]
+pub struct SuspendedMutation {
+    /// Mutations still to be checked for replacement effects, along with the
+    /// effects that have already changed each one.
+    pending: Vec<(GameMutation, Vec<EffectKey>)>,
+
+    /// Mutations that no more effects apply to, ready to be performed.
+    replaced: Vec<GameMutation>,
+
+    /// How much of each prevention shield has been used up so far, by the
+    /// shield's ID.
+    shields_used: HashMap<Id, usize>,
+}
+
+impl SuspendedMutation {
+    fn new(mutation: &GameMutation) -> SuspendedMutation {
+        SuspendedMutation {
+            pending: vec![(mutation.clone(), Vec::new())],
+            replaced: Vec::new(),
+            shields_used: HashMap::new(),
+        }
+    }
 }
 
 /// Represents all of the important serializable information about a game.
@@ -89,37 +586,110 @@ pub struct Game {
     /// the currently active effects.
     pub objects: HashMap<Id, Object>,
 
-    /// A simple representation of mana pools: the amount of green mana each
-    /// player has. This will need to be expanded!
+    /// The mana pool of each player in the game. Pools are emptied at the end
+    /// of every step and phase.
     // TODO: Move mana pools into player object?
-    pub mana_pools: HashMap<Id, usize>,
+    pub mana_pools: HashMap<Id, ManaPool>,
 
-    /// The order that players have their turns in.
+    /// The order that players have their turns in. Players leave the turn
+    /// order when they leave the game.
     pub player_turn_order: Vec<Id>,
 
+    /// The players who have lost the game so far, in the order they lost.
+    pub losers: Vec<Id>,
+
+    /// How many players the game started with, which doesn't change as
+    /// players leave.
+    pub starting_player_count: usize,
+
     /// The player whose turn it is right now.
     ///
-    /// I'm not sure if there's a game state in which there is no active player,
-    /// but it simplifies initialization of `Game`!
+    /// If the active player leaves the game partway through their turn, the
+    /// turn carries on without an active player (rule 800.4h).
     pub active_player: Option<Id>,
 
+    /// The player whose turn is next, if the active player left the game
+    /// partway through their turn. They also receive priority whenever the
+    /// active player would have.
+    pub next_active_player: Option<Id>,
+
     /// The player who currently has priority.
     ///
     /// In certain parts of the game, like resolution of state-based-actions and
     /// the untap step, no players have priority.
     pub priority_player: Option<Id>,
 
+    /// The number of players who have passed priority in a row without any
+    /// player taking an action in between. Once every player has passed, the
+    /// top of the stack resolves or, if the stack is empty, the step ends.
+    pub consecutive_passes: usize,
+
     /// The current phase of the game, as defined in the game rules.
     pub current_phase: GamePhase,
 
+    /// The number of the current turn, counting up from 1 for the starting
+    /// player's first turn.
+    pub turn_number: usize,
+
     /// The current game status, defining the core interaction loop.
     pub current_status: GameStatus,
 
-    // TODO: A reference to an object descriptor pool, like what cards are legal
-    //       in this format.
-    // TODO: The stack, a Vec<Object>?
+    /// The definitions of every card that can appear in this game. Shared, so
+    /// that cloning a game doesn't copy any card text.
+    pub registry: Arc<CardRegistry>,
+
     // TODO: A log of player actions
-    // TODO: A list of currently active effects and their durations
+    /// The continuous effects currently changing objects in the game, in the
+    /// order they started.
+    pub continuous_effects: Vec<ContinuousEffect>,
+
+    /// The replacement effects created by spells and abilities that are
+    /// watching for events, in the order they started.
+    pub replacement_effects: Vec<ReplacementEffect>,
+
+    /// A snapshot of every object that has left its zone, keyed by the ID it
+    /// had before leaving.
+    ///
+    /// Snapshots are only kept until the stack is next empty, unless an effect
+    /// still refers to the object.
+    pub last_known_information: HashMap<Id, LastKnownInformation>,
+
+    /// The ID of the new object each object became when it moved to another
+    /// zone, keyed by the ID it had before. Unlike snapshots, these are never
+    /// forgotten, so `current_object_id` can always follow an object.
+    pub new_object_ids: HashMap<Id, Id>,
+
+    /// Mutations waiting on a `PlayerChoice::ReplacementOrder`. The first is
+    /// the one the choice is for, and the rest came up while it was pending,
+    /// so they wait their turn.
+    pub suspended_mutations: Vec<SuspendedMutation>,
+
+    /// The creatures attacking this combat, by their IDs. Empty outside of
+    /// combat.
+    pub attacks: HashMap<Id, Attack>,
+
+    /// Cards that went on an adventure, by their IDs in exile, along with the
+    /// player who may cast them as creatures from there (rule 715.4).
+    pub adventurers: HashMap<Id, Id>,
+
+    /// The permanents whose loyalty abilities have been activated this turn,
+    /// which can't activate another until the next (rule 606.3).
+    pub loyalty_abilities_activated: Vec<Id>,
+
+    /// Triggered abilities that have triggered since a player last received
+    /// priority, in the order they'll be put on the stack.
+    pub pending_triggers: Vec<PendingTrigger>,
+
+    /// The mana abilities activated during the current
+    /// `PlayerChoice::PayMana`, which are undone if the player cancels.
+    pub payment_activations: Vec<SourceActivation>,
+
+    /// Every object's characteristics and controller with continuous effects
+    /// applied, kept until the game state next changes.
+    ///
+    /// Anything that changes objects or effects has to clear it with
+    /// `layers_changed`.
+    pub(crate) layer_cache: RefCell<Option<Arc<LayerState>>>,
 }
 
 impl Game {
@@ -143,6 +713,15 @@ impl Game {
         // Players can only take an action if the game can accept one!
         match self.current_status {
             GameStatus::NeedsPlayerAction => {},
+            GameStatus::NeedsPlayerChoice(ref choice) => match *action {
+                // Players can concede at any time.
+                PlayerAction::Concede => {},
+                _ => {
+                    let choice = choice.clone();
+
+                    return self.do_player_choice(acting_player_id, &choice, action);
+                },
+            },
             _ => return Err(PlayerActionError::NotAllowed("Game unable to accept actions at this time")),
         }
 
@@ -150,7 +729,12 @@ impl Game {
         // eventually
         match *action {
             PlayerAction::Concede => {
-                self.current_status = GameStatus::Ended;
+                if !self.player_turn_order.contains(&acting_player_id) {
+                    return Err(PlayerActionError::NotAllowed("Player is not in the game"));
+                }
+
+                // Conceding means losing the game right away (rule 104.3a).
+                self.players_lose(&[acting_player_id]);
 
                 Ok(())
             },
@@ -162,72 +746,67 @@ impl Game {
                     .position(|&id| id == acting_player_id)
                     .expect("Player with priority is missing from player_turn_order!");
 
-                let current_active_id = self.active_player.unwrap();
-                let current_active_index = self.player_turn_order
-                    .iter()
-                    .position(|&id| id == current_active_id)
-                    .expect("Player with priority is missing from player_turn_order!");
-
                 let player_count = self.player_turn_order.len();
 
                 let next_priority_index = (current_priority_index + 1) % player_count;
                 let next_priority_id = self.player_turn_order[next_priority_index];
 
-                // If priority would swing back around to the active player,
-                // it's time to advance!
-                if next_priority_id == current_active_id {
-                    // TODO: Pop one item off the stack if not empty
+                self.consecutive_passes += 1;
 
-                    // Are we at the end of the turn?
-                    match self.current_phase.next() {
-                        Some(next_phase) => {
-                            // To the next phase!
-                            self.current_phase = next_phase;
-                            self.priority_player = self.active_player;
-
-                            // TODO: Empty mana pools of all players
-                        },
-                        None => {
-                            // We're out of phases, advance turns!
-                            let next_active_index = (current_active_index + 1) % player_count;
-                            let next_active_id = self.player_turn_order[next_active_index];
+                // If every player passed in a row, either the top of the stack
+                // resolves or it's time to advance!
+                if self.consecutive_passes >= player_count {
+                    if self.stack_is_empty() {
+                        self.advance_phase();
+                    } else {
+                        self.resolve_top_of_stack();
 
-                            self.active_player = Some(next_active_id);
-                            self.priority_player = Some(next_active_id);
-                        },
+                        // The active player receives priority after any spell
+                        // or ability resolves (rule 117.3b).
+                        self.consecutive_passes = 0;
+                        self.grant_priority_to_active();
                     }
                 } else {
-                    self.priority_player = Some(next_priority_id);
+                    self.grant_priority(next_priority_id);
                 }
 
                 Ok(())
             },
-            PlayerAction::PlayLand { object_id } => {
-                // TODO: Players can only play lands when it's their turn
-                // TODO: Players can only play lands during a main phase
-                // TODO: Players can only play lands when the stack is empty
-                // TODO: Players can only play one land per turn
-
+            PlayerAction::PlayLand { object_id, face } => {
                 self.check_priority(acting_player_id)?;
 
+                // Playing a land is a special action that's only allowed at
+                // sorcery speed (rule 305.2).
+                if self.active_player != Some(acting_player_id) {
+                    return Err(PlayerActionError::NotAllowed("Players can only play lands during their own turn"));
+                }
+
+                if !self.current_phase.is_main_phase() {
+                    return Err(PlayerActionError::NotAllowed("Players can only play lands during a main phase"));
+                }
+
+                if !self.stack_is_empty() {
+                    return Err(PlayerActionError::NotAllowed("Players can only play lands when the stack is empty"));
+                }
+
+                let lands_played = self.players.get(&acting_player_id)
+                    .map(|player| player.lands_played_this_turn)
+                    .unwrap_or(0);
+
+                if lands_played >= self.land_plays_allowed(acting_player_id) {
+                    return Err(PlayerActionError::NotAllowed("Player has no land plays left this turn"));
+                }
+
                 let player_hand_id = self
-                    .find_zone_id(|zone| {
-                        match zone.details {
-                            ZoneDetails::Hand { player_id } => player_id == acting_player_id,
-                            _ => false,
-                        }
-                    })
+                    .hand_id(acting_player_id)
                     .expect("Unable to locate player's hand!");
 
                 let battlefield_id = self
-                    .find_zone_id(|zone| {
-                        match zone.details {
-                            ZoneDetails::Battlefield => true,
-                            _ => false,
-                        }
-                    })
+                    .battlefield_id()
                     .expect("Unable to locate battlefield!");
 
+                self.check_controls(&self.layers(), acting_player_id, object_id)?;
+
                 // We need to make sure we have a land to play!
                 match self.objects.get(&object_id) {
                     Some(object) => {
@@ -236,122 +815,3201 @@ impl Game {
                             return Err(PlayerActionError::NotAllowed("Land not in player's hand"));
                         }
 
-                        // Make sure it's a land
-                        match object.details {
-                            ObjectDetails::Forest { .. } => {},
-                            // TODO: Other object details, when they're introduced
+                        // Make sure it's a land, with the face it's being
+                        // played with up
+                        let is_land = self.face_characteristics(object_id, face)?
+                            .has_card_type(CardType::Land);
+
+                        if !is_land {
+                            return Err(PlayerActionError::NotAllowed("Object is not a land"));
                         }
                     }
                     None => return Err(PlayerActionError::NotAllowed("Object not found")),
                 }
 
-                // We just checked to make sure this existed above!
-                let object = self.objects.remove(&object_id).unwrap();
+                let can_play_face = self.objects.get(&object_id)
+                    .and_then(|object| object.definition_id())
+                    .and_then(|definition_id| self.registry.get(definition_id))
+                    .is_some_and(|definition| definition.can_play_face(face));
 
-                // For now, let's just generate a new object and put it on the
-                // battlefield.
-                let new_object = Object {
-                    id: get_id(),
-                    zone: battlefield_id,
-                    timestamp: get_timestamp(),
-                    details: object.details,
-                    abilities: object.abilities,
-                };
+                if !can_play_face {
+                    return Err(PlayerActionError::NotAllowed("Card can't be played with that face up"));
+                }
+
+                self.move_object_face_up(object_id, battlefield_id, face);
 
-                // TODO: Use GameMutation instead?
-                self.objects.insert(new_object.id, new_object);
+                if let Some(player) = self.players.get_mut(&acting_player_id) {
+                    player.lands_played_this_turn += 1;
+                }
+
+                // The player receives priority again after a special action
+                // (rule 116.3c).
+                self.consecutive_passes = 0;
+                self.process_state_based_actions();
 
                 Ok(())
             },
-            PlayerAction::ActivateAbility { object_id, ability_id } => {
-                // TODO: This function is definitely temporary, like the rest of
-                //       the ability system.
-
+            PlayerAction::CastSpell { object_id, face } => {
                 self.check_priority(acting_player_id)?;
 
-                let mut object = match self.objects.get_mut(&object_id) {
-                    Some(object) => object,
-                    None => return Err(PlayerActionError::NotAllowed("Object not found")),
-                };
+                self.cast_spell(acting_player_id, object_id, face)?;
 
-                // TODO: Make sure the acting player controls this object!
+                if self.current_status == GameStatus::NeedsPlayerAction {
+                    self.finish_casting(acting_player_id);
+                }
 
-                let ability = match object.abilities.get(&ability_id) {
-                    Some(ability) => ability,
-                    None => return Err(PlayerActionError::NotAllowed("Ability not found on object")),
-                };
+                Ok(())
+            },
+            PlayerAction::ActivateAbility { object_id, ability_id } => {
+                self.check_priority(acting_player_id)?;
 
-                //  such green
-                //       so forest
-                //    wow
-                match *ability {
-                    Ability::AddGreen => {
-                        // Make sure we're on the battlefield! Abilities will
-                        // have to specify when they are valid to activate.
-                        match self.zones.get(&object.zone) {
-                            Some(zone) => match zone.details {
-                                ZoneDetails::Battlefield => {},
-                                _ => return Err(PlayerActionError::NotAllowed("Object not on battlefield")),
-                            },
-                            None => return Err(PlayerActionError::NotAllowed("Could not find object's zone by ID")),
-                        }
+                self.activate_ability(acting_player_id, object_id, ability_id)?;
 
-                        // Make sure our land is untapped
-                        match object.details {
-                            ObjectDetails::Forest { tapped } => {
-                                if tapped {
-                                    return Err(PlayerActionError::NotAllowed("Object already tapped"));
-                                }
-
-                                // Tap the land!
-                                object.details = ObjectDetails::Forest {
-                                    tapped: true
-                                };
-                            }
-                        }
+                // The player who activated the ability receives priority
+                // afterwards (rule 117.3c).
+                if self.current_status == GameStatus::NeedsPlayerAction {
+                    self.consecutive_passes = 0;
+                    self.process_state_based_actions();
+                }
 
-                        // Find our mana pool and increment it
-                        // Eventually, mana will be more complex than a usize
-                        let mana_value = *self.mana_pools.get(&acting_player_id)
-                            .expect("Player was missing their mana pool!");
+                Ok(())
+            },
+            PlayerAction::ActivateManaAbility { object_id, ability_id, mana_type } => {
+                self.check_priority(acting_player_id)?;
 
-                        self.mana_pools.insert(acting_player_id, mana_value + 1);
-                    },
-                }
+                self.activate_mana_ability(acting_player_id, object_id, ability_id, mana_type)?;
+
+                self.consecutive_passes = 0;
 
                 Ok(())
             },
+            PlayerAction::Discard { .. } => {
+                Err(PlayerActionError::NotAllowed("No discard is pending"))
+            },
+            PlayerAction::KeepLegend { .. } => {
+                Err(PlayerActionError::NotAllowed("No legend rule choice is pending"))
+            },
+            PlayerAction::ChooseReplacement { .. } => {
+                Err(PlayerActionError::NotAllowed("No replacement effect choice is pending"))
+            },
+            PlayerAction::DeclareAttackers { .. } => {
+                Err(PlayerActionError::NotAllowed("Players can only declare attackers as the declare attackers step begins"))
+            },
+            PlayerAction::DeclareBlockers { .. } => {
+                Err(PlayerActionError::NotAllowed("Players can only declare blockers as the declare blockers step begins"))
+            },
+            PlayerAction::FinishPayment | PlayerAction::CancelPayment => {
+                Err(PlayerActionError::NotAllowed("No spell is waiting to be paid for"))
+            },
         }
     }
 
-    /// Apply all state-based actions, like creatures dying due to damage.
-    pub fn process_state_based_actions(&mut self) {
-        // TODO: State based actions!
+    /// Makes sure the given player controls the given object.
+    fn check_controls(&self, state: &LayerState, acting_player_id: Id, object_id: Id) -> Result<(), PlayerActionError> {
+        if self.controller_in(state, object_id) == Some(acting_player_id) {
+            Ok(())
+        } else {
+            Err(PlayerActionError::NotAllowed("Player doesn't control this object"))
+        }
     }
 
-    /// Finds the zone that passes the given condition, if it exists.
-    pub fn find_zone_id<F>(&self, predicate: F) -> Option<Id>
-    where
-        F: Fn(&Zone) -> bool
-    {
-        self.zones.values()
-            .find(|zone| predicate(zone))
-            .and_then(|zone| Some(zone.id))
+    /// Makes sure the given object's abilities work from the zone it's in, so
+    /// that they can be activated.
+    fn check_abilities_function(&self, object: &Object) -> Result<(), PlayerActionError> {
+        if !self.zones.contains_key(&object.zone) {
+            return Err(PlayerActionError::NotAllowed("Could not find object's zone by ID"));
+        }
+
+        if self.abilities_function(object.id) {
+            Ok(())
+        } else {
+            Err(PlayerActionError::NotAllowed("Object's abilities don't work from its zone"))
+        }
     }
 
+    /// Whether the given object's abilities work from the zone it's in right
+    /// now: a permanent's on the battlefield, and an emblem's in the command
+    /// zone (rule 113.6).
+    ///
+    /// Static and triggered abilities should only apply while this is true.
+    // TODO: Abilities will have to specify where else they work from, like
+    //       cycling from a player's hand.
+    pub fn abilities_function(&self, object_id: Id) -> bool {
+        let object = match self.objects.get(&object_id) {
+            Some(object) => object,
+            None => return false,
+        };
 
-    /// Create a version of `Game` as viewed by the given player. This
-    /// should collapse hidden zones and unknown information. Hopefully, it's
-    /// also cheap, since the most naive way to implement client communication
-    /// would be to send a full state on every change!
-    pub fn view_as_player(&self, _player_id: Id) -> Game {
-        // TODO: Hide objects that player has no knowledge of, like face-down
-        //       permanents.
+        match self.zones.get(&object.zone).map(|zone| &zone.details) {
+            Some(&ZoneDetails::Battlefield) => !object.is_emblem() && !object.status.phased_out,
+            Some(&ZoneDetails::Command) => object.is_emblem(),
+            _ => false,
+        }
+    }
 
-        // TODO: Hide zones that player has no knowledge of, like libraries, and
-        //       face-down exile zones created by cards like Bomat Courier.
+    /// Works out how the given player would pay an ability's activation cost
+    /// right now, without paying anything. Returns the plan for paying the
+    /// cost's mana, if it has any.
+    fn plan_activation_cost(&self, player_id: Id, object: &Object, cost: &ActivationCost) -> Result<Option<PaymentPlan>, PlayerActionError> {
+        self.check_activation_cost(player_id, object, cost)?;
 
-        self.clone()
+        match cost.mana {
+            Some(ref mana_cost) => {
+                self.plan_mana_payment(player_id, object.id, mana_cost)
+                    .map(Some)
+                    .ok_or(PlayerActionError::NotAllowed("Unable to pay mana cost"))
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Checks that the given player could pay every part of an ability's
+    /// activation cost but its mana right now.
+    fn check_activation_cost(&self, player_id: Id, object: &Object, cost: &ActivationCost) -> Result<(), PlayerActionError> {
+        if cost.tap && object.is_tapped() {
+            return Err(PlayerActionError::NotAllowed("Object already tapped"));
+        }
+
+        // TODO: Creatures with summoning sickness can't pay {T} costs
+
+        // Loyalty abilities can only be activated once each turn, any time
+        // their controller could cast a sorcery (rule 606.3).
+        if let Some(loyalty) = cost.loyalty {
+            if !self.at_sorcery_speed(player_id) {
+                return Err(PlayerActionError::NotAllowed("Loyalty abilities can only be activated at sorcery speed"));
+            }
+
+            if self.loyalty_abilities_activated.contains(&object.id) {
+                return Err(PlayerActionError::NotAllowed("A loyalty ability of this permanent was already activated this turn"));
+            }
+
+            if loyalty < 0 && object.counters(&ObjectCounter::Loyalty) < loyalty.unsigned_abs() as usize {
+                return Err(PlayerActionError::NotAllowed("Not enough loyalty"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Works out how the given player would pay the mana part of an ability's
+    /// activation cost right now, or `None` if they can't.
+    fn plan_mana_payment(&self, player_id: Id, object_id: Id, mana_cost: &ManaCost) -> Option<PaymentPlan> {
+        let pool = self.mana_pools.get(&player_id)?;
+
+        // An object can't tap for mana to help pay for its own ability.
+        let sources: Vec<_> = self.available_mana_sources(player_id)
+            .into_iter()
+            .filter(|source| source.object_id != object_id)
+            .collect();
+
+        let max_life = self.players.get(&player_id)
+            .map(|player| player.life.max(0) as usize)
+            .unwrap_or(0);
+
+        // TODO: Choosing X for activated abilities
+        solve_payment(mana_cost, 0, pool, &sources, max_life)
+    }
+
+    /// Pays an activation cost planned by `plan_activation_cost`.
+    fn pay_activation_cost(&mut self, player_id: Id, object_id: Id, cost: &ActivationCost, plan: Option<PaymentPlan>) -> Result<(), PlayerActionError> {
+        if let (Some(mana_cost), Some(plan)) = (cost.mana.as_ref(), plan) {
+            self.pay_mana_cost(player_id, mana_cost, 0, &plan)?;
+        }
+
+        if cost.tap {
+            let object = self.objects.get_mut(&object_id)
+                .ok_or(PlayerActionError::NotAllowed("Object not found"))?;
+
+            object.set_tapped(true);
+        }
+
+        match cost.loyalty {
+            Some(loyalty) if loyalty > 0 => {
+                self.add_counters(object_id, ObjectCounter::Loyalty, loyalty as usize);
+            },
+            Some(loyalty) if loyalty < 0 => {
+                self.remove_counters(object_id, ObjectCounter::Loyalty, loyalty.unsigned_abs() as usize)?;
+            },
+            _ => {},
+        }
+
+        if cost.loyalty.is_some() {
+            self.loyalty_abilities_activated.push(object_id);
+        }
+
+        if cost.sacrifice {
+            self.sacrifice(object_id)
+                .ok_or(PlayerActionError::NotAllowed("Object can't be sacrificed"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Sacrifices a permanent, moving it from the battlefield to its owner's
+    /// graveyard (rule 701.21a). Returns the ID of the object in the graveyard,
+    /// or `None` if it isn't on the battlefield.
+    pub fn sacrifice(&mut self, object_id: Id) -> Option<Id> {
+        let object = self.objects.get(&object_id)?;
+
+        if Some(object.zone) != self.battlefield_id() {
+            return None;
+        }
+
+        let graveyard_id = self.graveyard_id(object.owner)?;
+
+        self.move_object(object_id, graveyard_id)
+    }
+
+    /// Activates one of an object's mana abilities for the given player, adding
+    /// one mana of the chosen type to their mana pool.
+    ///
+    /// Mana abilities don't use the stack, so this is also how mana sources are
+    /// activated in the middle of paying a cost.
+    fn activate_mana_ability(&mut self, acting_player_id: Id, object_id: Id, ability_id: Id, mana_type: ManaType) -> Result<(), PlayerActionError> {
+        let object = match self.objects.get(&object_id) {
+            Some(object) => object,
+            None => return Err(PlayerActionError::NotAllowed("Object not found")),
+        };
+
+        let state = self.layers();
+        self.check_controls(&state, acting_player_id, object_id)?;
+
+        let mana_ability = match self.ability_in(&state, object_id, ability_id) {
+            Some(Ability::Mana(mana_ability)) => mana_ability,
+            Some(_) => return Err(PlayerActionError::NotAllowed("Ability is not a mana ability")),
+            None => return Err(PlayerActionError::NotAllowed("Ability not found on object")),
+        };
+
+        if !mana_ability.produces.contains(&mana_type) {
+            return Err(PlayerActionError::NotAllowed("Ability can't produce that type of mana"));
+        }
+
+        self.check_abilities_function(object)?;
+
+        // Make sure there's a pool to put mana into before we pay anything.
+        if !self.mana_pools.contains_key(&acting_player_id) {
+            return Err(ManaError::PoolNotFound.into());
+        }
+
+        let plan = self.plan_activation_cost(acting_player_id, object, &mana_ability.cost)?;
+        self.pay_activation_cost(acting_player_id, object_id, &mana_ability.cost, plan)?;
+
+        self.add_mana(acting_player_id, mana_type, 1)?;
+
+        Ok(())
+    }
+
+    /// Reverses the mana abilities the given player activated while paying
+    /// for a spell or ability they gave up on, untapping the sources and
+    /// taking their mana back out of their mana pool (rule 726.3).
+    fn undo_payment_activations(&mut self, player_id: Id) {
+        for activation in ::std::mem::take(&mut self.payment_activations) {
+            let untaps = match self.ability(activation.object_id, activation.ability_id) {
+                Some(Ability::Mana(mana_ability)) => mana_ability.cost.tap,
+                _ => false,
+            };
+
+            // TODO: Sources sacrificed for mana can't be brought back, so this
+            //       only undoes the rest of their costs.
+            if untaps {
+                if let Some(object) = self.objects.get_mut(&activation.object_id) {
+                    object.set_tapped(false);
+                }
+            }
+
+            // The mana may have already gone, like if the pool emptied.
+            if let Some(pool) = self.mana_pools.get_mut(&player_id) {
+                let _ = pool.remove(activation.mana_type, 1);
+            }
+        }
+    }
+
+    /// The characteristics an object would have with the given face up, like
+    /// while deciding which face of a card to cast.
+    fn face_characteristics(&self, object_id: Id, face: Option<Face>) -> Result<&Characteristics, PlayerActionError> {
+        let definition = self.objects.get(&object_id)
+            .and_then(|object| object.definition_id())
+            .and_then(|definition_id| self.registry.get(definition_id))
+            .ok_or(PlayerActionError::NotAllowed("Object has no card definition"))?;
+
+        match face {
+            Some(face) => definition.face(face)
+                .ok_or(PlayerActionError::NotAllowed("Card doesn't have that face")),
+            None => Ok(&definition.characteristics),
+        }
+    }
+
+    /// Casts a spell from the given player's hand with the given face up,
+    /// paying its mana cost and putting it on the stack.
+    // TODO: Casting from other zones, alternative and additional costs,
+    //       choosing X, and targets
+    fn cast_spell(&mut self, acting_player_id: Id, object_id: Id, face: Option<Face>) -> Result<(), PlayerActionError> {
+        let object = self.objects.get(&object_id)
+            .ok_or(PlayerActionError::NotAllowed("Object not found"))?;
+
+        self.check_controls(&self.layers(), acting_player_id, object_id)?;
+
+        // An adventurer can be cast as a creature from exile once it's been
+        // on its adventure, but can't go on another (rule 715.4).
+        let from_exile = self.adventurers.get(&object_id) == Some(&acting_player_id);
+
+        if from_exile && face.is_some() {
+            return Err(PlayerActionError::NotAllowed("Only the creature can be cast after an adventure"));
+        }
+
+        if Some(object.zone) != self.hand_id(acting_player_id) && !from_exile {
+            return Err(PlayerActionError::NotAllowed("Card not in player's hand"));
+        }
+
+        let can_cast_face = object.definition_id()
+            .and_then(|definition_id| self.registry.get(definition_id))
+            .is_some_and(|definition| definition.can_cast_face(face));
+
+        if !can_cast_face {
+            return Err(PlayerActionError::NotAllowed("Card can't be cast with that face up"));
+        }
+
+        let characteristics = self.face_characteristics(object_id, face)?;
+
+        if characteristics.has_card_type(CardType::Land) {
+            return Err(PlayerActionError::NotAllowed("Lands are played, not cast"));
+        }
+
+        // TODO: Flash
+        let is_instant = characteristics.has_card_type(CardType::Instant);
+
+        if !is_instant && !self.at_sorcery_speed(acting_player_id) {
+            return Err(PlayerActionError::NotAllowed("Only instants can be cast right now"));
+        }
+
+        let stack_id = self.stack_id()
+            .expect("Unable to locate stack!");
+
+        // Cards without a mana cost can't be cast by paying one (rule 118.6).
+        let mana_cost = characteristics.mana_cost.clone()
+            .ok_or(PlayerActionError::NotAllowed("Card has no mana cost"))?;
+
+        let cast_from = object.zone;
+
+        match self.find_payment(acting_player_id, &mana_cost, 0) {
+            Some(plan) => {
+                self.pay_mana_cost(acting_player_id, &mana_cost, 0, &plan)?;
+                self.move_object_face_up(object_id, stack_id, face);
+            },
+            None => {
+                // The spell is put on the stack before its costs are paid
+                // (rule 601.2a), and waits there while the player activates
+                // mana abilities to pay for it (rule 601.2g).
+                let spell_id = self.move_object_face_up(object_id, stack_id, face)
+                    .ok_or(PlayerActionError::NotAllowed("Unable to put the spell on the stack"))?;
+
+                self.current_status = GameStatus::NeedsPlayerChoice(PlayerChoice::PayMana {
+                    player_id: acting_player_id,
+                    object_id: spell_id,
+                    cost: ActivationCost {
+                        mana: Some(mana_cost),
+                        ..ActivationCost::default()
+                    },
+                    cast_from: Some(cast_from),
+                });
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Finishes casting a spell whose costs have been paid: abilities that
+    /// trigger on it being cast trigger (rule 601.2i), and the player who
+    /// cast it receives priority afterwards (rule 117.3c).
+    fn finish_casting(&mut self, player_id: Id) {
+        self.trigger_abilities(|trigger, controller| {
+            *trigger == Trigger::YouCastSpell && controller == player_id
+        });
+
+        self.consecutive_passes = 0;
+        self.process_state_based_actions();
+    }
+
+    /// Whether the given player could cast a sorcery right now: during a main
+    /// phase of their own turn, while the stack is empty (rule 307.1).
+    fn at_sorcery_speed(&self, player_id: Id) -> bool {
+        self.active_player == Some(player_id)
+            && self.current_phase.is_main_phase()
+            && self.stack_is_empty()
+    }
+
+    /// Activates one of an object's non-mana abilities for the given player,
+    /// paying its costs and putting it on the stack.
+    fn activate_ability(&mut self, acting_player_id: Id, object_id: Id, ability_id: Id) -> Result<(), PlayerActionError> {
+        let object = match self.objects.get(&object_id) {
+            Some(object) => object,
+            None => return Err(PlayerActionError::NotAllowed("Object not found")),
+        };
+
+        let state = self.layers();
+        self.check_controls(&state, acting_player_id, object_id)?;
+
+        let activated_ability = match self.ability_in(&state, object_id, ability_id) {
+            Some(Ability::Activated(activated_ability)) => activated_ability,
+            Some(Ability::Mana(_)) => return Err(PlayerActionError::NotAllowed("Mana abilities are activated with ActivateManaAbility")),
+            Some(_) => return Err(PlayerActionError::NotAllowed("Ability can't be activated")),
+            None => return Err(PlayerActionError::NotAllowed("Ability not found on object")),
+        };
+
+        self.check_abilities_function(object)?;
+        self.check_activation_cost(acting_player_id, object, &activated_ability.cost)?;
+
+        let plan = match activated_ability.cost.mana {
+            Some(ref mana_cost) => match self.plan_mana_payment(acting_player_id, object_id, mana_cost) {
+                Some(plan) => Some(plan),
+                None => {
+                    // Like a spell, the ability is put on the stack before
+                    // its costs are paid, and waits there while the player
+                    // activates mana abilities to pay for it (rule 602.2).
+                    let ability_object_id = self.put_ability_on_stack(object_id, acting_player_id, activated_ability.effects);
+
+                    self.current_status = GameStatus::NeedsPlayerChoice(PlayerChoice::PayMana {
+                        player_id: acting_player_id,
+                        object_id: ability_object_id,
+                        cost: activated_ability.cost,
+                        cast_from: None,
+                    });
+
+                    return Ok(());
+                },
+            },
+            None => None,
+        };
+
+        self.pay_activation_cost(acting_player_id, object_id, &activated_ability.cost, plan)?;
+        self.put_ability_on_stack(object_id, acting_player_id, activated_ability.effects);
+
+        Ok(())
+    }
+
+    /// Pays the rest of an activation cost for an ability waiting on the stack
+    /// in a `PlayerChoice::PayMana`. The ability's source has to still be able
+    /// to pay its part, since the player may have used it to make mana.
+    fn finish_ability_payment(&mut self, player_id: Id, source_id: Id, cost: &ActivationCost) -> Result<(), PlayerActionError> {
+        let source = self.objects.get(&source_id)
+            .ok_or(PlayerActionError::NotAllowed("Ability's source is gone"))?;
+
+        if cost.tap && source.is_tapped() {
+            return Err(PlayerActionError::NotAllowed("Object already tapped"));
+        }
+
+        let plan = match cost.mana {
+            Some(ref mana_cost) => {
+                let plan = self.plan_mana_payment(player_id, source_id, mana_cost)
+                    .ok_or(PlayerActionError::NotAllowed("Unable to pay mana cost"))?;
+
+                Some(plan)
+            },
+            None => None,
+        };
+
+        self.pay_activation_cost(player_id, source_id, cost, plan)
+    }
+
+    /// Puts an activated or triggered ability on the stack, controlled by the
+    /// given player. Returns the ID of the new stack object.
+    fn put_ability_on_stack(&mut self, source_id: Id, controller: Id, effects: Vec<Effect>) -> Id {
+        let stack_id = self.stack_id()
+            .expect("Unable to locate stack!");
+
+        self.add_object(Object {
+            id: get_id(),
+            zone: stack_id,
+            timestamp: get_timestamp(),
+            owner: controller,
+            controller,
+            damage: 0,
+            status: PermanentStatus::default(),
+            counters: HashMap::new(),
+            face: None,
+            attached_to: None,
+            details: ObjectDetails::Ability {
+                source_id,
+                effects,
+            },
+        })
+    }
+
+    /// Checks every triggered ability whose source's abilities function right
+    /// now, with the given test for whether its trigger event just happened,
+    /// given the ability's controller. The ones that triggered wait in
+    /// `pending_triggers`.
+    fn trigger_abilities<F>(&mut self, triggered_by: F)
+        where F: Fn(&Trigger, Id) -> bool
+    {
+        let state = self.layers();
+        let mut triggered = Vec::new();
+
+        for (&source_id, characteristics) in &state.characteristics {
+            if !self.abilities_function(source_id) {
+                continue;
+            }
+
+            let controller = state.controllers[&source_id];
+
+            for (&ability_id, ability) in &characteristics.abilities {
+                if let Ability::Triggered(ref ability) = *ability {
+                    if triggered_by(&ability.trigger, controller) {
+                        let key = (self.objects[&source_id].timestamp, source_id, ability_id);
+
+                        triggered.push((key, PendingTrigger {
+                            source_id,
+                            controller,
+                            effects: ability.effects.clone(),
+                        }));
+                    }
+                }
+            }
+        }
+
+        triggered.sort_by_key(|&(key, _)| key);
+        self.pending_triggers.extend(triggered.into_iter().map(|(_, trigger)| trigger));
+    }
+
+    /// Puts every pending triggered ability on the stack.
+    // TODO: Each player puts theirs on in APNAP order, choosing the order of
+    //       their own (rule 603.3b). For now, they go on oldest source first.
+    fn put_triggers_on_stack(&mut self) {
+        for trigger in ::std::mem::take(&mut self.pending_triggers) {
+            self.put_ability_on_stack(trigger.source_id, trigger.controller, trigger.effects);
+        }
+    }
+
+    /// Lists the mana sources the given player could activate right now to help
+    /// pay a cost, sorted by object ID so that payments are deterministic.
+    pub fn available_mana_sources(&self, player_id: Id) -> Vec<ManaSource> {
+        let battlefield_id = match self.battlefield_id() {
+            Some(battlefield_id) => battlefield_id,
+            None => return Vec::new(),
+        };
+
+        let tap_cost = ActivationCost {
+            tap: true,
+            ..ActivationCost::default()
+        };
+
+        let state = self.layers();
+        let mut sources = Vec::new();
+
+        for object in self.objects.values() {
+            if object.zone != battlefield_id || state.controllers.get(&object.id) != Some(&player_id) {
+                continue;
+            }
+
+            if object.is_tapped() {
+                continue;
+            }
+
+            let characteristics = match state.characteristics.get(&object.id) {
+                Some(characteristics) => characteristics,
+                None => continue,
+            };
+
+            for (&ability_id, ability) in &characteristics.abilities {
+                // Only abilities that cost nothing but {T} can be activated
+                // this way, since anything else would need paying for too.
+                let produces = match *ability {
+                    Ability::Mana(ref mana_ability) if mana_ability.cost == tap_cost => {
+                        mana_ability.produces.clone()
+                    },
+                    _ => continue,
+                };
+
+                sources.push(ManaSource {
+                    object_id: object.id,
+                    ability_id,
+                    produces,
+                });
+            }
+        }
+
+        sources.sort_by_key(|source| (source.object_id, source.ability_id));
+        sources
+    }
+
+    /// Works out how the given player could pay a mana cost with the mana in
+    /// their pool and the mana sources they could activate. `x` is the value
+    /// chosen for `{X}`.
+    pub fn find_payment(&self, player_id: Id, cost: &ManaCost, x: usize) -> Option<PaymentPlan> {
+        let pool = self.mana_pools.get(&player_id)?;
+        let sources = self.available_mana_sources(player_id);
+        let max_life = self.players.get(&player_id)?.life.max(0) as usize;
+
+        solve_payment(cost, x, pool, &sources, max_life)
+    }
+
+    /// Pays a mana cost for the given player by following a payment plan:
+    /// activating the plan's mana sources, spending mana from their pool, then
+    /// paying life for any Phyrexian mana.
+    ///
+    /// The whole plan is checked before anything happens, so a payment that
+    /// fails leaves the game untouched.
+    pub fn pay_mana_cost(&mut self, player_id: Id, cost: &ManaCost, x: usize, plan: &PaymentPlan) -> Result<(), PlayerActionError> {
+        if !pays_exactly(cost, x, &plan.mana, plan.life) {
+            return Err(PlayerActionError::NotAllowed("Payment doesn't match the cost"));
+        }
+
+        if !self.can_pay_life(player_id, plan.life) {
+            return Err(PlayerActionError::NotAllowed("Player can't pay that much life"));
+        }
+
+        let mut available = self.mana_pools.get(&player_id)
+            .ok_or(ManaError::PoolNotFound)?
+            .clone();
+
+        let sources = self.available_mana_sources(player_id);
+
+        for (index, activation) in plan.activations.iter().enumerate() {
+            let is_duplicate = plan.activations[..index].iter()
+                .any(|other| other.object_id == activation.object_id && other.ability_id == activation.ability_id);
+
+            let can_activate = sources.iter().any(|source| {
+                source.object_id == activation.object_id
+                    && source.ability_id == activation.ability_id
+                    && source.produces.contains(&activation.mana_type)
+            });
+
+            if is_duplicate || !can_activate {
+                return Err(PlayerActionError::NotAllowed("Mana source can't be activated"));
+            }
+
+            available.add(activation.mana_type, 1);
+        }
+
+        for &mana_type in &ManaType::all() {
+            if available.get(mana_type) < plan.mana.get(mana_type) {
+                return Err(ManaError::NotEnoughMana.into());
+            }
+        }
+
+        for activation in &plan.activations {
+            self.activate_mana_ability(player_id, activation.object_id, activation.ability_id, activation.mana_type)?;
+        }
+
+        for &mana_type in &ManaType::all() {
+            self.remove_mana(player_id, mana_type, plan.mana.get(mana_type))?;
+        }
+
+        self.pay_life(player_id, plan.life)
+    }
+
+    /// Pays a mana cost for the given player, picking which mana sources to
+    /// activate with `find_payment`. Meant for clients and AIs that don't want
+    /// to choose every land by hand.
+    pub fn auto_pay_mana_cost(&mut self, player_id: Id, cost: &ManaCost, x: usize) -> Result<PaymentPlan, PlayerActionError> {
+        let plan = self.find_payment(player_id, cost, x)
+            .ok_or(PlayerActionError::NotAllowed("Unable to pay mana cost"))?;
+
+        self.pay_mana_cost(player_id, cost, x, &plan)?;
+
+        Ok(plan)
+    }
+
+    /// Process the given player action as the answer to the choice the game is
+    /// currently waiting on.
+    fn do_player_choice(&mut self, acting_player_id: Id, choice: &PlayerChoice, action: &PlayerAction) -> Result<(), PlayerActionError> {
+        match (choice, action) {
+            (&PlayerChoice::Discard { player_id, count }, PlayerAction::Discard { object_ids }) => {
+                if acting_player_id != player_id {
+                    return Err(PlayerActionError::NotAllowed("Player is not the one making this choice"));
+                }
+
+                if object_ids.len() != count {
+                    return Err(PlayerActionError::NotAllowed("Wrong number of cards to discard"));
+                }
+
+                let hand_id = self.hand_id(player_id)
+                    .expect("Unable to locate player's hand!");
+
+                let graveyard_id = self.graveyard_id(player_id)
+                    .expect("Unable to locate player's graveyard!");
+
+                for (index, object_id) in object_ids.iter().enumerate() {
+                    if object_ids[..index].contains(object_id) {
+                        return Err(PlayerActionError::NotAllowed("Card chosen more than once"));
+                    }
+
+                    match self.objects.get(object_id) {
+                        Some(object) => {
+                            if object.zone != hand_id {
+                                return Err(PlayerActionError::NotAllowed("Card not in player's hand"));
+                            }
+                        },
+                        None => return Err(PlayerActionError::NotAllowed("Object not found")),
+                    }
+                }
+
+                self.current_status = GameStatus::NeedsPlayerAction;
+
+                for &object_id in object_ids {
+                    self.move_object(object_id, graveyard_id);
+                }
+
+                // Cleanup picks back up once the replacement effect choice is
+                // made instead.
+                if self.waiting_on_replacement() {
+                    return Ok(());
+                }
+
+                // Discarding down to maximum hand size is the only discard the
+                // game asks for right now, so pick cleanup back up.
+                self.finish_cleanup();
+                self.advance_phase();
+
+                Ok(())
+            },
+            (&PlayerChoice::KeepLegend { player_id, ref object_ids }, &PlayerAction::KeepLegend { object_id }) => {
+                if acting_player_id != player_id {
+                    return Err(PlayerActionError::NotAllowed("Player is not the one making this choice"));
+                }
+
+                if !object_ids.contains(&object_id) {
+                    return Err(PlayerActionError::NotAllowed("Object is not one of the legends to choose from"));
+                }
+
+                self.current_status = GameStatus::NeedsPlayerAction;
+
+                for &other_id in object_ids.iter().filter(|&&other_id| other_id != object_id) {
+                    let graveyard_id = self.objects.get(&other_id)
+                        .and_then(|object| self.graveyard_id(object.owner));
+
+                    if let Some(graveyard_id) = graveyard_id {
+                        self.move_object(other_id, graveyard_id);
+                    }
+                }
+
+                // The state-based actions that were waiting on the choice
+                // happen now too.
+                self.process_state_based_actions();
+
+                Ok(())
+            },
+            (&PlayerChoice::ReplacementOrder { player_id, ref effect_ids }, &PlayerAction::ChooseReplacement { effect_id }) => {
+                if acting_player_id != player_id {
+                    return Err(PlayerActionError::NotAllowed("Player is not the one making this choice"));
+                }
+
+                if !effect_ids.contains(&effect_id) {
+                    return Err(PlayerActionError::NotAllowed("Effect is not one of the effects to choose from"));
+                }
+
+                self.current_status = GameStatus::NeedsPlayerAction;
+
+                let suspended = self.suspended_mutations.remove(0);
+                self.continue_mutation(suspended, Some(effect_id));
+                self.resume_suspended_mutations();
+
+                Ok(())
+            },
+            (&PlayerChoice::PayMana { player_id, .. }, &PlayerAction::ActivateManaAbility { object_id, ability_id, mana_type }) => {
+                if acting_player_id != player_id {
+                    return Err(PlayerActionError::NotAllowed("Player is not the one making this choice"));
+                }
+
+                self.activate_mana_ability(acting_player_id, object_id, ability_id, mana_type)?;
+
+                self.payment_activations.push(SourceActivation {
+                    object_id,
+                    ability_id,
+                    mana_type,
+                });
+
+                Ok(())
+            },
+            (&PlayerChoice::PayMana { player_id, object_id, ref cost, .. }, &PlayerAction::FinishPayment) => {
+                if acting_player_id != player_id {
+                    return Err(PlayerActionError::NotAllowed("Player is not the one making this choice"));
+                }
+
+                let source_id = match self.objects.get(&object_id).map(|object| &object.details) {
+                    Some(&ObjectDetails::Ability { source_id, .. }) => Some(source_id),
+                    _ => None,
+                };
+
+                match (source_id, cost.mana.as_ref()) {
+                    (Some(source_id), _) => self.finish_ability_payment(acting_player_id, source_id, cost)?,
+                    (None, Some(mana_cost)) => {
+                        self.auto_pay_mana_cost(acting_player_id, mana_cost, 0)?;
+                    },
+                    (None, None) => {},
+                }
+
+                self.current_status = GameStatus::NeedsPlayerAction;
+                self.payment_activations.clear();
+
+                if source_id.is_some() {
+                    self.consecutive_passes = 0;
+                    self.process_state_based_actions();
+                } else {
+                    self.finish_casting(acting_player_id);
+                }
+
+                Ok(())
+            },
+            (&PlayerChoice::PayMana { player_id, object_id, cast_from, .. }, &PlayerAction::CancelPayment) => {
+                if acting_player_id != player_id {
+                    return Err(PlayerActionError::NotAllowed("Player is not the one making this choice"));
+                }
+
+                self.current_status = GameStatus::NeedsPlayerAction;
+                self.undo_payment_activations(player_id);
+
+                match cast_from {
+                    // An adventurer cast from exile can still be cast from
+                    // there.
+                    Some(cast_from) => {
+                        if let Some(returned_id) = self.move_object(object_id, cast_from) {
+                            if Some(cast_from) == self.exile_id() {
+                                self.adventurers.insert(returned_id, player_id);
+                            }
+                        }
+                    },
+                    None => {
+                        self.remove_object(object_id);
+                    },
+                }
+
+                Ok(())
+            },
+            (&PlayerChoice::DeclareAttackers { player_id }, PlayerAction::DeclareAttackers { attackers }) => {
+                if acting_player_id != player_id {
+                    return Err(PlayerActionError::NotAllowed("Player is not the one making this choice"));
+                }
+
+                let state = self.layers();
+                let able_ids = self.untapped_creatures(&state, player_id);
+
+                for (index, &(attacker_id, defending_id)) in attackers.iter().enumerate() {
+                    if attackers[..index].iter().any(|&(other_id, _)| other_id == attacker_id) {
+                        return Err(PlayerActionError::NotAllowed("Creature declared as an attacker more than once"));
+                    }
+
+                    if !able_ids.contains(&attacker_id) {
+                        return Err(PlayerActionError::NotAllowed("Only untapped creatures the player controls can attack"));
+                    }
+
+                    if defending_id == player_id || !self.player_turn_order.contains(&defending_id) {
+                        return Err(PlayerActionError::NotAllowed("Creatures can only attack opponents"));
+                    }
+                }
+
+                self.current_status = GameStatus::NeedsPlayerAction;
+
+                for &(attacker_id, defending_player) in attackers {
+                    // Attacking creatures become tapped (rule 508.1f).
+                    // TODO: Vigilance
+                    if let Some(object) = self.objects.get_mut(&attacker_id) {
+                        object.set_tapped(true);
+                    }
+
+                    self.attacks.insert(attacker_id, Attack {
+                        defending_player,
+                        blockers: Vec::new(),
+                        blocked: false,
+                    });
+                }
+
+                self.grant_priority_to_active();
+
+                Ok(())
+            },
+            (&PlayerChoice::DeclareBlockers { player_id }, PlayerAction::DeclareBlockers { blockers }) => {
+                if acting_player_id != player_id {
+                    return Err(PlayerActionError::NotAllowed("Player is not the one making this choice"));
+                }
+
+                let state = self.layers();
+                let able_ids = self.untapped_creatures(&state, player_id);
+
+                for (index, &(blocker_id, attacker_id)) in blockers.iter().enumerate() {
+                    if blockers[..index].iter().any(|&(other_id, _)| other_id == blocker_id) {
+                        return Err(PlayerActionError::NotAllowed("Creature declared as a blocker more than once"));
+                    }
+
+                    if !able_ids.contains(&blocker_id) {
+                        return Err(PlayerActionError::NotAllowed("Only untapped creatures the player controls can block"));
+                    }
+
+                    let attacks_player = self.attacks.get(&attacker_id)
+                        .is_some_and(|attack| attack.defending_player == player_id);
+
+                    if !attacks_player {
+                        return Err(PlayerActionError::NotAllowed("Creatures can only block creatures attacking their controller"));
+                    }
+                }
+
+                self.current_status = GameStatus::NeedsPlayerAction;
+
+                for &(blocker_id, attacker_id) in blockers {
+                    if let Some(attack) = self.attacks.get_mut(&attacker_id) {
+                        attack.blockers.push(blocker_id);
+                        attack.blocked = true;
+                    }
+                }
+
+                match self.next_blocking_player(&state, Some(player_id)) {
+                    Some(next_player_id) => {
+                        self.current_status = GameStatus::NeedsPlayerChoice(PlayerChoice::DeclareBlockers {
+                            player_id: next_player_id,
+                        });
+                    },
+                    None => self.grant_priority_to_active(),
+                }
+
+                Ok(())
+            },
+            _ => Err(PlayerActionError::NotAllowed("Game is waiting on a different choice")),
+        }
+    }
+
+    /// Performs the mutations that were waiting on a replacement effect choice
+    /// once it's made, then picks the game back up where it stopped.
+    fn resume_suspended_mutations(&mut self) {
+        while !self.suspended_mutations.is_empty() {
+            if self.current_status != GameStatus::NeedsPlayerAction {
+                return;
+            }
+
+            let suspended = self.suspended_mutations.remove(0);
+            self.continue_mutation(suspended, None);
+        }
+
+        if self.current_status != GameStatus::NeedsPlayerAction {
+            return;
+        }
+
+        match self.priority_player {
+            // Whatever the player was about to get priority for already
+            // happened, so check state-based actions like granting priority
+            // would have.
+            Some(_) => self.process_state_based_actions(),
+
+            // The choice came up during a step's turn-based actions.
+            None => {
+                if self.current_phase == GamePhase::Cleanup {
+                    self.finish_cleanup();
+                    self.advance_phase();
+                } else if self.current_phase.grants_priority() {
+                    self.grant_priority_to_active();
+                } else {
+                    self.advance_phase();
+                }
+            },
+        }
+    }
+
+    /// Moves the game into its next step, advancing the turn once we run out of
+    /// steps, and gives the active player priority.
+    fn advance_phase(&mut self) {
+        self.prune_last_known_information();
+
+        match self.next_phase() {
+            Some(next_phase) => {
+                self.current_phase = next_phase;
+            },
+            None => {
+                // We're out of phases, advance turns!
+                self.advance_turn();
+            },
+        }
+
+        self.enter_phase();
+    }
+
+    /// Runs the step the game just moved into, then gives the active player
+    /// priority.
+    ///
+    /// Steps that no player receives priority in, like the untap step, are
+    /// passed through without stopping.
+    fn enter_phase(&mut self) {
+        self.priority_player = None;
+        self.consecutive_passes = 0;
+
+        loop {
+            // Mana empties from each player's mana pool at the end of each
+            // step and phase (rule 500.4).
+            for mana_pool in self.mana_pools.values_mut() {
+                mana_pool.empty();
+            }
+
+            self.do_turn_based_actions();
+
+            // Abilities that trigger at the beginning of a step trigger after
+            // its turn-based actions (rule 503.1a).
+            if let Some(active_id) = self.active_player {
+                let phase = self.current_phase;
+
+                self.trigger_abilities(|trigger, controller| {
+                    *trigger == Trigger::BeginningOfYourStep(phase) && controller == active_id
+                });
+            }
+
+            // Some turn-based actions, like discarding down to maximum hand
+            // size, need a player's input before the game can carry on.
+            if self.current_status != GameStatus::NeedsPlayerAction {
+                return;
+            }
+
+            if self.current_phase.grants_priority() {
+                break;
+            }
+
+            match self.next_phase() {
+                Some(next_phase) => {
+                    self.current_phase = next_phase;
+                },
+                None => {
+                    self.advance_turn();
+                },
+            }
+        }
+
+        self.grant_priority_to_active();
+    }
+
+    /// Gives the active player priority, like at the start of a step. If the
+    /// active player left the game during their turn, the next player in turn
+    /// order receives it instead (rule 800.4h).
+    fn grant_priority_to_active(&mut self) {
+        if let Some(player_id) = self.active_player.or(self.next_active_player) {
+            self.grant_priority(player_id);
+        }
+    }
+
+    /// The step the game moves to after the current one, or `None` if the turn
+    /// is over.
+    ///
+    /// If no creatures attacked, the declare blockers and combat damage steps
+    /// are skipped (rule 508.8).
+    fn next_phase(&self) -> Option<GamePhase> {
+        match self.current_phase {
+            GamePhase::DeclareAttackers if self.attacks.is_empty() => Some(GamePhase::EndOfCombat),
+            phase => phase.next(),
+        }
+    }
+
+    /// Gives the given player priority, checking state-based actions first
+    /// like the rules require whenever a player would receive priority
+    /// (rule 117.5).
+    fn grant_priority(&mut self, player_id: Id) {
+        self.priority_player = Some(player_id);
+        self.process_state_based_actions();
+    }
+
+    /// Makes the next player in turn order the active player and moves the
+    /// game to the start of their turn.
+    fn advance_turn(&mut self) {
+        // If the active player left the game, whose turn is next was worked
+        // out as they left.
+        if let Some(next_active_id) = self.next_active_player.take() {
+            self.start_turn(next_active_id);
+            return;
+        }
+
+        let current_active_id = self.active_player.unwrap();
+        let current_active_index = self.player_turn_order
+            .iter()
+            .position(|&id| id == current_active_id)
+            .expect("Active player is missing from player_turn_order!");
+
+        let next_active_index = (current_active_index + 1) % self.player_turn_order.len();
+        let next_active_id = self.player_turn_order[next_active_index];
+
+        self.start_turn(next_active_id);
+    }
+
+    /// Makes the given player the active player, starting a new turn from its
+    /// untap step.
+    fn start_turn(&mut self, player_id: Id) {
+        self.active_player = Some(player_id);
+        self.current_phase = GamePhase::Untap;
+        self.turn_number += 1;
+
+        self.continuous_effects.retain(|effect| effect.duration != Duration::UntilYourNextTurn(player_id));
+        self.replacement_effects.retain(|effect| effect.duration != Duration::UntilYourNextTurn(player_id));
+        self.layers_changed();
+        self.end_expired_effects();
+
+        for player in self.players.values_mut() {
+            player.lands_played_this_turn = 0;
+            player.additional_land_plays = 0;
+        }
+
+        self.loyalty_abilities_activated.clear();
+    }
+
+    /// The number of lands the given player may play this turn: the one the
+    /// rules allow, plus any extra granted by effects through
+    /// `Player::additional_land_plays` (rule 305.2).
+    pub fn land_plays_allowed(&self, player_id: Id) -> usize {
+        let additional_land_plays = self.players.get(&player_id)
+            .map(|player| player.additional_land_plays)
+            .unwrap_or(0);
+
+        1 + additional_land_plays
+    }
+
+    /// Performs the turn-based actions for the step the game just entered.
+    /// These happen before any player would receive priority.
+    fn do_turn_based_actions(&mut self) {
+        let active_player_id = match self.active_player {
+            Some(active_player_id) => active_player_id,
+
+            // Without an active player, only the turn-based actions that
+            // don't involve them happen (rule 800.4h).
+            None => {
+                match self.current_phase {
+                    GamePhase::CombatDamage => self.deal_all_combat_damage(),
+                    GamePhase::PostcombatMain => self.attacks.clear(),
+                    GamePhase::Cleanup => self.finish_cleanup(),
+                    _ => {},
+                }
+
+                return;
+            },
+        };
+
+        match self.current_phase {
+            GamePhase::Untap => {
+                let battlefield_id = self.battlefield_id()
+                    .expect("Unable to locate battlefield!");
+
+                self.do_phasing(active_player_id);
+
+                let state = self.layers();
+
+                let untapping_ids: Vec<Id> = self.objects.values()
+                    .filter(|object| object.zone == battlefield_id && state.controllers.get(&object.id) == Some(&active_player_id))
+                    .map(|object| object.id)
+                    .collect();
+
+                for object_id in untapping_ids {
+                    let is_stunned = self.objects.get(&object_id)
+                        .is_some_and(|object| object.is_tapped() && object.counters(&ObjectCounter::Stun) > 0);
+
+                    // Stunned permanents lose a stun counter instead of
+                    // untapping (rule 122.1d).
+                    if is_stunned {
+                        self.apply_mutation(&GameMutation::RemoveCounters {
+                            object_id,
+                            kind: ObjectCounter::Stun,
+                            amount: 1,
+                        });
+                    } else if let Some(object) = self.objects.get_mut(&object_id) {
+                        object.set_tapped(false);
+                    }
+                }
+            },
+            GamePhase::Draw => {
+                // In a two-player game, the starting player skips the draw on
+                // their first turn (rule 103.8a). It's a two-player game even
+                // if a player has already left.
+                let skips_draw = self.turn_number == 1 && self.starting_player_count == 2;
+
+                if !skips_draw {
+                    self.draw_card(active_player_id);
+                }
+            },
+            GamePhase::DeclareAttackers => {
+                let state = self.layers();
+
+                if !self.untapped_creatures(&state, active_player_id).is_empty() {
+                    self.current_status = GameStatus::NeedsPlayerChoice(PlayerChoice::DeclareAttackers {
+                        player_id: active_player_id,
+                    });
+                }
+            },
+            GamePhase::DeclareBlockers => {
+                let state = self.layers();
+
+                if let Some(player_id) = self.next_blocking_player(&state, None) {
+                    self.current_status = GameStatus::NeedsPlayerChoice(PlayerChoice::DeclareBlockers {
+                        player_id,
+                    });
+                }
+            },
+            GamePhase::CombatDamage => {
+                self.deal_all_combat_damage();
+            },
+            GamePhase::PostcombatMain => {
+                // Everything is removed from combat as the end of combat step
+                // ends (rule 511.3).
+                self.attacks.clear();
+            },
+            GamePhase::Cleanup => {
+                let hand_id = self.hand_id(active_player_id)
+                    .expect("Unable to locate player's hand!");
+
+                let hand_size = self.objects.values()
+                    .filter(|object| object.zone == hand_id)
+                    .count();
+
+                let max_hand_size = self.players.get(&active_player_id)
+                    .and_then(|player| player.max_hand_size);
+
+                if let Some(max_hand_size) = max_hand_size {
+                    if hand_size > max_hand_size {
+                        self.current_status = GameStatus::NeedsPlayerChoice(PlayerChoice::Discard {
+                            player_id: active_player_id,
+                            count: hand_size - max_hand_size,
+                        });
+
+                        return;
+                    }
+                }
+
+                self.finish_cleanup();
+            },
+            _ => {},
+        }
+    }
+
+    /// Performs the cleanup step's turn-based actions that happen after the
+    /// active player has discarded down to their maximum hand size.
+    // TODO: If state-based actions are performed or abilities trigger during
+    //       cleanup, players should receive priority, followed by another
+    //       cleanup step (rule 514.3a).
+    fn finish_cleanup(&mut self) {
+        for object in self.objects.values_mut() {
+            object.damage = 0;
+        }
+
+        self.continuous_effects.retain(|effect| effect.duration != Duration::UntilEndOfTurn);
+        self.replacement_effects.retain(|effect| effect.duration != Duration::UntilEndOfTurn);
+        self.layers_changed();
+        self.end_expired_effects();
+    }
+
+    /// The untapped creatures the given player controls, which are the ones
+    /// that can attack or block.
+    // TODO: Summoning sickness, and effects that stop creatures attacking or
+    //       blocking
+    fn untapped_creatures(&self, state: &LayerState, player_id: Id) -> Vec<Id> {
+        let battlefield_id = self.battlefield_id();
+
+        let mut creature_ids: Vec<Id> = self.objects.values()
+            .filter(|object| Some(object.zone) == battlefield_id && !object.is_tapped())
+            .filter(|object| state.controllers.get(&object.id) == Some(&player_id))
+            .filter(|object| {
+                state.characteristics.get(&object.id)
+                    .is_some_and(|characteristics| characteristics.has_card_type(CardType::Creature))
+            })
+            .map(|object| object.id)
+            .collect();
+
+        creature_ids.sort();
+        creature_ids
+    }
+
+    /// The next player being attacked who has creatures that could block,
+    /// going in turn order from the active player, starting after the given
+    /// player if there is one.
+    fn next_blocking_player(&self, state: &LayerState, after: Option<Id>) -> Option<Id> {
+        let player_count = self.player_turn_order.len();
+        let active_index = self.active_player.or(self.next_active_player)
+            .and_then(|active_id| self.player_turn_order.iter().position(|&id| id == active_id))
+            .unwrap_or(0);
+
+        let defending_ids: Vec<Id> = (0..player_count)
+            .map(|offset| self.player_turn_order[(active_index + offset) % player_count])
+            .filter(|&player_id| self.attacks.values().any(|attack| attack.defending_player == player_id))
+            .collect();
+
+        let start = after
+            .and_then(|after| defending_ids.iter().position(|&id| id == after))
+            .map_or(0, |index| index + 1);
+
+        defending_ids[start..].iter()
+            .cloned()
+            .find(|&player_id| !self.untapped_creatures(state, player_id).is_empty())
+    }
+
+    /// Has every attacking and blocking creature deal its combat damage at
+    /// once (rule 510.2).
+    ///
+    /// Blocked creatures assign lethal damage to each creature blocking them
+    /// in order, and the rest to the last one (rule 510.1c).
+    // TODO: Trample, first strike, and letting players divide damage
+    fn deal_all_combat_damage(&mut self) {
+        let state = self.layers();
+
+        let power = |object_id: Id| {
+            self.view_in(&state, object_id)
+                .and_then(|view| view.power())
+                .unwrap_or(0)
+                .max(0) as usize
+        };
+
+        let mut attacker_ids: Vec<Id> = self.attacks.keys().cloned().collect();
+        attacker_ids.sort();
+
+        let mut assignments = Vec::new();
+
+        for attacker_id in attacker_ids {
+            let attack = &self.attacks[&attacker_id];
+            let mut remaining = power(attacker_id);
+
+            if !attack.blocked {
+                assignments.push((attacker_id, DamageTarget::Player(attack.defending_player), remaining));
+                continue;
+            }
+
+            for (index, &blocker_id) in attack.blockers.iter().enumerate() {
+                let amount = if index + 1 == attack.blockers.len() {
+                    remaining
+                } else {
+                    let lethal = self.view_in(&state, blocker_id)
+                        .and_then(|view| view.toughness())
+                        .map_or(0, |toughness| (toughness - self.objects[&blocker_id].damage as i32).max(0) as usize);
+
+                    lethal.min(remaining)
+                };
+
+                remaining -= amount;
+
+                assignments.push((attacker_id, DamageTarget::Object(blocker_id), amount));
+                assignments.push((blocker_id, DamageTarget::Object(attacker_id), power(blocker_id)));
+            }
+        }
+
+        for (source_id, target, amount) in assignments {
+            if amount > 0 {
+                self.deal_combat_damage(source_id, target, amount);
+            }
+        }
+    }
+
+    /// Removes a creature from combat, like when it leaves the battlefield
+    /// (rule 506.4).
+    fn remove_from_combat(&mut self, object_id: Id) {
+        self.attacks.remove(&object_id);
+
+        for attack in self.attacks.values_mut() {
+            attack.blockers.retain(|&blocker_id| blocker_id != object_id);
+        }
+    }
+
+    /// Adds mana to the given player's mana pool.
+    pub fn add_mana(&mut self, player_id: Id, mana_type: ManaType, amount: usize) -> Result<(), ManaError> {
+        let mana_pool = self.mana_pools.get_mut(&player_id)
+            .ok_or(ManaError::PoolNotFound)?;
+
+        mana_pool.add(mana_type, amount);
+
+        Ok(())
+    }
+
+    /// Removes mana from the given player's mana pool, failing without
+    /// removing anything if they don't have enough.
+    pub fn remove_mana(&mut self, player_id: Id, mana_type: ManaType, amount: usize) -> Result<(), ManaError> {
+        let mana_pool = self.mana_pools.get_mut(&player_id)
+            .ok_or(ManaError::PoolNotFound)?;
+
+        mana_pool.remove(mana_type, amount)
+    }
+
+    /// Has the given player gain life.
+    pub fn gain_life(&mut self, player_id: Id, amount: usize) {
+        self.apply_mutation(&GameMutation::GainLife { player_id, amount });
+    }
+
+    /// Has the given player lose life. Their life total can drop below zero.
+    pub fn lose_life(&mut self, player_id: Id, amount: usize) {
+        self.apply_mutation(&GameMutation::LoseLife { player_id, amount });
+    }
+
+    /// Has the given source deal noncombat damage to a player or object, like
+    /// a spell or ability does.
+    pub fn deal_damage(&mut self, source_id: Id, target: DamageTarget, amount: usize) {
+        self.apply_mutation(&GameMutation::DealDamage { source_id, target, amount, combat: false });
+    }
+
+    /// Has the given source deal combat damage to a player or object, like
+    /// creatures do during the combat damage step.
+    pub fn deal_combat_damage(&mut self, source_id: Id, target: DamageTarget, amount: usize) {
+        self.apply_mutation(&GameMutation::DealDamage { source_id, target, amount, combat: true });
+    }
+
+    /// Whether the given player can pay an amount of life. Players can only
+    /// pay life they have, but can always pay 0 life (rule 119.4).
+    pub fn can_pay_life(&self, player_id: Id, amount: usize) -> bool {
+        match self.players.get(&player_id) {
+            Some(player) => amount == 0 || player.life >= amount as i32,
+            None => false,
+        }
+    }
+
+    /// Has the given player pay life as part of a cost, failing without
+    /// changing anything if they can't.
+    pub fn pay_life(&mut self, player_id: Id, amount: usize) -> Result<(), PlayerActionError> {
+        if !self.can_pay_life(player_id, amount) {
+            return Err(PlayerActionError::NotAllowed("Player can't pay that much life"));
+        }
+
+        if amount > 0 {
+            self.lose_life(player_id, amount);
+        }
+
+        Ok(())
+    }
+
+    /// Gives the given player counters of some kind, like poison counters.
+    pub fn add_player_counters(&mut self, player_id: Id, kind: PlayerCounter, amount: usize) {
+        self.apply_mutation(&GameMutation::AddPlayerCounters { player_id, kind, amount });
+    }
+
+    /// Removes counters from the given player, like when they pay energy.
+    /// Fails without removing anything if they don't have enough.
+    pub fn remove_player_counters(&mut self, player_id: Id, kind: PlayerCounter, amount: usize) -> Result<(), PlayerActionError> {
+        let has_enough = self.players.get(&player_id)
+            .map(|player| player.counters(&kind) >= amount)
+            .unwrap_or(false);
+
+        if !has_enough {
+            return Err(PlayerActionError::NotAllowed("Player doesn't have enough counters"));
+        }
+
+        self.apply_mutation(&GameMutation::RemovePlayerCounters { player_id, kind, amount });
+
+        Ok(())
+    }
+
+    /// Puts counters on the given object, like +1/+1 counters.
+    pub fn add_counters(&mut self, object_id: Id, kind: ObjectCounter, amount: usize) {
+        self.apply_mutation(&GameMutation::AddCounters { object_id, kind, amount });
+    }
+
+    /// Removes counters from the given object, like when a planeswalker's
+    /// loyalty ability is activated. Fails without removing anything if it
+    /// doesn't have enough.
+    pub fn remove_counters(&mut self, object_id: Id, kind: ObjectCounter, amount: usize) -> Result<(), PlayerActionError> {
+        let has_enough = self.objects.get(&object_id)
+            .map(|object| object.counters(&kind) >= amount)
+            .unwrap_or(false);
+
+        if !has_enough {
+            return Err(PlayerActionError::NotAllowed("Object doesn't have enough counters"));
+        }
+
+        self.apply_mutation(&GameMutation::RemoveCounters { object_id, kind, amount });
+
+        Ok(())
+    }
+
+    /// Applies a mutation to the game state, after letting replacement effects
+    /// change it. Mutations for players or objects that aren't in the game do
+    /// nothing.
+    ///
+    /// Returns the ID of the object the mutation ended up moving, if it moved
+    /// one, like the card a player drew.
+    ///
+    /// If a player has to choose the order replacement effects apply in, the
+    /// mutation waits for their `PlayerChoice::ReplacementOrder`, and this
+    /// returns `None`. So does any mutation applied while it's waiting.
+    pub fn apply_mutation(&mut self, mutation: &GameMutation) -> Option<Id> {
+        let suspended = SuspendedMutation::new(mutation);
+
+        if self.waiting_on_replacement() {
+            self.suspended_mutations.push(suspended);
+            return None;
+        }
+
+        self.continue_mutation(suspended, None)
+    }
+
+    /// Whether a mutation is waiting on a player to choose which replacement
+    /// effect applies to it.
+    fn waiting_on_replacement(&self) -> bool {
+        matches!(self.current_status, GameStatus::NeedsPlayerChoice(PlayerChoice::ReplacementOrder { .. }))
+    }
+
+    /// Carries on replacing a mutation, applying the chosen effect first if
+    /// there is one, then performs whatever it turns into. Suspends it again
+    /// if there's another choice to make.
+    fn continue_mutation(&mut self, mut suspended: SuspendedMutation, chosen_id: Option<Id>) -> Option<Id> {
+        if let Some(choice) = self.replace_mutation(&mut suspended, chosen_id) {
+            self.suspended_mutations.insert(0, suspended);
+            self.current_status = GameStatus::NeedsPlayerChoice(choice);
+
+            return None;
+        }
+
+        self.use_shields(&suspended.shields_used);
+
+        let mut moved_id = None;
+
+        for mutation in suspended.replaced {
+            moved_id = self.perform_mutation(&mutation).or(moved_id);
+        }
+
+        moved_id
+    }
+
+    /// Applies a mutation exactly as it is, without any replacement effects.
+    fn perform_mutation(&mut self, mutation: &GameMutation) -> Option<Id> {
+        self.layers_changed();
+
+        match *mutation {
+            GameMutation::GainLife { player_id, amount } => {
+                if let Some(player) = self.players.get_mut(&player_id) {
+                    player.life += amount as i32;
+                }
+            },
+            GameMutation::LoseLife { player_id, amount } => {
+                if let Some(player) = self.players.get_mut(&player_id) {
+                    player.life -= amount as i32;
+                }
+            },
+            GameMutation::AddPlayerCounters { player_id, ref kind, amount } => {
+                if let Some(player) = self.players.get_mut(&player_id) {
+                    *player.counters.entry(kind.clone()).or_insert(0) += amount;
+                }
+            },
+            GameMutation::RemovePlayerCounters { player_id, ref kind, amount } => {
+                if let Some(player) = self.players.get_mut(&player_id) {
+                    let remaining = player.counters(kind).saturating_sub(amount);
+
+                    if remaining == 0 {
+                        player.counters.remove(kind);
+                    } else {
+                        player.counters.insert(kind.clone(), remaining);
+                    }
+                }
+            },
+            GameMutation::AddCounters { object_id, ref kind, amount } => {
+                if let Some(object) = self.objects.get_mut(&object_id) {
+                    *object.counters.entry(kind.clone()).or_insert(0) += amount;
+                }
+            },
+            GameMutation::RemoveCounters { object_id, ref kind, amount } => {
+                if let Some(object) = self.objects.get_mut(&object_id) {
+                    let remaining = object.counters(kind).saturating_sub(amount);
+
+                    if remaining == 0 {
+                        object.counters.remove(kind);
+                    } else {
+                        object.counters.insert(kind.clone(), remaining);
+                    }
+                }
+            },
+            GameMutation::DrawCard { player_id } => {
+                let library_id = self.library_id(player_id)?;
+                let hand_id = self.hand_id(player_id)?;
+
+                let top_card_id = match self.zones.get(&library_id)?.order.last() {
+                    Some(&card_id) => card_id,
+                    None => {
+                        if let Some(player) = self.players.get_mut(&player_id) {
+                            player.drew_from_empty_library = true;
+                        }
+
+                        return None;
+                    },
+                };
+
+                return self.move_object(top_card_id, hand_id);
+            },
+            GameMutation::DealDamage { target, amount, .. } if amount > 0 => {
+                self.perform_damage(target, amount);
+            },
+            GameMutation::DealDamage { .. } => {},
+            GameMutation::MoveObject { object_id, zone_id, face, tapped } => {
+                return self.perform_move(object_id, zone_id, face, tapped);
+            },
+        }
+
+        None
+    }
+
+    /// Carries out the results of damage that's being dealt (rule 120.3).
+    /// Damage to a player makes them lose that much life, damage to a
+    /// planeswalker removes that many loyalty counters from it, and damage to
+    /// a creature is marked on it.
+    // TODO: Wither, infect, lifelink, and deathtouch
+    fn perform_damage(&mut self, target: DamageTarget, amount: usize) {
+        let object_id = match target {
+            DamageTarget::Player(player_id) => {
+                self.apply_mutation(&GameMutation::LoseLife { player_id, amount });
+                return;
+            },
+            DamageTarget::Object(object_id) => object_id,
+        };
+
+        let on_battlefield = self.objects.get(&object_id)
+            .is_some_and(|object| Some(object.zone) == self.battlefield_id());
+
+        let characteristics = match self.view_object(object_id).and_then(|view| view.characteristics) {
+            Some(characteristics) if on_battlefield => characteristics,
+            _ => return,
+        };
+
+        if characteristics.has_card_type(CardType::Planeswalker) {
+            self.apply_mutation(&GameMutation::RemoveCounters {
+                object_id,
+                kind: ObjectCounter::Loyalty,
+                amount,
+            });
+        }
+
+        if characteristics.has_card_type(CardType::Creature) {
+            if let Some(object) = self.objects.get_mut(&object_id) {
+                object.damage += amount;
+            }
+        }
+    }
+
+    /// Works out what a mutation turns into once replacement effects have
+    /// changed it.
+    ///
+    /// Whoever the mutation affects picks which effect applies first, then the
+    /// remaining effects are checked against the changed mutation, and so on.
+    /// Each effect can only change a mutation once (rule 616.1).
+    ///
+    /// `chosen_id` is the effect a player chose to apply first, if they were
+    /// asked. Returns the choice to ask for if a player has to choose again,
+    /// leaving the rest of the mutation for later.
+    fn replace_mutation(&self, suspended: &mut SuspendedMutation, mut chosen_id: Option<Id>) -> Option<PlayerChoice> {
+        let state = self.layers();
+
+        while !suspended.pending.is_empty() {
+            let (mutation, applied) = suspended.pending[0].clone();
+            let mut effects = Vec::new();
+
+            for mut effect in self.replacement_effects_for(&state, &mutation) {
+                if applied.contains(&effect.key) {
+                    continue;
+                }
+
+                // A shield that's already prevented some damage from this
+                // event only has what's left of it.
+                if let EffectKey::Created(effect_id) = effect.key {
+                    if let ReplacementEffectKind::PreventionShield { ref mut amount, .. } = effect.kind {
+                        *amount = amount.saturating_sub(suspended.shields_used.get(&effect_id).cloned().unwrap_or(0));
+
+                        if *amount == 0 {
+                            continue;
+                        }
+                    }
+                }
+
+                effects.push(effect);
+            }
+
+            let effect = match self.choose_replacement(&state, &mutation, &effects, chosen_id.take()) {
+                Ok(Some(effect)) => effect,
+                Ok(None) => {
+                    suspended.pending.remove(0);
+                    suspended.replaced.push(mutation);
+                    continue;
+                },
+                Err(choice) => return Some(choice),
+            };
+
+            suspended.pending.remove(0);
+
+            let mut applied = applied;
+            applied.push(effect.key);
+
+            if let (EffectKey::Created(effect_id), GameMutation::DealDamage { amount: damage, .. }) = (effect.key, &mutation) {
+                if let ReplacementEffectKind::PreventionShield { amount, .. } = effect.kind {
+                    *suspended.shields_used.entry(effect_id).or_insert(0) += amount.min(*damage);
+                }
+            }
+
+            // Whatever happens instead happens in place of the mutation, before
+            // anything after it.
+            for (index, new_mutation) in self.replace_with(effect, &mutation).into_iter().enumerate() {
+                suspended.pending.insert(index, (new_mutation, applied.clone()));
+            }
+        }
+
+        None
+    }
+
+    /// Wears down prevention shields by however much damage they prevented,
+    /// ending any that are used up (rule 615.7).
+    fn use_shields(&mut self, shields_used: &HashMap<Id, usize>) {
+        if shields_used.is_empty() {
+            return;
+        }
+
+        for effect in &mut self.replacement_effects {
+            if let ReplacementEffectKind::PreventionShield { ref mut amount, .. } = effect.kind {
+                *amount = amount.saturating_sub(shields_used.get(&effect.id).cloned().unwrap_or(0));
+            }
+        }
+
+        self.replacement_effects.retain(|effect| {
+            !matches!(effect.kind, ReplacementEffectKind::PreventionShield { amount: 0, .. })
+        });
+    }
+
+    /// Every replacement effect that would change the given mutation: ones
+    /// created by spells and abilities, ones from the abilities of permanents
+    /// and emblems, and ones from an object's own abilities as it enters the
+    /// battlefield (rule 614.12).
+    fn replacement_effects_for(&self, state: &LayerState, mutation: &GameMutation) -> Vec<ActiveReplacement> {
+        let mut effects: Vec<ActiveReplacement> = self.replacement_effects.iter()
+            .map(|effect| ActiveReplacement {
+                key: EffectKey::Created(effect.id),
+                source_id: None,
+                controller: effect.controller,
+                timestamp: effect.timestamp,
+                kind: effect.kind.clone(),
+            })
+            .collect();
+
+        let entering_id = match *mutation {
+            GameMutation::MoveObject { object_id, zone_id, .. } if Some(zone_id) == self.battlefield_id() => Some(object_id),
+            _ => None,
+        };
+
+        for (&source_id, characteristics) in &state.characteristics {
+            if !self.abilities_function(source_id) && Some(source_id) != entering_id {
+                continue;
+            }
+
+            for (&ability_id, ability) in &characteristics.abilities {
+                if let Ability::Replacement(ref kind) = *ability {
+                    effects.push(ActiveReplacement {
+                        key: EffectKey::Static { source_id, ability_id },
+                        source_id: Some(source_id),
+                        controller: state.controllers[&source_id],
+                        timestamp: self.objects[&source_id].timestamp,
+                        kind: kind.clone(),
+                    });
+                }
+            }
+        }
+
+        // Damage that can't be prevented ignores prevention effects, rather than
+        // having them apply and do nothing (rule 615.12).
+        let cant_be_prevented = match *mutation {
+            GameMutation::DealDamage { source_id, target, combat, .. } => {
+                effects.iter().any(|effect| {
+                    match effect.kind {
+                        ReplacementEffectKind::DamageCantBePrevented { ref damage } => {
+                            self.damage_matches(state, damage, effect.controller, source_id, target, combat)
+                        },
+                        _ => false,
+                    }
+                })
+            },
+            _ => false,
+        };
+
+        effects.retain(|effect| {
+            !(cant_be_prevented && effect.kind.is_prevention()) && self.replacement_applies(state, effect, mutation)
+        });
+        effects.sort_by_key(|effect| (effect.timestamp, effect.key));
+        effects
+    }
+
+    /// Whether a replacement effect would change the given mutation.
+    fn replacement_applies(&self, state: &LayerState, effect: &ActiveReplacement, mutation: &GameMutation) -> bool {
+        let battlefield_id = self.battlefield_id();
+
+        let matches = |filter: &ObjectFilter, object_id: Id| {
+            self.view_in(state, object_id)
+                .and_then(|view| {
+                    let same_controller = view.object.controller == effect.controller;
+
+                    view.characteristics
+                        .map(|characteristics| filter.matches(&characteristics, Some(object_id) == effect.source_id, same_controller))
+                })
+                .unwrap_or(false)
+        };
+
+        match *mutation {
+            GameMutation::MoveObject { object_id, zone_id, .. } => {
+                let to_graveyard = self.zones.get(&zone_id)
+                    .is_some_and(|zone| matches!(zone.details, ZoneDetails::Graveyard { .. }));
+
+                match effect.kind {
+                    ReplacementEffectKind::EntersTapped => {
+                        Some(zone_id) == battlefield_id && Some(object_id) == effect.source_id
+                    },
+                    ReplacementEffectKind::PermanentsEnterTapped { ref affected } => {
+                        Some(zone_id) == battlefield_id && matches(affected, object_id)
+                    },
+                    ReplacementEffectKind::ExileInstead { ref affected } => {
+                        to_graveyard && matches(affected, object_id)
+                    },
+                    _ => false,
+                }
+            },
+            GameMutation::DrawCard { player_id } => {
+                match effect.kind {
+                    ReplacementEffectKind::DrawInstead { .. } => player_id == effect.controller,
+                    _ => false,
+                }
+            },
+            GameMutation::DealDamage { source_id, target, amount, combat } => {
+                match effect.kind {
+                    ReplacementEffectKind::PreventDamage { ref damage, .. } => {
+                        amount > 0 && self.damage_matches(state, damage, effect.controller, source_id, target, combat)
+                    },
+                    ReplacementEffectKind::PreventionShield { ref damage, .. } => {
+                        amount > 0 && effect.source_id.is_none()
+                            && self.damage_matches(state, damage, effect.controller, source_id, target, combat)
+                    },
+                    _ => false,
+                }
+            },
+            GameMutation::AddCounters { object_id, .. } => {
+                let on_battlefield = self.objects.get(&object_id)
+                    .is_some_and(|object| Some(object.zone) == battlefield_id);
+
+                match effect.kind {
+                    ReplacementEffectKind::DoubleCounters { ref affected } => {
+                        on_battlefield && matches(affected, object_id)
+                    },
+                    _ => false,
+                }
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether damage from the given source to the given target matches a
+    /// filter belonging to an effect the given player controls.
+    ///
+    /// A source that has left its zone, like a creature that died with its
+    /// ability on the stack, still deals the damage, as it last existed
+    /// (rule 609.7a).
+    fn damage_matches(&self, state: &LayerState, filter: &DamageFilter, controller: Id, source_id: Id, target: DamageTarget, combat: bool) -> bool {
+        if !filter.matches(source_id, target, combat) {
+            return false;
+        }
+
+        let source = self.view_in(state, source_id)
+            .or_else(|| self.last_known_information(source_id).map(|info| info.view.clone()));
+
+        match source {
+            Some(source) => filter.matches_source(source.characteristics.as_ref(), source.object.controller == controller),
+            None => filter.matches_source(None, false),
+        }
+    }
+
+    /// What a mutation turns into once the given replacement effect has
+    /// changed it.
+    fn replace_with(&self, effect: &ActiveReplacement, mutation: &GameMutation) -> Vec<GameMutation> {
+        match (effect.kind.clone(), mutation.clone()) {
+            (ReplacementEffectKind::EntersTapped, GameMutation::MoveObject { object_id, zone_id, face, .. })
+            | (ReplacementEffectKind::PermanentsEnterTapped { .. }, GameMutation::MoveObject { object_id, zone_id, face, .. }) => {
+                vec![GameMutation::MoveObject { object_id, zone_id, face, tapped: true }]
+            },
+            (ReplacementEffectKind::DrawInstead { instead }, GameMutation::DrawCard { .. }) => {
+                instead
+            },
+            (ReplacementEffectKind::ExileInstead { .. }, GameMutation::MoveObject { object_id, face, tapped, zone_id }) => {
+                let zone_id = self.exile_id().unwrap_or(zone_id);
+
+                vec![GameMutation::MoveObject { object_id, zone_id, face, tapped }]
+            },
+            (ReplacementEffectKind::PreventDamage { amount: prevented, .. }, GameMutation::DealDamage { source_id, target, amount, combat }) => {
+                let amount = prevented.map_or(0, |prevented| amount.saturating_sub(prevented));
+
+                vec![GameMutation::DealDamage { source_id, target, amount, combat }]
+            },
+            (ReplacementEffectKind::PreventionShield { amount: prevented, .. }, GameMutation::DealDamage { source_id, target, amount, combat }) => {
+                vec![GameMutation::DealDamage { source_id, target, amount: amount.saturating_sub(prevented), combat }]
+            },
+            (ReplacementEffectKind::DoubleCounters { .. }, GameMutation::AddCounters { object_id, kind, amount }) => {
+                vec![GameMutation::AddCounters { object_id, kind, amount: amount * 2 }]
+            },
+            (_, mutation) => vec![mutation],
+        }
+    }
+
+    /// Picks which of the replacement effects that would change a mutation
+    /// applies first, or `None` if none of them would.
+    ///
+    /// The affected player, or the controller of the affected object, makes
+    /// the choice (rule 616.1). They're only asked if the effects would do
+    /// different things, and `chosen_id` is their answer if they've given
+    /// one. Otherwise, this returns the choice to ask them for.
+    fn choose_replacement<'a>(&self, state: &LayerState, mutation: &GameMutation, effects: &'a [ActiveReplacement], chosen_id: Option<Id>) -> Result<Option<&'a ActiveReplacement>, PlayerChoice> {
+        let first = match effects.first() {
+            Some(first) => first,
+            None => return Ok(None),
+        };
+
+        if let Some(chosen) = chosen_id.and_then(|chosen_id| effects.iter().find(|effect| effect.key.id() == chosen_id)) {
+            return Ok(Some(chosen));
+        }
+
+        // Effects are already sorted oldest first, which is as good an order as
+        // any when it doesn't matter.
+        if effects.iter().all(|effect| effect.kind == first.kind) {
+            return Ok(Some(first));
+        }
+
+        let chooser = match *mutation {
+            GameMutation::GainLife { player_id, .. }
+            | GameMutation::LoseLife { player_id, .. }
+            | GameMutation::AddPlayerCounters { player_id, .. }
+            | GameMutation::RemovePlayerCounters { player_id, .. }
+            | GameMutation::DrawCard { player_id } => Some(player_id),
+            GameMutation::AddCounters { object_id, .. }
+            | GameMutation::RemoveCounters { object_id, .. }
+            | GameMutation::MoveObject { object_id, .. }
+            | GameMutation::DealDamage { target: DamageTarget::Object(object_id), .. } => {
+                self.view_in(state, object_id).map(|view| view.object.controller)
+            },
+            GameMutation::DealDamage { target: DamageTarget::Player(player_id), .. } => Some(player_id),
+        };
+
+        // Nobody can be asked once the game is over, or if the player has left
+        // it.
+        let game_over = matches!(self.current_status, GameStatus::Ended(_));
+
+        match chooser.filter(|player_id| self.player_turn_order.contains(player_id)) {
+            Some(player_id) if !game_over => Err(PlayerChoice::ReplacementOrder {
+                player_id,
+                effect_ids: effects.iter().map(|effect| effect.key.id()).collect(),
+            }),
+            _ => Ok(Some(first)),
+        }
+    }
+
+    /// Starts a new replacement effect for the given player, returning the
+    /// effect's ID.
+    pub fn add_replacement_effect(&mut self, controller: Id, duration: Duration, kind: ReplacementEffectKind) -> Id {
+        let effect = ReplacementEffect {
+            id: get_id(),
+            controller,
+            timestamp: get_timestamp(),
+            duration,
+            kind,
+        };
+        let effect_id = effect.id;
+
+        self.replacement_effects.push(effect);
+        self.end_expired_effects();
+
+        effect_id
+    }
+
+    /// Whether there are no spells or abilities waiting to resolve.
+    pub fn stack_is_empty(&self) -> bool {
+        self.stack_id()
+            .and_then(|stack_id| self.zones.get(&stack_id))
+            .map(|stack| stack.order.is_empty())
+            .unwrap_or(true)
+    }
+
+    /// Resolves the spell or ability on top of the stack.
+    fn resolve_top_of_stack(&mut self) {
+        let stack_id = self.stack_id()
+            .expect("Unable to locate stack!");
+
+        let top_object_id = match self.zones.get(&stack_id).and_then(|stack| stack.order.last()) {
+            Some(&object_id) => object_id,
+            None => return,
+        };
+
+        let battlefield_id = self.battlefield_id()
+            .expect("Unable to locate battlefield!");
+
+        let object = self.objects.get(&top_object_id)
+            .expect("Object on the stack is missing!");
+
+        let is_permanent = self.characteristics(top_object_id)
+            .is_some_and(|characteristics| characteristics.is_permanent());
+
+        match object.details {
+            // Permanent spells become permanents as they resolve (rule 608.3).
+            ObjectDetails::Card { .. } if is_permanent => {
+                self.move_object(top_object_id, battlefield_id);
+            },
+            // Instants and sorceries follow the instructions of their spell
+            // abilities, then are put into their owner's graveyard once
+            // they're done resolving (rule 608.2).
+            ObjectDetails::Card { definition_id } => {
+                let owner = object.owner;
+                let controller = object.controller;
+
+                let is_adventure = object.face == Some(Face::Back)
+                    && self.registry.get(definition_id).is_some_and(|definition| definition.layout == CardLayout::Adventure);
+
+                let effects: Vec<Effect> = self.characteristics(top_object_id)
+                    .map(|characteristics| {
+                        characteristics.abilities.values()
+                            .filter_map(|ability| match *ability {
+                                Ability::Spell(ref effects) => Some(effects.clone()),
+                                _ => None,
+                            })
+                            .flatten()
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                // The spell itself is the source of whatever it does
+                // (rule 609.7a).
+                for effect in &effects {
+                    self.perform_effect(top_object_id, controller, effect);
+                }
+
+                // Adventures are exiled instead, so that the creature can be
+                // cast later (rule 715.4).
+                if is_adventure {
+                    let exile_id = self.exile_id()
+                        .expect("Unable to locate exile!");
+
+                    if let Some(exiled_id) = self.move_object(top_object_id, exile_id) {
+                        if self.objects.get(&exiled_id).is_some_and(|object| object.zone == exile_id) {
+                            self.adventurers.insert(exiled_id, controller);
+                        }
+                    }
+                } else {
+                    let graveyard_id = self.graveyard_id(owner)
+                        .expect("Unable to locate graveyard!");
+
+                    self.move_object(top_object_id, graveyard_id);
+                }
+            },
+            ObjectDetails::Ability { source_id, ref effects } => {
+                let controller = object.controller;
+                let effects = effects.clone();
+
+                for effect in &effects {
+                    self.perform_effect(source_id, controller, effect);
+                }
+
+                // Abilities cease to exist once they're done resolving
+                // (rule 608.2n).
+                self.remove_object(top_object_id);
+            },
+            // Tokens and emblems can't be cast, so neither should ever end up
+            // here.
+            ObjectDetails::Token { .. } | ObjectDetails::Emblem { .. } => {
+                self.remove_object(top_object_id);
+            },
+        }
+
+        self.prune_last_known_information();
+    }
+
+    /// Performs one effect of a resolving spell or ability on behalf of its
+    /// controller. The source is whatever the spell or ability is from, which
+    /// deals any damage it does.
+    ///
+    /// The source may have left its zone since, in which case it's the object
+    /// as it last existed, and keeps its old ID. Anything that needs to know
+    /// what it was looks at its last known information (rule 609.7a).
+    fn perform_effect(&mut self, source_id: Id, controller: Id, effect: &Effect) {
+        match *effect {
+            Effect::DrawCards { count } => {
+                for _ in 0..count {
+                    self.draw_card(controller);
+                }
+            },
+            Effect::GainLife { amount } => {
+                self.gain_life(controller, amount);
+            },
+            Effect::CreateEmblem { definition_id } => {
+                self.create_emblem(definition_id, controller);
+            },
+            Effect::GainControl { object_id, duration } => {
+                self.gain_control(controller, object_id, duration);
+            },
+            Effect::DealDamage { target, amount } => {
+                self.deal_damage(source_id, target, amount);
+            },
+            Effect::DamageEachOpponent { amount } => {
+                let opponent_ids: Vec<Id> = self.player_turn_order.iter()
+                    .cloned()
+                    .filter(|&player_id| player_id != controller)
+                    .collect();
+
+                for opponent_id in opponent_ids {
+                    self.deal_damage(source_id, DamageTarget::Player(opponent_id), amount);
+                }
+            },
+        }
+    }
+
+    /// Has the given player draw the top card of their library, returning the
+    /// ID of the card in their hand.
+    ///
+    /// Drawing from an empty library returns `None`, and makes the player lose
+    /// the next time state-based actions are checked.
+    pub fn draw_card(&mut self, player_id: Id) -> Option<Id> {
+        self.apply_mutation(&GameMutation::DrawCard { player_id })
+    }
+
+    /// Creates a new card from the given definition in the game's registry,
+    /// owned by the given player and put into the given zone. Returns the new
+    /// object's ID.
+    pub fn create_card(&mut self, definition_id: Id, zone_id: Id, owner: Id) -> Id {
+        self.add_object(Object {
+            id: get_id(),
+            zone: zone_id,
+            timestamp: get_timestamp(),
+            owner,
+            controller: owner,
+            damage: 0,
+            status: PermanentStatus::default(),
+            counters: HashMap::new(),
+            face: None,
+            attached_to: None,
+            details: ObjectDetails::Card {
+                definition_id,
+            },
+        })
+    }
+
+    /// Creates a token from the given definition in the game's registry,
+    /// putting it onto the battlefield under the given player's control.
+    /// Returns the token's ID, or `None` if there's no battlefield.
+    ///
+    /// The player who creates a token owns it (rule 111.2).
+    pub fn create_token(&mut self, definition_id: Id, controller: Id) -> Option<Id> {
+        let battlefield_id = self.battlefield_id()?;
+
+        let token_id = self.add_object(Object {
+            id: get_id(),
+            zone: battlefield_id,
+            timestamp: get_timestamp(),
+            owner: controller,
+            controller,
+            damage: 0,
+            status: PermanentStatus::default(),
+            counters: HashMap::new(),
+            face: None,
+            attached_to: None,
+            details: ObjectDetails::Token {
+                definition_id,
+            },
+        });
+
+        self.add_starting_counters(token_id);
+
+        Some(token_id)
+    }
+
+    /// Gives the given player an emblem from the given definition in the game's
+    /// registry, putting it into the command zone. Returns the emblem's ID, or
+    /// `None` if there's no command zone.
+    pub fn create_emblem(&mut self, definition_id: Id, owner: Id) -> Option<Id> {
+        let command_id = self.command_id()?;
+
+        Some(self.add_object(Object {
+            id: get_id(),
+            zone: command_id,
+            timestamp: get_timestamp(),
+            owner,
+            controller: owner,
+            damage: 0,
+            status: PermanentStatus::default(),
+            counters: HashMap::new(),
+            face: None,
+            attached_to: None,
+            details: ObjectDetails::Emblem {
+                definition_id,
+            },
+        }))
+    }
+
+    /// Creates a token that's a copy of the given object, like with Cackling
+    /// Counterpart. Returns the token's ID, or `None` if the object doesn't
+    /// exist or has no characteristics to copy.
+    ///
+    /// The copy gets the object's copiable values: the characteristics from
+    /// its definition and any copy effects, but not its status, counters, or
+    /// any other effects (rule 707.2).
+    pub fn create_token_copy(&mut self, object_id: Id, controller: Id) -> Option<Id> {
+        let definition_id = self.objects.get(&object_id)?.definition_id()?;
+        let copiable_values = self.copiable_values(object_id)?;
+
+        let token_id = self.create_token(definition_id, controller)?;
+
+        // The token's definition is only the whole story if nothing's changed
+        // the original's copiable values, like it being a Clone itself.
+        if self.characteristics(token_id) != Some(&copiable_values) {
+            self.add_continuous_effect(token_id, Duration::Indefinite, ContinuousEffectKind::BecomeCopy {
+                characteristics: Box::new(copiable_values),
+            });
+        }
+
+        Some(token_id)
+    }
+
+    /// Puts the counters an object enters the battlefield with onto it. For now
+    /// that's only planeswalkers, which enter with their printed loyalty in
+    /// loyalty counters (rule 306.5b).
+    fn add_starting_counters(&mut self, object_id: Id) {
+        let starting_loyalty = self.characteristics(object_id)
+            .filter(|characteristics| characteristics.has_card_type(CardType::Planeswalker))
+            .and_then(|characteristics| characteristics.loyalty)
+            .map(|loyalty| loyalty.value().max(0) as usize)
+            .unwrap_or(0);
+
+        if starting_loyalty > 0 {
+            self.add_counters(object_id, ObjectCounter::Loyalty, starting_loyalty);
+        }
+    }
+
+    /// Looks up the characteristics of an object, as defined by its card
+    /// definition. Abilities on the stack don't have any.
+    ///
+    /// Cards with more than one face have the characteristics of whichever
+    /// face is up right now.
+    pub fn characteristics(&self, object_id: Id) -> Option<&Characteristics> {
+        let object = self.objects.get(&object_id)?;
+        let definition = self.registry.get(object.definition_id()?)?;
+
+        let face = match definition.layout {
+            CardLayout::Flip if object.status.flipped => Some(Face::Back),
+            _ => object.face,
+        };
+
+        match face {
+            Some(face) => definition.face(face),
+            None => Some(&definition.characteristics),
+        }
+    }
+
+    /// Transforms a double-faced permanent, turning it to its other face. It
+    /// stays the same object (rule 712.18). Returns `false` if the object
+    /// isn't a transforming double-faced permanent.
+    pub fn transform(&mut self, object_id: Id) -> bool {
+        let battlefield_id = self.battlefield_id();
+
+        let can_transform = self.objects.get(&object_id)
+            .filter(|object| Some(object.zone) == battlefield_id)
+            .and_then(|object| object.definition_id())
+            .and_then(|definition_id| self.registry.get(definition_id))
+            .is_some_and(|definition| definition.layout == CardLayout::Transform);
+
+        if !can_transform {
+            return false;
+        }
+
+        if let Some(object) = self.objects.get_mut(&object_id) {
+            object.face = match object.face {
+                Some(Face::Back) => Some(Face::Front),
+                _ => Some(Face::Back),
+            };
+        }
+
+        self.layers_changed();
+
+        true
+    }
+
+    /// Flips a flip card permanent, giving it the characteristics printed
+    /// upside-down on it (rule 710.2). Returns `false` if the object isn't a
+    /// flip card on the battlefield, or has already been flipped.
+    pub fn flip(&mut self, object_id: Id) -> bool {
+        let battlefield_id = self.battlefield_id();
+
+        let can_flip = self.objects.get(&object_id)
+            .filter(|object| Some(object.zone) == battlefield_id && !object.status.flipped)
+            .and_then(|object| object.definition_id())
+            .and_then(|definition_id| self.registry.get(definition_id))
+            .is_some_and(|definition| definition.layout == CardLayout::Flip);
+
+        if let (true, Some(object)) = (can_flip, self.objects.get_mut(&object_id)) {
+            object.status.flipped = true;
+        }
+
+        self.layers_changed();
+
+        can_flip
+    }
+
+    /// An object's characteristics before any continuous effects apply: the
+    /// ones from its card definition, unless it's face down (rule 708.2).
+    fn base_characteristics(&self, object_id: Id) -> Option<Characteristics> {
+        let characteristics = self.characteristics(object_id)?;
+
+        if self.objects.get(&object_id)?.status.face_down {
+            return Some(Characteristics::face_down());
+        }
+
+        Some(characteristics.clone())
+    }
+
+    /// Turns a permanent face down, making it a 2/2 creature with no other
+    /// characteristics. It stays the same object (rule 708.2). Returns `false`
+    /// if the object isn't a face-up permanent card.
+    pub fn turn_face_down(&mut self, object_id: Id) -> bool {
+        let battlefield_id = self.battlefield_id();
+
+        match self.objects.get_mut(&object_id) {
+            Some(object) if Some(object.zone) == battlefield_id && object.is_card() && !object.status.face_down => {
+                object.status.face_down = true;
+            },
+            _ => return false,
+        }
+
+        self.layers_changed();
+
+        true
+    }
+
+    /// Turns a face-down permanent face up, giving it its printed
+    /// characteristics again (rule 708.8). Returns `false` if the object
+    /// isn't a face-down permanent.
+    pub fn turn_face_up(&mut self, object_id: Id) -> bool {
+        match self.objects.get_mut(&object_id) {
+            Some(object) if object.status.face_down => {
+                object.status.face_down = false;
+            },
+            _ => return false,
+        }
+
+        self.layers_changed();
+
+        true
+    }
+
+    /// Attaches one permanent to another, like an Aura to the permanent it
+    /// enchants (rule 701.3). Returns `false` if either isn't on the
+    /// battlefield. Attaching something where it isn't allowed to be is left
+    /// for state-based actions to deal with.
+    // TODO: Aura spells should attach to their target as they resolve (rule
+    //       303.4f), once spells can have targets.
+    pub fn attach(&mut self, object_id: Id, attached_id: Id) -> bool {
+        let battlefield_id = self.battlefield_id();
+        let on_battlefield = |object: &Object| Some(object.zone) == battlefield_id && !object.is_emblem();
+
+        if !self.objects.get(&attached_id).is_some_and(on_battlefield) {
+            return false;
+        }
+
+        match self.objects.get_mut(&object_id) {
+            Some(object) if on_battlefield(object) => {
+                object.attached_to = Some(attached_id);
+            },
+            _ => return false,
+        }
+
+        self.layers_changed();
+
+        true
+    }
+
+    /// Phases a permanent out, along with everything attached to it. They're
+    /// treated as though they don't exist until they phase back in, but they
+    /// don't change zones (rules 702.26b and 702.26g). Returns `false` if the
+    /// object isn't a phased-in permanent.
+    pub fn phase_out(&mut self, object_id: Id) -> bool {
+        let battlefield_id = self.battlefield_id();
+
+        match self.objects.get_mut(&object_id) {
+            Some(object) if Some(object.zone) == battlefield_id && !object.is_emblem() && !object.status.phased_out => {
+                object.status.phased_out = true;
+            },
+            _ => return false,
+        }
+
+        self.layers_changed();
+
+        self.remove_from_combat(object_id);
+
+        for attachment_id in self.attachments(object_id) {
+            self.phase_out(attachment_id);
+        }
+
+        true
+    }
+
+    /// Phases a phased-out permanent back in, along with everything that
+    /// phased out with it. Returns `false` if the object isn't phased out.
+    pub fn phase_in(&mut self, object_id: Id) -> bool {
+        match self.objects.get_mut(&object_id) {
+            Some(object) if object.status.phased_out => {
+                object.status.phased_out = false;
+            },
+            _ => return false,
+        }
+
+        self.layers_changed();
+
+        for attachment_id in self.attachments(object_id) {
+            self.phase_in(attachment_id);
+        }
+
+        true
+    }
+
+    /// The objects attached to the given object.
+    fn attachments(&self, object_id: Id) -> Vec<Id> {
+        self.objects.values()
+            .filter(|object| object.attached_to == Some(object_id) && object.id != object_id)
+            .map(|object| object.id)
+            .collect()
+    }
+
+    /// Phases permanents in and out at the start of the given player's untap
+    /// step, before anything untaps (rule 502.1). Their permanents with
+    /// phasing phase out, and the ones that phased out phase in, all at once.
+    fn do_phasing(&mut self, player_id: Id) {
+        let battlefield_id = self.battlefield_id();
+        let state = self.layers();
+
+        let phasing_ids: Vec<Id> = self.objects.values()
+            .filter(|object| Some(object.zone) == battlefield_id)
+            .filter(|object| {
+                // Phased-out permanents aren't in the layer state, so they
+                // phase in for whoever controlled them before any effects.
+                // Ones that phased out along with something they're attached
+                // to phase in with it instead (rule 702.26h).
+                // TODO: Control-changing effects on phased-out permanents
+                if object.status.phased_out {
+                    let attached_phased_out = object.attached_to
+                        .and_then(|attached_id| self.objects.get(&attached_id))
+                        .is_some_and(|attached| attached.status.phased_out);
+
+                    return object.controller == player_id && !attached_phased_out;
+                }
+
+                state.controllers.get(&object.id) == Some(&player_id)
+                    && state.characteristics.get(&object.id).is_some_and(|characteristics| {
+                        characteristics.abilities.values().any(|ability| *ability == Ability::Phasing)
+                    })
+            })
+            .map(|object| object.id)
+            .collect();
+
+        for object_id in phasing_ids {
+            if !self.phase_in(object_id) {
+                self.phase_out(object_id);
+            }
+        }
+    }
+
+    /// Finds out who controls an object right now, taking control-changing
+    /// effects into account.
+    pub fn controller(&self, object_id: Id) -> Option<Id> {
+        self.controller_in(&self.layers(), object_id)
+    }
+
+    /// Finds out who controls an object using continuous effects that have
+    /// already been applied.
+    fn controller_in(&self, state: &LayerState, object_id: Id) -> Option<Id> {
+        self.view_in(state, object_id).map(|view| view.object.controller)
+    }
+
+    /// Starts a new continuous effect on an object, returning the effect's ID.
+    pub fn add_continuous_effect(&mut self, object_id: Id, duration: Duration, kind: ContinuousEffectKind) -> Id {
+        let effect = ContinuousEffect {
+            id: get_id(),
+            object_id,
+            timestamp: get_timestamp(),
+            duration,
+            kind,
+        };
+        let effect_id = effect.id;
+
+        self.continuous_effects.push(effect);
+        self.layers_changed();
+
+        // An effect that lasts "for as long as" something that isn't true does
+        // nothing at all (rule 611.2b), and a new effect can change who
+        // controls something, which can end other effects.
+        self.end_expired_effects();
+
+        effect_id
+    }
+
+    /// Ends every continuous effect whose duration is up because of a zone or
+    /// control change. Ending an effect can change control in turn, so this
+    /// keeps going until nothing else ends.
+    ///
+    /// Effects that end at a particular time, like at the end of the turn, are
+    /// ended by the turn structure instead.
+    fn end_expired_effects(&mut self) {
+        loop {
+            let state = self.layers();
+
+            let expired_ids: Vec<Id> = self.continuous_effects.iter()
+                .filter(|effect| self.has_expired(&state, effect.duration))
+                .map(|effect| effect.id)
+                .chain(self.replacement_effects.iter()
+                    .filter(|effect| self.has_expired(&state, effect.duration))
+                    .map(|effect| effect.id))
+                .collect();
+
+            if expired_ids.is_empty() {
+                break;
+            }
+
+            self.continuous_effects.retain(|effect| !expired_ids.contains(&effect.id));
+            self.replacement_effects.retain(|effect| !expired_ids.contains(&effect.id));
+            self.layers_changed();
+        }
+    }
+
+    /// Whether an effect with the given duration should end because of a zone
+    /// or control change.
+    fn has_expired(&self, state: &LayerState, duration: Duration) -> bool {
+        let battlefield_id = self.battlefield_id();
+
+        let on_battlefield = |object_id: Id| {
+            self.objects.get(&object_id).is_some_and(|object| Some(object.zone) == battlefield_id)
+        };
+
+        match duration {
+            Duration::WhileOnBattlefield(object_id) => !on_battlefield(object_id),
+            // Phased-out permanents aren't in the layer state, but they keep
+            // whoever controlled them.
+            Duration::WhileControlled { controller, object_id } => {
+                !on_battlefield(object_id)
+                    || state.controllers.get(&object_id).is_some_and(|&current| current != controller)
+            },
+            Duration::UntilEndOfTurn | Duration::UntilYourNextTurn(_) | Duration::Indefinite => false,
+        }
+    }
+
+    /// Has the given player gain control of a permanent for a while, like with
+    /// Threaten or Mind Control. Returns the ID of the control-changing effect.
+    pub fn gain_control(&mut self, player_id: Id, object_id: Id, duration: Duration) -> Option<Id> {
+        let battlefield_id = self.battlefield_id()?;
+
+        // Only permanents can change control this way.
+        if self.objects.get(&object_id)?.zone != battlefield_id {
+            return None;
+        }
+
+        Some(self.add_continuous_effect(object_id, duration, ContinuousEffectKind::ChangeControl {
+            controller: player_id,
+        }))
+    }
+
+    /// Finds one of an object's abilities by ID, including abilities that
+    /// effects have given it.
+    pub fn ability(&self, object_id: Id, ability_id: Id) -> Option<Ability> {
+        self.ability_in(&self.layers(), object_id, ability_id)
+    }
+
+    /// Finds one of an object's abilities by ID using continuous effects that
+    /// have already been applied.
+    fn ability_in(&self, state: &LayerState, object_id: Id, ability_id: Id) -> Option<Ability> {
+        self.view_in(state, object_id)?
+            .characteristics?
+            .abilities
+            .remove(&ability_id)
+    }
+
+    /// Adds a new object to the game, putting it on top of its zone if the zone
+    /// is ordered. Returns the object's ID.
+    pub fn add_object(&mut self, object: Object) -> Id {
+        let object_id = object.id;
+
+        if let Some(zone) = self.zones.get_mut(&object.zone) {
+            if zone.details.is_ordered() {
+                zone.order.push(object_id);
+            }
+        }
+
+        self.objects.insert(object_id, object);
+        self.layers_changed();
+
+        object_id
+    }
+
+    /// Removes an object from the game entirely, like an ability that finished
+    /// resolving. Returns the removed object, if it existed.
+    pub fn remove_object(&mut self, object_id: Id) -> Option<Object> {
+        // Effects still apply to the snapshot, so it has to be taken first.
+        // Phased-out permanents don't have one, since they aren't there.
+        if let Some(view) = self.view_object(object_id) {
+            self.last_known_information.insert(object_id, LastKnownInformation { view });
+        }
+
+        let object = self.objects.remove(&object_id)?;
+        self.layers_changed();
+
+        if let Some(zone) = self.zones.get_mut(&object.zone) {
+            zone.order.retain(|&id| id != object_id);
+        }
+
+        self.remove_from_combat(object_id);
+        self.adventurers.remove(&object_id);
+
+        // The object is gone, so effects on it are too, along with effects
+        // that only last while it's around.
+        self.continuous_effects.retain(|effect| effect.object_id != object_id);
+        self.end_expired_effects();
+
+        Some(object)
+    }
+
+    /// Moves an object into a new zone.
+    ///
+    /// The rules say that an object changing zones becomes a new object with no
+    /// memory of the old one, so the moved object gets a new ID and timestamp.
+    /// Returns the new ID, or `None` if the object or zone doesn't exist, if
+    /// the object is a token that has already left the battlefield, or if it's
+    /// an emblem.
+    pub fn move_object(&mut self, object_id: Id, zone_id: Id) -> Option<Id> {
+        // A spell keeps the face it was cast with as it becomes a permanent
+        // (rule 712.14), but nothing else remembers its face.
+        let face = self.objects.get(&object_id)
+            .filter(|object| Some(object.zone) == self.stack_id() && Some(zone_id) == self.battlefield_id())
+            .and_then(|object| object.face);
+
+        self.move_object_face_up(object_id, zone_id, face)
+    }
+
+    /// Like `move_object`, but puts the object into its new zone with the
+    /// given face up, like when a modal double-faced card is cast or played
+    /// with its back face up.
+    fn move_object_face_up(&mut self, object_id: Id, zone_id: Id, face: Option<Face>) -> Option<Id> {
+        self.apply_mutation(&GameMutation::MoveObject {
+            object_id,
+            zone_id,
+            face,
+            tapped: false,
+        })
+    }
+
+    /// Moves an object, once replacement effects have had their say.
+    fn perform_move(&mut self, object_id: Id, zone_id: Id, face: Option<Face>, tapped: bool) -> Option<Id> {
+        if !self.zones.contains_key(&zone_id) {
+            return None;
+        }
+
+        // A token that has already left the battlefield stays where it is
+        // until state-based actions get rid of it (rule 111.8).
+        let is_stranded_token = self.objects.get(&object_id)
+            .is_some_and(|object| object.is_token() && Some(object.zone) != self.battlefield_id());
+
+        if is_stranded_token {
+            return None;
+        }
+
+        // Emblems stay in the command zone for the rest of the game.
+        if self.objects.get(&object_id).is_some_and(|object| object.is_emblem()) {
+            return None;
+        }
+
+        let object = self.remove_object(object_id)?;
+
+        // Only objects on the battlefield or the stack can be controlled by
+        // someone other than their owner.
+        let keeps_controller = self.zones.get(&zone_id)
+            .is_some_and(|zone| matches!(zone.details, ZoneDetails::Battlefield | ZoneDetails::Stack));
+
+        let new_object = Object {
+            id: get_id(),
+            zone: zone_id,
+            timestamp: get_timestamp(),
+            controller: if keeps_controller { object.controller } else { object.owner },
+            damage: 0,
+            status: PermanentStatus::default(),
+            counters: HashMap::new(),
+            face,
+            attached_to: None,
+            ..object
+        };
+
+        let new_object_id = self.add_object(new_object);
+
+        self.new_object_ids.insert(object_id, new_object_id);
+
+        if Some(zone_id) == self.battlefield_id() {
+            if let Some(object) = self.objects.get_mut(&new_object_id) {
+                object.set_tapped(tapped);
+            }
+
+            self.add_starting_counters(new_object_id);
+        }
+
+        Some(new_object_id)
+    }
+
+    /// Has the given players lose the game at the same time, removing them from
+    /// it. The game ends once at most one player is left.
+    ///
+    /// In a multiplayer game, everyone else keeps playing: the losing players'
+    /// objects leave the game, and priority moves on if it was theirs
+    /// (rule 800.4a). If it was one of their turns, the turn carries on
+    /// without an active player (rule 800.4h). Any choice the game was waiting
+    /// on them to make is abandoned.
+    pub fn players_lose(&mut self, player_ids: &[Id]) {
+        let mut next_active_id = None;
+        let mut next_priority_id = None;
+
+        let abandoned_choice = match self.current_status {
+            GameStatus::NeedsPlayerChoice(ref choice) if player_ids.contains(&choice.player_id()) => Some(choice.clone()),
+            _ => None,
+        };
+
+        // Players declare blockers in turn order, so the next one has to be
+        // worked out while the player leaving is still in it.
+        let next_blocking_id = match abandoned_choice {
+            Some(PlayerChoice::DeclareBlockers { player_id }) => {
+                let state = self.layers();
+
+                self.next_blocking_player(&state, Some(player_id))
+                    .filter(|next_id| !player_ids.contains(next_id))
+            },
+            _ => None,
+        };
+
+        for &player_id in player_ids {
+            let index = match self.player_turn_order.iter().position(|&id| id == player_id) {
+                Some(index) => index,
+                None => continue,
+            };
+
+            self.player_turn_order.remove(index);
+            self.mana_pools.remove(&player_id);
+            self.losers.push(player_id);
+
+            if self.player_turn_order.is_empty() {
+                continue;
+            }
+
+            let next_player_id = self.player_turn_order[index % self.player_turn_order.len()];
+
+            if self.active_player == Some(player_id) || self.next_active_player == Some(player_id) || next_active_id == Some(player_id) {
+                next_active_id = Some(next_player_id);
+            }
+
+            if self.priority_player == Some(player_id) || next_priority_id == Some(player_id) {
+                next_priority_id = Some(next_player_id);
+            }
+        }
+
+        self.remove_objects_of_players(player_ids);
+
+        if self.player_turn_order.len() <= 1 {
+            self.current_status = GameStatus::Ended(GameResult {
+                winners: self.player_turn_order.clone(),
+                losers: self.losers.clone(),
+            });
+
+            self.priority_player = None;
+
+            return;
+        }
+
+        if let Some(next_active_id) = next_active_id {
+            self.active_player = None;
+            self.next_active_player = Some(next_active_id);
+        }
+
+        if let Some(next_priority_id) = next_priority_id {
+            self.consecutive_passes = 0;
+            self.priority_player = Some(next_priority_id);
+        }
+
+        if let Some(choice) = abandoned_choice {
+            self.current_status = GameStatus::NeedsPlayerAction;
+
+            match choice {
+                // Replacement effects apply oldest first instead.
+                PlayerChoice::ReplacementOrder { .. } => self.resume_suspended_mutations(),
+                PlayerChoice::Discard { .. } => {
+                    self.finish_cleanup();
+                    self.advance_phase();
+                },
+                PlayerChoice::KeepLegend { .. } => self.process_state_based_actions(),
+                PlayerChoice::DeclareAttackers { .. } => self.grant_priority_to_active(),
+                // The spell left the game along with them.
+                PlayerChoice::PayMana { .. } => self.payment_activations.clear(),
+                PlayerChoice::DeclareBlockers { .. } => match next_blocking_id {
+                    Some(player_id) => {
+                        self.current_status = GameStatus::NeedsPlayerChoice(PlayerChoice::DeclareBlockers { player_id });
+                    },
+                    None => self.grant_priority_to_active(),
+                },
+            }
+        }
+    }
+
+    /// Has the given player win the game, like from an effect that says so.
+    /// Every other player loses (rule 104.2b).
+    pub fn player_wins(&mut self, player_id: Id) {
+        let other_player_ids: Vec<Id> = self.player_turn_order.iter()
+            .cloned()
+            .filter(|&id| id != player_id)
+            .collect();
+
+        self.players_lose(&other_player_ids);
+    }
+
+    /// Removes every object belonging to players who left the game: the
+    /// objects they own, and the objects they still control once effects giving
+    /// them control have ended (rule 800.4a).
+    fn remove_objects_of_players(&mut self, player_ids: &[Id]) {
+        // Players who left will never have another turn, so effects lasting
+        // until then end now too.
+        let waits_for_turn = |duration: Duration| {
+            match duration {
+                Duration::UntilYourNextTurn(player_id) => player_ids.contains(&player_id),
+                _ => false,
+            }
+        };
+
+        self.continuous_effects.retain(|effect| {
+            let gives_control = effect.kind.new_controller()
+                .is_some_and(|controller| player_ids.contains(&controller));
+
+            !gives_control && !waits_for_turn(effect.duration)
+        });
+        self.layers_changed();
+
+        self.replacement_effects.retain(|effect| {
+            !player_ids.contains(&effect.controller) && !waits_for_turn(effect.duration)
+        });
+
+        self.pending_triggers.retain(|trigger| !player_ids.contains(&trigger.controller));
+
+        // Creatures attacking a player who left have nothing left to attack.
+        self.attacks.retain(|_, attack| !player_ids.contains(&attack.defending_player));
+
+        let state = self.layers();
+
+        let leaving_object_ids: Vec<Id> = self.objects.values()
+            .filter(|object| {
+                let controller = state.controllers.get(&object.id).cloned().unwrap_or(object.controller);
+
+                player_ids.contains(&object.owner) || player_ids.contains(&controller)
+            })
+            .map(|object| object.id)
+            .collect();
+
+        for object_id in leaving_object_ids {
+            // Objects the players control but don't own are exiled instead.
+            let is_owned = self.objects.get(&object_id)
+                .is_some_and(|object| player_ids.contains(&object.owner));
+
+            match self.exile_id() {
+                Some(exile_id) if !is_owned => {
+                    self.move_object(object_id, exile_id);
+                },
+                _ => {
+                    self.remove_object(object_id);
+                },
+            }
+        }
+    }
+
+    /// Apply all state-based actions, like creatures dying due to damage.
+    ///
+    /// Every applicable action is performed at once, then the check repeats
+    /// until none apply (rule 704.3).
+    ///
+    /// If the legend rule needs a player to choose which legend to keep, the
+    /// game waits for the choice before performing any of them.
+    ///
+    /// Once none apply, any abilities that triggered are put on the stack,
+    /// since this is checked whenever a player would receive priority
+    /// (rule 117.5).
+    pub fn process_state_based_actions(&mut self) {
+        loop {
+            if self.current_status != GameStatus::NeedsPlayerAction {
+                return;
+            }
+
+            let state = self.layers();
+
+            if let Some(choice) = self.find_legend_rule_choice(&state) {
+                self.current_status = GameStatus::NeedsPlayerChoice(choice);
+                return;
+            }
+
+            let losing_player_ids = self.find_losing_players();
+            let mut dying_object_ids = self.find_dying_objects(&state);
+            let cancelling_counters = self.find_cancelling_counters();
+            let stranded_token_ids = self.find_stranded_tokens();
+
+            for player in self.players.values_mut() {
+                player.drew_from_empty_library = false;
+            }
+
+            for object_id in self.find_illegal_auras(&state) {
+                if !dying_object_ids.contains(&object_id) {
+                    dying_object_ids.push(object_id);
+                }
+            }
+
+            if losing_player_ids.is_empty() && dying_object_ids.is_empty()
+                && cancelling_counters.is_empty() && stranded_token_ids.is_empty() {
+                self.put_triggers_on_stack();
+                return;
+            }
+
+            for object_id in stranded_token_ids {
+                self.remove_object(object_id);
+            }
+
+            for (object_id, amount) in cancelling_counters {
+                self.apply_mutation(&GameMutation::RemoveCounters {
+                    object_id,
+                    kind: ObjectCounter::PlusOnePlusOne,
+                    amount,
+                });
+
+                self.apply_mutation(&GameMutation::RemoveCounters {
+                    object_id,
+                    kind: ObjectCounter::MinusOneMinusOne,
+                    amount,
+                });
+            }
+
+            // TODO: Regeneration and indestructible
+            for object_id in dying_object_ids {
+                let graveyard_id = self.objects.get(&object_id)
+                    .and_then(|object| self.graveyard_id(object.owner));
+
+                if let Some(graveyard_id) = graveyard_id {
+                    self.move_object(object_id, graveyard_id);
+                }
+            }
+
+            if !losing_player_ids.is_empty() {
+                self.players_lose(&losing_player_ids);
+            }
+        }
+    }
+
+    /// Finds the permanents that state-based actions put into their owner's
+    /// graveyard: creatures with 0 or less toughness or lethal damage, and
+    /// planeswalkers with 0 loyalty (rules 704.5f, 704.5g, and 704.5i).
+    fn find_dying_objects(&self, state: &LayerState) -> Vec<Id> {
+        let battlefield_id = match self.battlefield_id() {
+            Some(battlefield_id) => battlefield_id,
+            None => return Vec::new(),
+        };
+
+        let mut dying_object_ids: Vec<Id> = self.objects.values()
+            .filter(|object| object.zone == battlefield_id)
+            .filter_map(|object| self.view_in(state, object.id))
+            .filter(|view| {
+                let characteristics = match view.characteristics {
+                    Some(ref characteristics) => characteristics,
+                    None => return false,
+                };
+
+                let is_dead_creature = characteristics.has_card_type(CardType::Creature)
+                    && view.toughness().is_some_and(|toughness| {
+                        toughness <= 0 || (view.object.damage > 0 && view.object.damage as i32 >= toughness)
+                    });
+
+                let is_dead_planeswalker = characteristics.has_card_type(CardType::Planeswalker)
+                    && view.loyalty().is_some_and(|loyalty| loyalty <= 0);
+
+                is_dead_creature || is_dead_planeswalker
+            })
+            .map(|view| view.object.id)
+            .collect();
+
+        dying_object_ids.sort();
+        dying_object_ids
+    }
+
+    /// Finds the Auras that state-based actions put into their owner's
+    /// graveyard: ones that aren't attached to a permanent, or are attached to
+    /// one their enchant ability doesn't allow (rule 704.5m).
+    // TODO: Equipment and Fortifications attached to something they can't be
+    //       become unattached instead (rule 704.5n).
+    fn find_illegal_auras(&self, state: &LayerState) -> Vec<Id> {
+        let battlefield_id = self.battlefield_id();
+        let on_battlefield = |object_id: Id| {
+            self.objects.get(&object_id).is_some_and(|object| Some(object.zone) == battlefield_id)
+        };
+
+        let mut aura_ids: Vec<Id> = state.characteristics.iter()
+            .filter(|&(&object_id, characteristics)| {
+                if !on_battlefield(object_id) || !characteristics.has_subtype("Aura") {
+                    return false;
+                }
+
+                // Phased-out permanents aren't in the layer state, so Auras
+                // can't be attached to them either.
+                let attached_id = match self.objects[&object_id].attached_to {
+                    Some(attached_id) if attached_id != object_id && on_battlefield(attached_id) => attached_id,
+                    _ => return true,
+                };
+
+                let attached = match state.characteristics.get(&attached_id) {
+                    Some(attached) => attached,
+                    None => return true,
+                };
+
+                let same_controller = state.controllers.get(&attached_id) == state.controllers.get(&object_id);
+
+                characteristics.abilities.values().any(|ability| {
+                    match *ability {
+                        Ability::Enchant(ref filter) => !filter.matches(attached, false, same_controller),
+                        _ => false,
+                    }
+                })
+            })
+            .map(|(&object_id, _)| object_id)
+            .collect();
+
+        aura_ids.sort();
+        aura_ids
+    }
+
+    /// Finds the legendary permanents that break the legend rule, if there are
+    /// any, and asks their controller which one to keep (rule 704.5j). Players
+    /// are asked one name at a time, in turn order.
+    fn find_legend_rule_choice(&self, state: &LayerState) -> Option<PlayerChoice> {
+        let battlefield_id = self.battlefield_id()?;
+        let mut legends: BTreeMap<(usize, &str), Vec<Id>> = BTreeMap::new();
+
+        for (&object_id, characteristics) in &state.characteristics {
+            // Face-down permanents have no name, so they never share one.
+            if self.objects[&object_id].zone != battlefield_id || characteristics.name.is_empty()
+                || !characteristics.has_supertype(Supertype::Legendary) {
+                continue;
+            }
+
+            let turn_index = state.controllers.get(&object_id)
+                .and_then(|controller| self.player_turn_order.iter().position(|id| id == controller));
+
+            if let Some(turn_index) = turn_index {
+                legends.entry((turn_index, &characteristics.name)).or_default().push(object_id);
+            }
+        }
+
+        legends.into_iter()
+            .find(|(_, object_ids)| object_ids.len() > 1)
+            .map(|((turn_index, _), mut object_ids)| {
+                object_ids.sort();
+
+                PlayerChoice::KeepLegend {
+                    player_id: self.player_turn_order[turn_index],
+                    object_ids,
+                }
+            })
+    }
+
+    /// Finds the tokens that have left the battlefield, which cease to exist
+    /// (rule 704.5d).
+    fn find_stranded_tokens(&self) -> Vec<Id> {
+        let battlefield_id = self.battlefield_id();
+
+        self.objects.values()
+            .filter(|object| object.is_token() && Some(object.zone) != battlefield_id)
+            .map(|object| object.id)
+            .collect()
+    }
+
+    /// Finds the permanents with both +1/+1 and -1/-1 counters on them, and how
+    /// many of each should be removed (rule 704.5q).
+    fn find_cancelling_counters(&self) -> Vec<(Id, usize)> {
+        let battlefield_id = self.battlefield_id();
+
+        self.objects.values()
+            .filter(|object| Some(object.zone) == battlefield_id)
+            .map(|object| {
+                let amount = object.counters(&ObjectCounter::PlusOnePlusOne)
+                    .min(object.counters(&ObjectCounter::MinusOneMinusOne));
+
+                (object.id, amount)
+            })
+            .filter(|&(_, amount)| amount > 0)
+            .collect()
+    }
+
+    /// Finds the players still in the game that state-based actions make lose:
+    /// those at 0 or less life, who drew from an empty library, or with too
+    /// many poison counters (rules 704.5a-c).
+    fn find_losing_players(&self) -> Vec<Id> {
+        self.player_turn_order.iter()
+            .cloned()
+            .filter(|player_id| {
+                let player = match self.players.get(player_id) {
+                    Some(player) => player,
+                    None => return false,
+                };
+
+                player.life <= 0
+                    || player.drew_from_empty_library
+                    || player.counters(&PlayerCounter::Poison) >= LETHAL_POISON_COUNTERS
+            })
+            .collect()
+    }
+
+    /// Finds the zone that passes the given condition, if it exists.
+    pub fn find_zone_id<F>(&self, predicate: F) -> Option<Id>
+    where
+        F: Fn(&Zone) -> bool
+    {
+        self.zones.values()
+            .find(|zone| predicate(zone))
+            .map(|zone| zone.id)
+    }
+
+    /// Finds the ID of the battlefield.
+    pub fn battlefield_id(&self) -> Option<Id> {
+        self.find_zone_id(|zone| matches!(zone.details, ZoneDetails::Battlefield))
+    }
+
+    /// Finds the ID of the stack.
+    pub fn stack_id(&self) -> Option<Id> {
+        self.find_zone_id(|zone| matches!(zone.details, ZoneDetails::Stack))
+    }
+
+    /// Finds the ID of the command zone.
+    pub fn command_id(&self) -> Option<Id> {
+        self.find_zone_id(|zone| matches!(zone.details, ZoneDetails::Command))
+    }
+
+    /// Finds the ID of the exile zone.
+    pub fn exile_id(&self) -> Option<Id> {
+        self.find_zone_id(|zone| matches!(zone.details, ZoneDetails::Exile))
+    }
+
+    /// Finds the ID of the given player's hand.
+    pub fn hand_id(&self, target_player_id: Id) -> Option<Id> {
+        self.find_zone_id(|zone| {
+            match zone.details {
+                ZoneDetails::Hand { player_id } => player_id == target_player_id,
+                _ => false,
+            }
+        })
+    }
+
+    /// Finds the ID of the given player's library.
+    pub fn library_id(&self, target_player_id: Id) -> Option<Id> {
+        self.find_zone_id(|zone| {
+            match zone.details {
+                ZoneDetails::Library { player_id } => player_id == target_player_id,
+                _ => false,
+            }
+        })
+    }
+
+    /// Finds the ID of the given player's graveyard.
+    pub fn graveyard_id(&self, target_player_id: Id) -> Option<Id> {
+        self.find_zone_id(|zone| {
+            match zone.details {
+                ZoneDetails::Graveyard { player_id } => player_id == target_player_id,
+                _ => false,
+            }
+        })
+    }
+
+
+    /// Create a version of `Game` as viewed by the given player. This
+    /// should collapse hidden zones and unknown information. Hopefully, it's
+    /// also cheap, since the most naive way to implement client communication
+    /// would be to send a full state on every change!
+    pub fn view_as_player(&self, _player_id: Id) -> Game {
+        // TODO: Hide objects that player has no knowledge of, like face-down
+        //       permanents.
+
+        // TODO: Hide zones that player has no knowledge of, like libraries, and
+        //       face-down exile zones created by cards like Bomat Courier.
+
+        self.clone()
     }
 
     /// Queries an object in the game by ID.
@@ -366,9 +4024,9 @@ impl Game {
     /// The definition of Mountain says that it has type `Basic Land - Mountain`
     /// and thus has `{T}: Add {R} to your mana pool`.
     ///
-    /// Spreading Seas, when on the battlefield, has a replacement effect for
-    /// that removes its target's abilities and changes its type line to `Basic
-    /// Land - Island`.
+    /// Spreading Seas, when on the battlefield, has a continuous effect that
+    /// removes its target's abilities and changes its type line to `Basic Land
+    /// - Island`.
     ///
     /// The mountain's current state (post-effect) thus says that it has type
     /// `Basic Land - Island` and taps for blue mana only!
@@ -378,12 +4036,494 @@ impl Game {
     /// keep a list of active effects and calculate them on each observation.
     ///
     /// While the former technique is possible, I think that calculating effects
-    /// on each individual observation event is simpler.
-    pub fn view_object(&self, object_id: Id) -> Option<Object> {
+    /// on observation is simpler. They're only calculated again once the game
+    /// state has changed, though, so looking at many objects is cheap.
+    ///
+    /// Effects are applied in layer order (rule 613.1), and in timestamp order
+    /// within each layer (rule 613.7).
+    pub fn view_object(&self, object_id: Id) -> Option<ObjectView> {
+        self.view_in(&self.layers(), object_id)
+    }
+
+    /// Every object's characteristics and controller with continuous effects
+    /// applied, calculated once per change to the game state.
+    fn layers(&self) -> Arc<LayerState> {
+        if let Some(ref state) = *self.layer_cache.borrow() {
+            return state.clone();
+        }
+
+        let state = Arc::new(self.apply_continuous_effects());
+        *self.layer_cache.borrow_mut() = Some(state.clone());
+
+        state
+    }
+
+    /// Forgets the continuous effects applied by `layers`, since something
+    /// they depend on has changed.
+    fn layers_changed(&mut self) {
+        *self.layer_cache.get_mut() = None;
+    }
+
+    /// Views an object using continuous effects that have already been
+    /// applied, so that looking at many objects only applies them once.
+    fn view_in(&self, state: &LayerState, object_id: Id) -> Option<ObjectView> {
         let base_object = self.objects.get(&object_id)?;
 
-        // TODO: Enumerate the set of effects that could affect this object.
+        if base_object.status.phased_out {
+            return None;
+        }
+
+        // Cards in libraries are left out of the layer state, since nothing
+        // changes them there.
+        let characteristics = match state.characteristics.get(&object_id) {
+            Some(characteristics) => Some(characteristics.clone()),
+            None => self.characteristics(object_id).map(|characteristics| {
+                let mut characteristics = characteristics.clone();
+                fix_stats(&mut characteristics);
+                characteristics
+            }),
+        };
+
+        let object = Object {
+            controller: state.controllers.get(&object_id).cloned().unwrap_or(base_object.controller),
+            ..base_object.clone()
+        };
+
+        Some(ObjectView {
+            object,
+            characteristics,
+        })
+    }
+
+    /// Forgets the last known information that nothing can need anymore.
+    ///
+    /// Abilities on the stack look back at their sources, so nothing is
+    /// forgotten until the stack is empty. After that, only objects that
+    /// effects still refer to, like the source chosen for a prevention effect,
+    /// are remembered.
+    fn prune_last_known_information(&mut self) {
+        if !self.stack_is_empty() || !self.suspended_mutations.is_empty() {
+            return;
+        }
+
+        let mut referenced_ids: Vec<Id> = self.continuous_effects.iter()
+            .map(|effect| effect.object_id)
+            .collect();
+
+        for effect in &self.replacement_effects {
+            match effect.kind {
+                ReplacementEffectKind::PreventDamage { ref damage, .. }
+                | ReplacementEffectKind::PreventionShield { ref damage, .. }
+                | ReplacementEffectKind::DamageCantBePrevented { ref damage } => {
+                    referenced_ids.extend(damage.source_id);
+                },
+                _ => {},
+            }
+        }
+
+        self.last_known_information.retain(|object_id, _| referenced_ids.contains(object_id));
+    }
+
+    /// Applies every continuous effect in the game to every object at once,
+    /// since effects like Blood Moon's can change which objects other effects
+    /// apply to.
+    ///
+    /// Effects are applied in layer order (rule 613.1). Within a layer, an
+    /// effect that depends on another is applied after it, and the rest are
+    /// applied in timestamp order (rules 613.7 and 613.8).
+    ///
+    /// Cards in libraries are left out, since there are usually a lot of them
+    /// and effects don't change them. Phased-out permanents are left out too,
+    /// since they're treated as though they don't exist (rule 702.26b).
+    fn apply_continuous_effects(&self) -> LayerState {
+        let in_library = |object: &Object| {
+            self.zones.get(&object.zone)
+                .is_some_and(|zone| matches!(zone.details, ZoneDetails::Library { .. }))
+        };
+
+        let objects: Vec<&Object> = self.objects.values()
+            .filter(|object| !in_library(object) && !object.status.phased_out)
+            .collect();
+
+        let mut state = LayerState {
+            characteristics: objects.iter()
+                .filter_map(|object| self.base_characteristics(object.id).map(|characteristics| (object.id, characteristics)))
+                .collect(),
+            controllers: objects.iter()
+                .map(|object| (object.id, object.controller))
+                .collect(),
+        };
+
+        let mut started = HashMap::new();
+
+        for &layer in Layer::all().iter() {
+            let mut pending = self.effects_in_layer(layer, &state, &started);
+
+            loop {
+                // An effect from a static ability stops existing if an earlier
+                // effect removed the ability.
+                pending.retain(|effect| self.effect_exists(effect, &state, &started));
+
+                if pending.is_empty() {
+                    break;
+                }
+
+                let effect = pending.remove(self.next_effect(&pending, &state, &started));
+                let object_ids = self.affected_objects(&effect, &state, &started);
+
+                if let AffectedObjects::Static { ref ability, .. } = effect.affected {
+                    started.entry(effect.key).or_insert_with(|| StartedEffect {
+                        object_ids: object_ids.clone(),
+                        timestamp: effect.timestamp,
+                        ability: ability.clone(),
+                    });
+                }
+
+                if let ContinuousEffectKind::CountPowerToughness { ref counted } = effect.kind {
+                    let source_id = match effect.affected {
+                        AffectedObjects::Object(object_id) => object_id,
+                        AffectedObjects::Static { source_id, .. } => source_id,
+                    };
+
+                    let count = self.matching_permanents(&state, counted, source_id).len() as i32;
+                    define_power_toughness(&mut state, &object_ids, count);
+                }
+
+                state.apply(&effect.kind, &object_ids);
+            }
+
+            if layer == Layer::CharacteristicDefining {
+                // Whatever `*` no characteristic-defining ability defined
+                // counts as 0 from here on.
+                for characteristics in state.characteristics.values_mut() {
+                    fix_stats(characteristics);
+                }
+            }
+
+            if layer == Layer::ModifyPowerToughness {
+                // Counters modify power and toughness in layer 7c too (rule
+                // 613.4c). Everything in 7c adds up, so the order doesn't
+                // matter.
+                for (object_id, characteristics) in &mut state.characteristics {
+                    let object = &self.objects[object_id];
+                    let modifier = object.counters(&ObjectCounter::PlusOnePlusOne) as i32
+                        - object.counters(&ObjectCounter::MinusOneMinusOne) as i32;
+
+                    ContinuousEffectKind::ModifyPowerToughness {
+                        power: modifier,
+                        toughness: modifier,
+                    }.apply(characteristics);
+                }
+            }
+        }
+
+        // A planeswalker's loyalty on the battlefield is the number of loyalty
+        // counters on it (rule 306.5b).
+        for (object_id, characteristics) in &mut state.characteristics {
+            let object = &self.objects[object_id];
+
+            if Some(object.zone) == self.battlefield_id() && characteristics.has_card_type(CardType::Planeswalker) {
+                characteristics.loyalty = Some(StatValue::Fixed(object.counters(&ObjectCounter::Loyalty) as i32));
+            }
+        }
+
+        state
+    }
+
+    /// Every part of a continuous effect that applies in the given layer,
+    /// including parts of static abilities that have already started applying
+    /// in an earlier layer, in timestamp order.
+    fn effects_in_layer(&self, layer: Layer, state: &LayerState, started: &HashMap<EffectKey, StartedEffect>) -> Vec<PendingEffect> {
+        let mut abilities: Vec<(EffectKey, Timestamp, StaticAbility)> = started.iter()
+            .map(|(&key, effect)| (key, effect.timestamp, effect.ability.clone()))
+            .collect();
+
+        for (&source_id, characteristics) in &state.characteristics {
+            // Characteristic-defining abilities work from every zone
+            // (rule 604.3).
+            if !self.abilities_function(source_id) && layer != Layer::CharacteristicDefining {
+                continue;
+            }
+
+            for (&ability_id, ability) in &characteristics.abilities {
+                let key = EffectKey::Static { source_id, ability_id };
+
+                if started.contains_key(&key) {
+                    continue;
+                }
+
+                if let Ability::Static(ref ability) = *ability {
+                    abilities.push((key, self.objects[&source_id].timestamp, ability.clone()));
+                }
+            }
+        }
+
+        // Several abilities on one object share a timestamp, so they're sorted
+        // by ID too, to keep their order from changing between calls.
+        abilities.sort_by_key(|&(key, timestamp, _)| (timestamp, key));
+
+        let mut effects: Vec<PendingEffect> = self.continuous_effects.iter()
+            .filter(|effect| effect.kind.layer() == layer)
+            .map(|effect| PendingEffect {
+                key: EffectKey::Created(effect.id),
+                timestamp: effect.timestamp,
+                kind: effect.kind.clone(),
+                affected: AffectedObjects::Object(effect.object_id),
+            })
+            .collect();
+
+        for (key, timestamp, ability) in abilities {
+            let source_id = match key {
+                EffectKey::Static { source_id, .. } => source_id,
+                EffectKey::Created(_) => continue,
+            };
+
+            for kind in &ability.effects {
+                if kind.layer() == layer {
+                    effects.push(PendingEffect {
+                        key,
+                        timestamp,
+                        kind: kind.clone(),
+                        affected: AffectedObjects::Static {
+                            source_id,
+                            ability: ability.clone(),
+                        },
+                    });
+                }
+            }
+        }
+
+        effects.sort_by_key(|effect| effect.timestamp);
+        effects
+    }
+
+    /// Whether an effect still exists. Effects from static abilities only
+    /// exist while their source has the ability, unless they've already
+    /// started applying (rule 613.6).
+    fn effect_exists(&self, effect: &PendingEffect, state: &LayerState, started: &HashMap<EffectKey, StartedEffect>) -> bool {
+        match effect.key {
+            EffectKey::Created(_) => true,
+            EffectKey::Static { source_id, ability_id } => {
+                started.contains_key(&effect.key)
+                    || state.characteristics.get(&source_id)
+                        .and_then(|characteristics| characteristics.abilities.get(&ability_id))
+                        .is_some_and(|ability| matches!(*ability, Ability::Static(_)))
+            },
+        }
+    }
+
+    /// The objects an effect applies to, partway through applying effects.
+    /// Once an effect has started applying, it keeps applying to the same
+    /// objects in later layers (rule 613.6).
+    fn affected_objects(&self, effect: &PendingEffect, state: &LayerState, started: &HashMap<EffectKey, StartedEffect>) -> Vec<Id> {
+        if let Some(started_effect) = started.get(&effect.key) {
+            return started_effect.object_ids.clone();
+        }
+
+        let (source_id, filter) = match effect.affected {
+            AffectedObjects::Object(object_id) => return vec![object_id],
+            AffectedObjects::Static { source_id, ref ability } => (source_id, &ability.affected),
+        };
+
+        // Characteristic-defining abilities only define their own object's
+        // characteristics.
+        if effect.kind.layer() == Layer::CharacteristicDefining {
+            return vec![source_id];
+        }
+
+        self.matching_permanents(state, filter, source_id)
+    }
+
+    /// The permanents on the battlefield matching the filter, as seen partway
+    /// through applying effects, sorted by ID. `you_control` and `other` are
+    /// relative to the given source.
+    fn matching_permanents(&self, state: &LayerState, filter: &ObjectFilter, source_id: Id) -> Vec<Id> {
+        let battlefield_id = self.battlefield_id();
+
+        let mut object_ids: Vec<Id> = state.characteristics.iter()
+            .filter(|&(&object_id, characteristics)| {
+                let same_controller = state.controllers.get(&object_id) == state.controllers.get(&source_id);
+
+                Some(self.objects[&object_id].zone) == battlefield_id
+                    && filter.matches(characteristics, object_id == source_id, same_controller)
+            })
+            .map(|(&object_id, _)| object_id)
+            .collect();
+
+        object_ids.sort();
+        object_ids
+    }
+
+    /// Whether one effect depends on another: whether applying the other
+    /// would change whether it exists or what it applies to (rule 613.8a).
+    // TODO: Effects can also depend on others by what they do to the objects
+    //       they apply to, like "gets +1/+1 for each Forest you control", once
+    //       there are effects like that.
+    // TODO: Characteristic-defining abilities only depend on each other.
+    fn depends_on(&self, effect: &PendingEffect, other: &PendingEffect, state: &LayerState, started: &HashMap<EffectKey, StartedEffect>) -> bool {
+        if effect.key == other.key || started.contains_key(&effect.key) {
+            return false;
+        }
+
+        // Effects created for a single object always exist and apply to that
+        // object, so only static abilities' effects can depend on others.
+        let (source_id, filter) = match effect.affected {
+            AffectedObjects::Static { source_id, ref ability } => (source_id, &ability.affected),
+            AffectedObjects::Object(_) => return false,
+        };
+
+        let ability_id = match effect.key {
+            EffectKey::Static { ability_id, .. } => ability_id,
+            EffectKey::Created(_) => return false,
+        };
+
+        // Only the objects the other effect changes can come out differently,
+        // so they're the only ones copied.
+        let changed_ids = self.affected_objects(other, state, started);
+        let mut changed = LayerState {
+            characteristics: changed_ids.iter()
+                .filter_map(|id| state.characteristics.get(id).map(|characteristics| (*id, characteristics.clone())))
+                .collect(),
+            controllers: changed_ids.iter()
+                .filter_map(|id| state.controllers.get(id).map(|&controller| (*id, controller)))
+                .collect(),
+        };
+        changed.apply(&other.kind, &changed_ids);
+
+        let lookup = |after: bool, object_id: Id| {
+            let characteristics = changed.characteristics.get(&object_id).filter(|_| after)
+                .or_else(|| state.characteristics.get(&object_id));
+            let controller = changed.controllers.get(&object_id).filter(|_| after)
+                .or_else(|| state.controllers.get(&object_id));
+
+            (characteristics, controller)
+        };
+
+        let has_ability = |after: bool| {
+            lookup(after, source_id).0
+                .and_then(|characteristics| characteristics.abilities.get(&ability_id))
+                .is_some_and(|ability| matches!(*ability, Ability::Static(_)))
+        };
+
+        if has_ability(false) != has_ability(true) {
+            return true;
+        }
+
+        let battlefield_id = self.battlefield_id();
 
-        Some(base_object.clone())
+        let is_affected = |after: bool, object_id: Id| {
+            let (characteristics, controller) = lookup(after, object_id);
+            let same_controller = controller == lookup(after, source_id).1;
+
+            Some(self.objects[&object_id].zone) == battlefield_id
+                && characteristics.is_some_and(|characteristics| {
+                    filter.matches(characteristics, object_id == source_id, same_controller)
+                })
+        };
+
+        // If the source changes controller, "you control" can change for
+        // every object, not just the ones the other effect changes.
+        let checked_ids: Vec<Id> = if lookup(false, source_id).1 != lookup(true, source_id).1 {
+            state.characteristics.keys().cloned().collect()
+        } else {
+            changed_ids
+        };
+
+        checked_ids.into_iter().any(|object_id| is_affected(false, object_id) != is_affected(true, object_id))
+    }
+
+    /// Picks which of the effects waiting in a layer to apply next: the
+    /// earliest one that doesn't depend on any of the others. Effects that
+    /// depend on each other in a loop are applied in timestamp order instead
+    /// (rule 613.8b).
+    fn next_effect(&self, pending: &[PendingEffect], state: &LayerState, started: &HashMap<EffectKey, StartedEffect>) -> usize {
+        let count = pending.len();
+
+        let mut depends = vec![vec![false; count]; count];
+
+        for (i, effect) in pending.iter().enumerate() {
+            for (j, other) in pending.iter().enumerate() {
+                depends[i][j] = i != j && self.depends_on(effect, other, state, started);
+            }
+        }
+
+        // Works out which effects depend on which others through a chain of
+        // dependencies, to find loops.
+        let mut reaches = depends.clone();
+
+        for k in 0..count {
+            for i in 0..count {
+                for j in 0..count {
+                    reaches[i][j] = reaches[i][j] || (reaches[i][k] && reaches[k][j]);
+                }
+            }
+        }
+
+        // There's always at least one effect here, since the dependencies
+        // between loops can't form a loop of their own.
+        (0..count)
+            .find(|&i| (0..count).all(|j| !depends[i][j] || reaches[j][i]))
+            .unwrap_or(0)
+    }
+
+    /// The given object's copiable values: the characteristics that an object
+    /// copying it would get. That's its own characteristics, changed only by
+    /// copy effects (rule 707.2).
+    pub fn copiable_values(&self, object_id: Id) -> Option<Characteristics> {
+        let mut characteristics = self.base_characteristics(object_id)?;
+
+        // Effects are kept in timestamp order, which is the order copy effects
+        // apply in.
+        for effect in &self.continuous_effects {
+            if effect.object_id == object_id && effect.kind.layer() == Layer::Copy {
+                effect.kind.apply(&mut characteristics);
+            }
+        }
+
+        Some(characteristics)
+    }
+
+    /// Has one object become a copy of another for a while, like a Clone
+    /// entering the battlefield. The copiable values are locked in as the
+    /// effect begins. Returns the ID of the copy effect.
+    pub fn become_copy(&mut self, object_id: Id, copied_id: Id, duration: Duration) -> Option<Id> {
+        if !self.objects.contains_key(&object_id) {
+            return None;
+        }
+
+        let characteristics = self.copiable_values(copied_id)?;
+
+        Some(self.add_continuous_effect(object_id, duration, ContinuousEffectKind::BecomeCopy {
+            characteristics: Box::new(characteristics),
+        }))
+    }
+
+    /// What the given object looked like just before it left its zone, if it
+    /// has left one.
+    pub fn last_known_information(&self, object_id: Id) -> Option<&LastKnownInformation> {
+        self.last_known_information.get(&object_id)
+    }
+
+    /// Views the given object if it still exists, or its last known information
+    /// if it doesn't. Useful for abilities that care about their source after
+    /// it's gone, like "When this creature dies, it deals damage equal to its
+    /// power to any target."
+    pub fn view_object_or_last_known(&self, object_id: Id) -> Option<ObjectView> {
+        self.view_object(object_id)
+            .or_else(|| self.last_known_information(object_id).map(|info| info.view.clone()))
+    }
+
+    /// Follows an object through every zone change it has made, returning the
+    /// ID of the object it is now. Returns `None` if it has left the game.
+    pub fn current_object_id(&self, object_id: Id) -> Option<Id> {
+        let mut current_id = object_id;
+
+        loop {
+            if self.objects.contains_key(&current_id) {
+                return Some(current_id);
+            }
+
+            current_id = *self.new_object_ids.get(&current_id)?;
+        }
     }
 }
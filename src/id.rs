@@ -1,3 +1,6 @@
+// lazy_static 0.2 expands to the deprecated `ONCE_INIT`.
+#![allow(deprecated)]
+
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// A unique identifier, not guaranteed to be generated in any order.
@@ -1,17 +1,39 @@
 #[macro_use]
 extern crate lazy_static;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate toml;
 
 mod object;
+mod card_definition;
+mod card_loader;
+mod characteristics;
+mod continuous_effect;
+mod effect;
 mod id;
+mod mana;
+mod mana_cost;
+mod payment;
 mod game;
 mod player;
+mod replacement_effect;
 mod timestamp;
 mod zone;
 
 pub use object::*;
+pub use card_definition::*;
+pub use card_loader::*;
+pub use characteristics::*;
+pub use continuous_effect::*;
+pub use effect::*;
 pub use id::*;
+pub use mana::*;
+pub use mana_cost::*;
+pub use payment::*;
 pub use game::*;
 pub use player::*;
+pub use replacement_effect::*;
 pub use timestamp::*;
 pub use zone::*;
 
@@ -0,0 +1,185 @@
+/// The five colors of *Magic*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    White,
+    Blue,
+    Black,
+    Red,
+    Green,
+}
+
+impl Color {
+    /// Every color, in WUBRG order.
+    pub fn all() -> [Color; 5] {
+        [Color::White, Color::Blue, Color::Black, Color::Red, Color::Green]
+    }
+
+    /// The type of mana of this color.
+    pub fn mana_type(&self) -> ManaType {
+        match *self {
+            Color::White => ManaType::White,
+            Color::Blue => ManaType::Blue,
+            Color::Black => ManaType::Black,
+            Color::Red => ManaType::Red,
+            Color::Green => ManaType::Green,
+        }
+    }
+
+    /// The single-letter abbreviation used for this color in mana symbols.
+    pub fn symbol(&self) -> char {
+        match *self {
+            Color::White => 'W',
+            Color::Blue => 'U',
+            Color::Black => 'B',
+            Color::Red => 'R',
+            Color::Green => 'G',
+        }
+    }
+
+    /// Finds the color with the given mana symbol abbreviation, like `U`.
+    pub fn from_symbol(symbol: &str) -> Option<Color> {
+        match symbol {
+            "W" => Some(Color::White),
+            "U" => Some(Color::Blue),
+            "B" => Some(Color::Black),
+            "R" => Some(Color::Red),
+            "G" => Some(Color::Green),
+            _ => None,
+        }
+    }
+}
+
+/// The kinds of mana a player can have: one of the five colors, or colorless.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ManaType {
+    White,
+    Blue,
+    Black,
+    Red,
+    Green,
+    Colorless,
+}
+
+impl ManaType {
+    /// Every type of mana, in WUBRG order followed by colorless.
+    pub fn all() -> [ManaType; 6] {
+        [
+            ManaType::White,
+            ManaType::Blue,
+            ManaType::Black,
+            ManaType::Red,
+            ManaType::Green,
+            ManaType::Colorless,
+        ]
+    }
+
+    /// Finds the type of mana with the given mana symbol abbreviation, like
+    /// `G` or `C`.
+    pub fn from_symbol(symbol: &str) -> Option<ManaType> {
+        match symbol {
+            "C" => Some(ManaType::Colorless),
+            _ => Color::from_symbol(symbol).map(|color| color.mana_type()),
+        }
+    }
+
+    /// The type of mana a land with the given basic land type can tap for,
+    /// like `G` for a Forest (rule 305.6).
+    pub fn from_basic_land_type(subtype: &str) -> Option<ManaType> {
+        match subtype {
+            "Plains" => Some(ManaType::White),
+            "Island" => Some(ManaType::Blue),
+            "Swamp" => Some(ManaType::Black),
+            "Mountain" => Some(ManaType::Red),
+            "Forest" => Some(ManaType::Green),
+            _ => None,
+        }
+    }
+}
+
+/// Defines all of the issues we can run into when adding or removing mana.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManaError {
+    /// The player doesn't have a mana pool, usually because they aren't in the
+    /// game.
+    PoolNotFound,
+
+    /// The pool doesn't have enough mana of the requested type.
+    NotEnoughMana,
+}
+
+/// A player's mana pool, tracking how much of each type of mana they have.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ManaPool {
+    pub white: usize,
+    pub blue: usize,
+    pub black: usize,
+    pub red: usize,
+    pub green: usize,
+    pub colorless: usize,
+}
+
+impl ManaPool {
+    /// Creates an empty mana pool.
+    pub fn new() -> ManaPool {
+        ManaPool::default()
+    }
+
+    /// The amount of the given type of mana in the pool.
+    pub fn get(&self, mana_type: ManaType) -> usize {
+        match mana_type {
+            ManaType::White => self.white,
+            ManaType::Blue => self.blue,
+            ManaType::Black => self.black,
+            ManaType::Red => self.red,
+            ManaType::Green => self.green,
+            ManaType::Colorless => self.colorless,
+        }
+    }
+
+    fn get_mut(&mut self, mana_type: ManaType) -> &mut usize {
+        match mana_type {
+            ManaType::White => &mut self.white,
+            ManaType::Blue => &mut self.blue,
+            ManaType::Black => &mut self.black,
+            ManaType::Red => &mut self.red,
+            ManaType::Green => &mut self.green,
+            ManaType::Colorless => &mut self.colorless,
+        }
+    }
+
+    /// Adds mana of the given type to the pool.
+    pub fn add(&mut self, mana_type: ManaType, amount: usize) {
+        *self.get_mut(mana_type) += amount;
+    }
+
+    /// Removes mana of the given type from the pool, leaving the pool untouched
+    /// if there isn't enough.
+    pub fn remove(&mut self, mana_type: ManaType, amount: usize) -> Result<(), ManaError> {
+        let current = self.get_mut(mana_type);
+
+        if *current < amount {
+            return Err(ManaError::NotEnoughMana);
+        }
+
+        *current -= amount;
+
+        Ok(())
+    }
+
+    /// The total amount of mana in the pool, of any type.
+    pub fn total(&self) -> usize {
+        ManaType::all()
+            .iter()
+            .map(|&mana_type| self.get(mana_type))
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    /// Removes all mana from the pool, like at the end of each step.
+    pub fn empty(&mut self) {
+        *self = ManaPool::new();
+    }
+}
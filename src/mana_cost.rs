@@ -0,0 +1,201 @@
+use std::fmt;
+use std::str::FromStr;
+
+use mana::Color;
+
+/// A single mana symbol in a mana cost, like `{2}` or `{G/P}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManaSymbol {
+    /// `{1}`, `{2}`, etc. Payable with any type of mana.
+    Generic(usize),
+
+    /// `{W}`, `{U}`, etc. Payable only with mana of that color.
+    Colored(Color),
+
+    /// `{C}`, payable only with colorless mana.
+    Colorless,
+
+    /// `{X}`, whose value is chosen as the spell is cast.
+    Variable,
+
+    /// `{W/U}`, payable with mana of either color.
+    Hybrid(Color, Color),
+
+    /// `{2/B}`, payable with one mana of the color or two generic mana.
+    MonoHybrid(Color),
+
+    /// `{G/P}`, payable with one mana of the color or 2 life.
+    Phyrexian(Color),
+}
+
+impl ManaSymbol {
+    /// The amount this symbol adds to the mana value of its cost (rule 202.3).
+    pub fn mana_value(&self) -> usize {
+        match *self {
+            ManaSymbol::Generic(amount) => amount,
+            ManaSymbol::Variable => 0,
+            ManaSymbol::MonoHybrid(_) => 2,
+            _ => 1,
+        }
+    }
+
+    /// The colors of this symbol, which give a card with it in its mana cost
+    /// those colors (rule 202.2).
+    pub fn colors(&self) -> Vec<Color> {
+        match *self {
+            ManaSymbol::Colored(color)
+                | ManaSymbol::MonoHybrid(color)
+                | ManaSymbol::Phyrexian(color) => vec![color],
+            ManaSymbol::Hybrid(first, second) => vec![first, second],
+            _ => Vec::new(),
+        }
+    }
+
+    fn parse(symbol: &str) -> Result<ManaSymbol, ManaCostParseError> {
+        // `usize` parsing also accepts a leading `+`, which isn't a mana symbol.
+        let is_number = !symbol.is_empty() && symbol.bytes().all(|byte| byte.is_ascii_digit());
+
+        if is_number {
+            if let Ok(amount) = symbol.parse::<usize>() {
+                return Ok(ManaSymbol::Generic(amount));
+            }
+        }
+
+        match symbol {
+            "C" => return Ok(ManaSymbol::Colorless),
+            "X" => return Ok(ManaSymbol::Variable),
+            _ => {},
+        }
+
+        if let Some(color) = Color::from_symbol(symbol) {
+            return Ok(ManaSymbol::Colored(color));
+        }
+
+        let unknown = || ManaCostParseError::UnknownSymbol(symbol.to_string());
+
+        let mut halves = symbol.split('/');
+        let (first, second) = match (halves.next(), halves.next(), halves.next()) {
+            (Some(first), Some(second), None) => (first, second),
+            _ => return Err(unknown()),
+        };
+
+        match (first, Color::from_symbol(first), second, Color::from_symbol(second)) {
+            (_, Some(first_color), _, Some(second_color)) if first_color != second_color => {
+                Ok(ManaSymbol::Hybrid(first_color, second_color))
+            },
+            (_, Some(color), "P", _) => Ok(ManaSymbol::Phyrexian(color)),
+            ("2", _, _, Some(color)) => Ok(ManaSymbol::MonoHybrid(color)),
+            _ => Err(unknown()),
+        }
+    }
+}
+
+impl fmt::Display for ManaSymbol {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ManaSymbol::Generic(amount) => write!(formatter, "{{{}}}", amount),
+            ManaSymbol::Colored(color) => write!(formatter, "{{{}}}", color.symbol()),
+            ManaSymbol::Colorless => write!(formatter, "{{C}}"),
+            ManaSymbol::Variable => write!(formatter, "{{X}}"),
+            ManaSymbol::Hybrid(first, second) => write!(formatter, "{{{}/{}}}", first.symbol(), second.symbol()),
+            ManaSymbol::MonoHybrid(color) => write!(formatter, "{{2/{}}}", color.symbol()),
+            ManaSymbol::Phyrexian(color) => write!(formatter, "{{{}/P}}", color.symbol()),
+        }
+    }
+}
+
+/// Defines all of the issues we can run into when parsing a mana cost.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManaCostParseError {
+    /// The cost had text outside of a `{...}` symbol, or a brace that was
+    /// never closed.
+    Malformed,
+
+    /// A symbol inside braces wasn't one we know about, like `{Q}`.
+    UnknownSymbol(String),
+}
+
+impl fmt::Display for ManaCostParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ManaCostParseError::Malformed => write!(formatter, "malformed mana cost"),
+            ManaCostParseError::UnknownSymbol(ref symbol) => write!(formatter, "unknown mana symbol {{{}}}", symbol),
+        }
+    }
+}
+
+/// A mana cost, written in Oracle text as a series of mana symbols like
+/// `{2}{G}{G}`.
+///
+/// An object with no mana symbols at all, like a land, has no mana cost. That
+/// is distinct from a cost of `{0}`, so objects should use `Option<ManaCost>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManaCost {
+    pub symbols: Vec<ManaSymbol>,
+}
+
+impl ManaCost {
+    /// Parses a mana cost written with Oracle mana symbols, like `{X}{R}`.
+    pub fn parse(source: &str) -> Result<ManaCost, ManaCostParseError> {
+        let mut symbols = Vec::new();
+        let mut rest = source.trim();
+
+        while !rest.is_empty() {
+            if !rest.starts_with('{') {
+                return Err(ManaCostParseError::Malformed);
+            }
+
+            let end = rest.find('}').ok_or(ManaCostParseError::Malformed)?;
+            let symbol = &rest[1..end];
+
+            if symbol.contains('{') {
+                return Err(ManaCostParseError::Malformed);
+            }
+
+            symbols.push(ManaSymbol::parse(symbol)?);
+            rest = &rest[end + 1..];
+        }
+
+        Ok(ManaCost {
+            symbols,
+        })
+    }
+
+    /// The total amount of mana this cost represents (rule 202.3). `{X}`
+    /// counts as zero.
+    pub fn mana_value(&self) -> usize {
+        self.symbols.iter()
+            .map(|symbol| symbol.mana_value())
+            .sum()
+    }
+
+    /// Whether this cost contains `{X}`, meaning its caster has to choose a
+    /// value for X.
+    pub fn has_variable(&self) -> bool {
+        self.symbols.contains(&ManaSymbol::Variable)
+    }
+
+    /// Whether any symbol in this cost is of the given color.
+    pub fn has_color(&self, color: Color) -> bool {
+        self.symbols.iter()
+            .any(|symbol| symbol.colors().contains(&color))
+    }
+}
+
+impl FromStr for ManaCost {
+    type Err = ManaCostParseError;
+
+    fn from_str(source: &str) -> Result<ManaCost, ManaCostParseError> {
+        ManaCost::parse(source)
+    }
+}
+
+impl fmt::Display for ManaCost {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for symbol in &self.symbols {
+            write!(formatter, "{}", symbol)?;
+        }
+
+        Ok(())
+    }
+}
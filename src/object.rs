@@ -1,26 +1,343 @@
 use std::collections::HashMap;
 
+use card_definition::Face;
+use characteristics::{CardType, Characteristics, Supertype};
+use continuous_effect::ContinuousEffectKind;
+use effect::Effect;
+use game::GamePhase;
 use id::Id;
+use mana::ManaType;
+use mana_cost::{ManaCost, ManaCostParseError};
+use replacement_effect::ReplacementEffectKind;
 use timestamp::Timestamp;
 
-/// Temporary structure to encode object details.
-///
-/// I'm still thinking about how objects should be structured, but I want to
-/// tinker with other pieces of the codebase.
+/// What kind of object an object is, and the data specific to that kind.
 #[derive(Debug, Clone)]
 pub enum ObjectDetails {
-    Forest {
-        // This isn't relevant outside the battlefield
-        tapped: bool,
+    /// A card, or the spell or permanent that represents it. Its
+    /// characteristics come from its definition in the game's `CardRegistry`.
+    // TODO: Copies of spells
+    Card {
+        definition_id: Id,
+    },
+
+    /// A token, which is a permanent but never a card (rule 111.1). Tokens
+    /// have characteristics from a definition in the `CardRegistry` too, which
+    /// is the copied object's definition for tokens that are copies.
+    ///
+    /// Tokens stop existing once they leave the battlefield (rule 111.7).
+    Token {
+        definition_id: Id,
+    },
+
+    /// An emblem in the command zone, usually created by a planeswalker's
+    /// ability. Emblems have no characteristics besides their abilities, which
+    /// come from a definition in the `CardRegistry` (rule 114.3). They're
+    /// neither cards nor permanents, and stay for the rest of the game.
+    Emblem {
+        definition_id: Id,
+    },
+
+    /// An activated or triggered ability on the stack. Abilities aren't cards,
+    /// so they cease to exist once they leave the stack.
+    Ability {
+        /// The object the ability came from, which may no longer exist.
+        source_id: Id,
+        effects: Vec<Effect>,
     },
 }
 
-/// A super simplified view of abilities; this will need to be separated into
-/// mana abilities and not, represent priority, and actually represent
-/// descriptors of what these abilities do.
-#[derive(Debug, Clone)]
+/// The kinds of counters that can be put on an object (rule 122).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ObjectCounter {
+    /// Gives the object +1/+1. Cancels out with -1/-1 counters (rule 704.5q).
+    PlusOnePlusOne,
+    MinusOneMinusOne,
+
+    /// A planeswalker's loyalty is the number of these it has (rule 306.5b).
+    Loyalty,
+    Lore,
+    Charge,
+    Time,
+
+    /// A stunned permanent stays tapped the next time it would untap, losing
+    /// a stun counter instead (rule 122.1d).
+    Stun,
+
+    /// Any other kind of counter, named by the card that creates it.
+    Named(String),
+}
+
+/// Everything that has to be paid to activate an ability, written before the
+/// colon in its rules text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActivationCost {
+    /// The mana part of the cost, like the `{2}` in `{2}, {T}: Draw a card.`
+    pub mana: Option<ManaCost>,
+
+    /// Whether the cost includes `{T}`, tapping the object.
+    pub tap: bool,
+
+    /// The number of loyalty counters put on (if positive) or removed from (if
+    /// negative) a planeswalker to activate one of its loyalty abilities,
+    /// like the `[-3]` in `[-3]: You get an emblem with ...`.
+    pub loyalty: Option<i32>,
+
+    /// Whether the cost includes sacrificing the object itself, like a
+    /// Treasure's `{T}, Sacrifice this artifact`.
+    pub sacrifice: bool,
+
+    // TODO: Other costs, like sacrificing other objects, discarding, or paying
+    //       life
+}
+
+impl ActivationCost {
+    /// Parses an activation cost written like it is in rules text, like
+    /// `{2}, {T}` or `{T}, Sacrifice this artifact`. Loyalty costs are written
+    /// like `[+1]` or `[-3]`.
+    pub fn parse(source: &str) -> Result<ActivationCost, ManaCostParseError> {
+        let mut cost = ActivationCost::default();
+
+        for part in source.split(',').map(|part| part.trim()) {
+            if part == "{T}" {
+                if cost.tap {
+                    return Err(ManaCostParseError::Malformed);
+                }
+
+                cost.tap = true;
+            } else if part.starts_with('[') && part.ends_with(']') {
+                if cost.loyalty.is_some() {
+                    return Err(ManaCostParseError::Malformed);
+                }
+
+                // Printed loyalty costs use a minus sign rather than a hyphen.
+                let amount = part[1..part.len() - 1].replace('\u{2212}', "-");
+                let amount = amount.trim_start_matches('+')
+                    .parse()
+                    .map_err(|_| ManaCostParseError::Malformed)?;
+
+                cost.loyalty = Some(amount);
+            } else if part == "Sacrifice this" || part.starts_with("Sacrifice this ") {
+                if cost.sacrifice {
+                    return Err(ManaCostParseError::Malformed);
+                }
+
+                cost.sacrifice = true;
+            } else {
+                if cost.mana.is_some() || part.is_empty() {
+                    return Err(ManaCostParseError::Malformed);
+                }
+
+                cost.mana = Some(ManaCost::parse(part)?);
+            }
+        }
+
+        Ok(cost)
+    }
+}
+
+/// An activated ability that adds mana as it resolves and has no targets
+/// (rule 605.1a), like a Forest's `{T}: Add {G}.`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManaAbility {
+    pub cost: ActivationCost,
+
+    /// The types of mana the ability can add. Activating it adds one mana of
+    /// whichever of these types the player picks.
+    // TODO: Abilities that add more than one mana, like Sol Ring
+    pub produces: Vec<ManaType>,
+}
+
+/// Any activated ability that isn't a mana ability.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivatedAbility {
+    pub cost: ActivationCost,
+
+    /// What the ability does when it resolves.
+    pub effects: Vec<Effect>,
+}
+
+/// What a triggered ability waits for, written after its "when," "whenever,"
+/// or "at" (rule 603.1).
+// TODO: Every other trigger event, like permanents entering or leaving the
+//       battlefield
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+    /// The beginning of the given step of its controller's turn, like `At the
+    /// beginning of your upkeep`.
+    BeginningOfYourStep(GamePhase),
+
+    /// Its controller casting a spell, like `Whenever you cast a spell`.
+    YouCastSpell,
+}
+
+/// An ability that does something whenever its trigger event happens, like an
+/// emblem's `At the beginning of your upkeep, draw a card.`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggeredAbility {
+    pub trigger: Trigger,
+
+    /// What the ability does when it resolves.
+    pub effects: Vec<Effect>,
+}
+
+/// A static ability that changes every permanent matching a description, like
+/// Blood Moon's `Nonbasic lands are Mountains.` (rule 604.2)
+///
+/// The permanents it affects are worked out again every time the game looks
+/// at them (rule 611.3a).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticAbility {
+    pub affected: ObjectFilter,
+
+    /// What the ability does to each permanent it affects. Each part is applied
+    /// in its own layer.
+    pub effects: Vec<ContinuousEffectKind>,
+}
+
+/// A description of a group of permanents, like "nonbasic lands" or "each
+/// other non-Aura enchantment".
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjectFilter {
+    /// Card types the permanent has to have all of.
+    pub card_types: Vec<CardType>,
+
+    /// Subtypes the permanent has to have all of.
+    pub subtypes: Vec<String>,
+
+    /// Supertypes the permanent can't have, like the "nonbasic" in "nonbasic
+    /// lands".
+    pub excluded_supertypes: Vec<Supertype>,
+
+    /// Subtypes the permanent can't have, like the "non-Aura" in "non-Aura
+    /// enchantment".
+    pub excluded_subtypes: Vec<String>,
+
+    /// Whether the permanent has to be controlled by whoever controls the
+    /// source, like "lands you control".
+    pub you_control: bool,
+
+    /// Whether the source itself is left out, like "each other creature".
+    pub other: bool,
+}
+
+impl ObjectFilter {
+    /// Whether a permanent with the given characteristics fits the
+    /// description. `is_source` says whether it's the source of the effect,
+    /// and `same_controller` whether the same player controls both.
+    pub fn matches(&self, characteristics: &Characteristics, is_source: bool, same_controller: bool) -> bool {
+        if (self.other && is_source) || (self.you_control && !same_controller) {
+            return false;
+        }
+
+        self.card_types.iter().all(|&card_type| characteristics.has_card_type(card_type))
+            && self.subtypes.iter().all(|subtype| characteristics.has_subtype(subtype))
+            && !self.excluded_supertypes.iter().any(|&supertype| characteristics.has_supertype(supertype))
+            && !self.excluded_subtypes.iter().any(|subtype| characteristics.has_subtype(subtype))
+    }
+
+    /// Replaces every subtype in the description that's exactly the word
+    /// `from` with `to`, for a text-changing effect (rule 612.2).
+    pub fn change_text(&mut self, from: &str, to: &str) {
+        for subtype in self.subtypes.iter_mut().chain(self.excluded_subtypes.iter_mut()) {
+            if subtype == from {
+                *subtype = to.to_string();
+            }
+        }
+    }
+}
+
+/// The abilities an object can have.
+#[derive(Debug, Clone, PartialEq)]
 pub enum Ability {
-    AddGreen,
+    /// Mana abilities don't use the stack. They can be activated whenever a
+    /// player has priority, or in the middle of casting a spell or activating
+    /// an ability to pay its costs (rule 605.3).
+    Mana(ManaAbility),
+
+    /// Every other activated ability goes on the stack when it's activated,
+    /// and has to wait for players to pass priority before it resolves.
+    Activated(ActivatedAbility),
+
+    /// A spell ability, which is just what an instant or sorcery does as it
+    /// resolves, like Shock's `Shock deals 2 damage to any target.`
+    /// (rule 113.3a). Only does anything on instant and sorcery spells.
+    Spell(Vec<Effect>),
+
+    /// Static abilities aren't activated at all. They change the game for as
+    /// long as their source's abilities function (rule 604.1).
+    Static(StaticAbility),
+
+    /// Replacement abilities change events as they would happen, like Rest in
+    /// Peace's (rule 614).
+    Replacement(ReplacementEffectKind),
+
+    /// Phasing, which phases the permanent out during each of its
+    /// controller's untap steps, and back in during the next one (rule
+    /// 702.26a).
+    Phasing,
+
+    /// Enchant, which limits what an Aura can be attached to (rule 702.5a),
+    /// like `Enchant creature`.
+    // TODO: Auras that enchant players
+    Enchant(ObjectFilter),
+
+    /// Triggered abilities go on the stack the next time a player would
+    /// receive priority after their trigger event happens (rule 603.3).
+    Triggered(TriggeredAbility),
+}
+
+impl Ability {
+    /// Replaces every instance of the word `from` in what the ability refers
+    /// to with `to`, for a text-changing effect like Mind Bend's (rule 612.2).
+    /// A Forest's mana ability comes from its land type rather than its text,
+    /// so mana abilities are left alone.
+    pub fn change_text(&mut self, from: &str, to: &str) {
+        match *self {
+            Ability::Static(ref mut ability) => {
+                ability.affected.change_text(from, to);
+
+                for effect in &mut ability.effects {
+                    effect.change_text(from, to);
+                }
+            },
+            Ability::Replacement(ref mut kind) => kind.change_text(from, to),
+            Ability::Enchant(ref mut filter) => filter.change_text(from, to),
+            Ability::Mana(_) | Ability::Activated(_) | Ability::Spell(_)
+            | Ability::Phasing | Ability::Triggered(_) => {},
+        }
+    }
+
+    /// Creates the common `{T}: Add {?}.` mana ability, like a basic land has.
+    pub fn tap_for_mana(mana_type: ManaType) -> Ability {
+        Ability::Mana(ManaAbility {
+            cost: ActivationCost {
+                tap: true,
+                ..ActivationCost::default()
+            },
+            produces: vec![mana_type],
+        })
+    }
+}
+
+/// A permanent's status, as described in rule 110.5. Status isn't a
+/// characteristic, so effects that copy or change an object's characteristics
+/// leave it alone.
+///
+/// Objects that aren't on the battlefield always have the default status:
+/// untapped, unflipped, face up, and phased in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PermanentStatus {
+    pub tapped: bool,
+    pub flipped: bool,
+
+    /// Face-down permanents are 2/2 creatures with no other characteristics
+    /// (rule 708.2). See `Game::turn_face_down`.
+    pub face_down: bool,
+
+    /// Phased-out permanents are treated as though they don't exist (rule
+    /// 702.26b). See `Game::phase_out`.
+    pub phased_out: bool,
 }
 
 /// Describes an object that exists anywhere in the game.
@@ -50,16 +367,121 @@ pub struct Object {
     /// zone.
     pub timestamp: Timestamp,
 
-    /// Temporary field denoting object details
+    /// The player who owns this object: the player who started the game with
+    /// the card in their deck, or who put the ability on the stack.
+    pub owner: Id,
+
+    /// The player who controls this object, before any control-changing
+    /// effects. Only objects on the battlefield and the stack can be
+    /// controlled by anyone other than their owner (rule 108.4).
+    ///
+    /// Use `Game::controller` to find out who controls an object right now.
+    pub controller: Id,
+
+    /// The amount of damage marked on this object. Damage is removed during the
+    /// cleanup step, or when the object changes zones.
+    pub damage: usize,
+
+    /// The object's status, like whether it's tapped. Status is reset when the
+    /// object changes zones.
+    pub status: PermanentStatus,
+
+    /// The number of each kind of counter on the object. Kinds without an
+    /// entry have no counters. Counters are lost when the object changes zones.
+    pub counters: HashMap<ObjectCounter, usize>,
+
+    /// Which face of a multi-faced card is up: the face it was cast or played
+    /// with, or the face a transforming permanent is showing. `None` means no
+    /// particular face, which is how cards are everywhere but the stack and
+    /// the battlefield.
+    ///
+    /// Flip cards use `PermanentStatus::flipped` instead.
+    pub face: Option<Face>,
+
+    /// The object this one is attached to, like the creature an Aura is
+    /// enchanting (rule 701.3). Attachments end when the object changes zones.
+    pub attached_to: Option<Id>,
+
     pub details: ObjectDetails,
+}
+
+impl Object {
+    /// The ID of the card definition this object was made from, if it was
+    /// made from one.
+    pub fn definition_id(&self) -> Option<Id> {
+        match self.details {
+            ObjectDetails::Card { definition_id } |
+            ObjectDetails::Token { definition_id } |
+            ObjectDetails::Emblem { definition_id } => Some(definition_id),
+            ObjectDetails::Ability { .. } => None,
+        }
+    }
+
+    pub fn is_card(&self) -> bool {
+        matches!(self.details, ObjectDetails::Card { .. })
+    }
+
+    pub fn is_token(&self) -> bool {
+        matches!(self.details, ObjectDetails::Token { .. })
+    }
+
+    pub fn is_emblem(&self) -> bool {
+        matches!(self.details, ObjectDetails::Emblem { .. })
+    }
+
+    /// The number of counters of the given kind on the object.
+    pub fn counters(&self, kind: &ObjectCounter) -> usize {
+        self.counters.get(kind).cloned().unwrap_or(0)
+    }
+
+    /// Whether the object is tapped. Objects that can't be tapped never are.
+    pub fn is_tapped(&self) -> bool {
+        self.status.tapped
+    }
+
+    /// Taps or untaps the object, returning `false` if it can't be tapped.
+    pub fn set_tapped(&mut self, new_tapped: bool) -> bool {
+        match self.details {
+            ObjectDetails::Card { .. } | ObjectDetails::Token { .. } => {
+                self.status.tapped = new_tapped;
+                true
+            },
+            ObjectDetails::Emblem { .. } | ObjectDetails::Ability { .. } => false,
+        }
+    }
+}
+
+/// What an object looks like right now, after applying every effect that
+/// changes it. Returned by `Game::view_object`.
+#[derive(Debug, Clone)]
+pub struct ObjectView {
+    /// The object itself, with its current controller.
+    pub object: Object,
+
+    /// The object's current characteristics, if it has any. Power, toughness,
+    /// loyalty, and defense are always `StatValue::Fixed` here.
+    pub characteristics: Option<Characteristics>,
+}
+
+/// What an object looked like just before it left its zone or the game, for
+/// anything that needs to look back at it afterwards, like an ability whose
+/// source has died (rule 608.2h).
+#[derive(Debug, Clone)]
+pub struct LastKnownInformation {
+    /// The object as it last existed, with every effect still applied.
+    pub view: ObjectView,
+}
+
+impl ObjectView {
+    pub fn power(&self) -> Option<i32> {
+        self.characteristics.as_ref()?.power.map(|power| power.value())
+    }
 
-    /// All of the abilities that this object has available to activate
-    pub abilities: HashMap<Id, Ability>,
+    pub fn toughness(&self) -> Option<i32> {
+        self.characteristics.as_ref()?.toughness.map(|toughness| toughness.value())
+    }
 
-    // TODO: Types
-    // TODO: Supertypes
-    // TODO: Subtypes
-    // TODO: Owner, a player ID
-    // TODO: Controller, a player ID
-    // TODO: Counters of various types
+    pub fn loyalty(&self) -> Option<i32> {
+        self.characteristics.as_ref()?.loyalty.map(|loyalty| loyalty.value())
+    }
 }
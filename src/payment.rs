@@ -0,0 +1,350 @@
+use id::Id;
+use mana::{Color, ManaPool, ManaType};
+use mana_cost::{ManaCost, ManaSymbol};
+
+/// Something a player could activate to produce mana while paying a cost, like
+/// an untapped Forest. Each activation produces one mana of any one of the
+/// listed types.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManaSource {
+    pub object_id: Id,
+    pub ability_id: Id,
+    pub produces: Vec<ManaType>,
+}
+
+/// A mana source that a `PaymentPlan` activates, and the type of mana it should
+/// produce.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceActivation {
+    pub object_id: Id,
+    pub ability_id: Id,
+    pub mana_type: ManaType,
+}
+
+/// A concrete way to pay a mana cost.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentPlan {
+    /// The mana sources to activate before paying, adding their mana to the
+    /// player's mana pool.
+    pub activations: Vec<SourceActivation>,
+
+    /// The mana to spend from the pool once every source has been activated.
+    pub mana: ManaPool,
+
+    /// The life to pay in place of Phyrexian mana symbols.
+    pub life: usize,
+}
+
+/// How many of a cost's `{2/B}`-style and Phyrexian symbols of each color are
+/// paid the unusual way: with two generic mana or with 2 life. Colors are
+/// indexed in WUBRG order.
+#[derive(Clone, Copy, Default)]
+struct Alternatives {
+    mono_hybrid: [usize; 5],
+    phyrexian: [usize; 5],
+}
+
+fn color_index(color: Color) -> usize {
+    Color::all().iter().position(|&other| other == color).unwrap()
+}
+
+/// What a cost asks for once every symbol with two ways of paying it has had
+/// one picked.
+struct Requirements {
+    /// Single mana that have to be one of the given types.
+    specific: Vec<Vec<ManaType>>,
+
+    /// Mana that can be of any type.
+    generic: usize,
+
+    life: usize,
+}
+
+impl Requirements {
+    /// Lays out what the cost needs, paying as many `{2/B}`-style and
+    /// Phyrexian symbols the unusual way as `alternatives` says.
+    fn new(cost: &ManaCost, x: usize, alternatives: &Alternatives) -> Requirements {
+        let mut requirements = Requirements {
+            specific: Vec::new(),
+            generic: 0,
+            life: 0,
+        };
+
+        let mut left = *alternatives;
+
+        for symbol in &cost.symbols {
+            match *symbol {
+                ManaSymbol::Generic(amount) => requirements.generic += amount,
+                ManaSymbol::Variable => requirements.generic += x,
+                ManaSymbol::Colored(color) => requirements.specific.push(vec![color.mana_type()]),
+                ManaSymbol::Colorless => requirements.specific.push(vec![ManaType::Colorless]),
+                ManaSymbol::Hybrid(first, second) => {
+                    requirements.specific.push(vec![first.mana_type(), second.mana_type()]);
+                },
+                ManaSymbol::MonoHybrid(color) => {
+                    let index = color_index(color);
+
+                    if left.mono_hybrid[index] > 0 {
+                        left.mono_hybrid[index] -= 1;
+                        requirements.generic += 2;
+                    } else {
+                        requirements.specific.push(vec![color.mana_type()]);
+                    }
+                },
+                ManaSymbol::Phyrexian(color) => {
+                    let index = color_index(color);
+
+                    if left.phyrexian[index] > 0 {
+                        left.phyrexian[index] -= 1;
+                        requirements.life += 2;
+                    } else {
+                        requirements.specific.push(vec![color.mana_type()]);
+                    }
+                },
+            }
+        }
+
+        requirements
+    }
+}
+
+/// Lists every way of picking between the two options of each `{2/B}`-style
+/// or Phyrexian symbol in the cost.
+///
+/// Symbols of the same kind and color are interchangeable, so only how many of
+/// them are paid each way matters. That keeps the number of choices from
+/// growing exponentially with the number of symbols.
+fn alternative_choices(cost: &ManaCost) -> Vec<Alternatives> {
+    let mut totals = Alternatives::default();
+
+    for symbol in &cost.symbols {
+        match *symbol {
+            ManaSymbol::MonoHybrid(color) => totals.mono_hybrid[color_index(color)] += 1,
+            ManaSymbol::Phyrexian(color) => totals.phyrexian[color_index(color)] += 1,
+            _ => {},
+        }
+    }
+
+    let mut choices = vec![Alternatives::default()];
+
+    for index in 0..5 {
+        choices = choices.into_iter()
+            .flat_map(|choice| {
+                (0..=totals.mono_hybrid[index]).flat_map(move |mono_hybrid| {
+                    (0..=totals.phyrexian[index]).map(move |phyrexian| {
+                        let mut choice = choice;
+                        choice.mono_hybrid[index] = mono_hybrid;
+                        choice.phyrexian[index] = phyrexian;
+                        choice
+                    })
+                })
+            })
+            .collect();
+    }
+
+    choices
+}
+
+/// A single mana that could be spent: either already floating in the pool, or
+/// produced by activating a source.
+#[derive(Clone, Copy)]
+enum Unit<'a> {
+    Pool(ManaType),
+    Source(&'a ManaSource),
+}
+
+impl<'a> Unit<'a> {
+    fn can_produce(&self, mana_type: ManaType) -> bool {
+        match *self {
+            Unit::Pool(pool_type) => pool_type == mana_type,
+            Unit::Source(source) => source.produces.contains(&mana_type),
+        }
+    }
+
+    fn can_pay(&self, allowed: &[ManaType]) -> bool {
+        allowed.iter().any(|&mana_type| self.can_produce(mana_type))
+    }
+}
+
+/// Tries to find a unit for the given specific requirement, moving other
+/// requirements to different units if that frees one up.
+fn assign(
+    requirement: usize,
+    specific: &[Vec<ManaType>],
+    units: &[Unit],
+    visited: &mut [bool],
+    unit_assignments: &mut [Option<usize>],
+) -> bool {
+    for (unit_index, unit) in units.iter().enumerate() {
+        if visited[unit_index] || !unit.can_pay(&specific[requirement]) {
+            continue;
+        }
+
+        visited[unit_index] = true;
+
+        let freed = match unit_assignments[unit_index] {
+            Some(other) => assign(other, specific, units, visited, unit_assignments),
+            None => true,
+        };
+
+        if freed {
+            unit_assignments[unit_index] = Some(requirement);
+            return true;
+        }
+    }
+
+    false
+}
+
+/// What a unit ends up being spent on.
+#[derive(Clone, Copy)]
+enum Spent {
+    /// The specific requirement at this index.
+    Specific(usize),
+    Generic,
+}
+
+/// Picks units to pay the requirements with, returning what each unit is spent
+/// on, if anything.
+///
+/// Units earlier in the list are preferred.
+fn match_units(requirements: &Requirements, units: &[Unit]) -> Option<Vec<Option<Spent>>> {
+    let mut unit_assignments = vec![None; units.len()];
+
+    for requirement in 0..requirements.specific.len() {
+        let mut visited = vec![false; units.len()];
+
+        if !assign(requirement, &requirements.specific, units, &mut visited, &mut unit_assignments) {
+            return None;
+        }
+    }
+
+    let mut generic_left = requirements.generic;
+
+    let spent = unit_assignments.into_iter()
+        .map(|assignment| match assignment {
+            Some(requirement) => Some(Spent::Specific(requirement)),
+            None if generic_left > 0 => {
+                generic_left -= 1;
+                Some(Spent::Generic)
+            },
+            None => None,
+        })
+        .collect();
+
+    if generic_left > 0 {
+        return None;
+    }
+
+    Some(spent)
+}
+
+fn pool_units(pool: &ManaPool) -> Vec<Unit<'static>> {
+    let mut units = Vec::new();
+
+    for &mana_type in &ManaType::all() {
+        for _ in 0..pool.get(mana_type) {
+            units.push(Unit::Pool(mana_type));
+        }
+    }
+
+    units
+}
+
+/// Works out how to pay a mana cost, using the mana already in a pool and the
+/// given mana sources, paying at most `max_life` life for Phyrexian symbols.
+/// `x` is the value chosen for `{X}`.
+///
+/// Plans that pay less life are preferred, followed by plans that activate
+/// fewer sources. Mana in the pool is always spent before activating a source,
+/// and sources that can produce fewer types of mana are activated first.
+/// Returns `None` if there's no way to pay the cost.
+pub fn solve_payment(cost: &ManaCost, x: usize, pool: &ManaPool, sources: &[ManaSource], max_life: usize) -> Option<PaymentPlan> {
+    let mut sorted_sources: Vec<&ManaSource> = sources.iter()
+        .filter(|source| !source.produces.is_empty())
+        .collect();
+    sorted_sources.sort_by_key(|source| source.produces.len());
+
+    let mut units = pool_units(pool);
+    units.extend(sorted_sources.into_iter().map(Unit::Source));
+
+    let mut best_plan: Option<PaymentPlan> = None;
+
+    for alternatives in alternative_choices(cost) {
+        let requirements = Requirements::new(cost, x, &alternatives);
+
+        if requirements.life > max_life {
+            continue;
+        }
+
+        let spent = match match_units(&requirements, &units) {
+            Some(spent) => spent,
+            None => continue,
+        };
+
+        let mut plan = PaymentPlan {
+            activations: Vec::new(),
+            mana: ManaPool::new(),
+            life: requirements.life,
+        };
+
+        for (unit, spent_on) in units.iter().zip(spent) {
+            let spent_on = match spent_on {
+                Some(spent_on) => spent_on,
+                None => continue,
+            };
+
+            let mana_type = match *unit {
+                Unit::Pool(mana_type) => mana_type,
+                Unit::Source(source) => {
+                    let mana_type = match spent_on {
+                        Spent::Specific(requirement) => {
+                            let allowed = &requirements.specific[requirement];
+
+                            *source.produces.iter()
+                                .find(|mana_type| allowed.contains(mana_type))
+                                .unwrap()
+                        },
+                        Spent::Generic => source.produces[0],
+                    };
+
+                    plan.activations.push(SourceActivation {
+                        object_id: source.object_id,
+                        ability_id: source.ability_id,
+                        mana_type,
+                    });
+
+                    mana_type
+                },
+            };
+
+            plan.mana.add(mana_type, 1);
+        }
+
+        let is_better = match best_plan {
+            Some(ref best_plan) => {
+                (plan.life, plan.activations.len()) < (best_plan.life, best_plan.activations.len())
+            },
+            None => true,
+        };
+
+        if is_better {
+            best_plan = Some(plan);
+        }
+    }
+
+    best_plan
+}
+
+/// Checks that spending exactly the given mana and life pays the cost, with
+/// nothing left over. `x` is the value chosen for `{X}`.
+pub fn pays_exactly(cost: &ManaCost, x: usize, mana: &ManaPool, life: usize) -> bool {
+    let units = pool_units(mana);
+
+    alternative_choices(cost)
+        .into_iter()
+        .map(|alternatives| Requirements::new(cost, x, &alternatives))
+        .filter(|requirements| requirements.life == life)
+        .filter(|requirements| requirements.specific.len() + requirements.generic == units.len())
+        .any(|requirements| match_units(&requirements, &units).is_some())
+}
@@ -1,11 +1,76 @@
-use id::Id;
+use std::collections::HashMap;
+
+use id::{Id, get_id};
+
+/// The life total each player starts with in most formats (rule 103.4).
+pub const DEFAULT_STARTING_LIFE: i32 = 20;
+
+/// The number of poison counters that makes a player lose the game
+/// (rule 704.5c).
+pub const LETHAL_POISON_COUNTERS: usize = 10;
+
+/// The kinds of counters that can be put on a player.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PlayerCounter {
+    /// A player with ten or more poison counters loses the game (rule 104.3d).
+    Poison,
+    Energy,
+    Experience,
+
+    /// Any other kind of counter, named by the card that creates it.
+    Named(String),
+}
 
 /// Describes a player actively engaged in a duel.
 #[derive(Debug, Clone)]
 pub struct Player {
     pub id: Id,
 
+    /// The player's life total, which can drop below zero.
+    pub life: i32,
+
+    /// The number of each kind of counter the player has. Kinds without an
+    /// entry have no counters.
+    pub counters: HashMap<PlayerCounter, usize>,
+
+    /// The number of cards the player discards down to during their cleanup
+    /// step. Effects like Reliquary Tower can remove the limit entirely.
+    pub max_hand_size: Option<usize>,
+
+    /// The number of lands the player has played this turn.
+    pub lands_played_this_turn: usize,
+
+    /// Extra land plays granted to the player for the rest of this turn, on top
+    /// of the one the rules allow. This is where effects that let a player play
+    /// more lands, like Explore, grant them, and it's reset as each turn
+    /// starts.
+    pub additional_land_plays: usize,
+
+    /// Whether the player tried to draw from an empty library since
+    /// state-based actions were last checked, which makes them lose the game
+    /// (rule 704.5b).
+    pub drew_from_empty_library: bool,
+
     // TODO: Reference to some descriptor containing name?
-    // TODO: Life total
-    // TODO: Counters, like energy and poison
+}
+
+impl Player {
+    /// Creates a new player with the given starting life total and the usual
+    /// maximum hand size of seven.
+    pub fn new(starting_life: i32) -> Player {
+        Player {
+            id: get_id(),
+            life: starting_life,
+            counters: HashMap::new(),
+            max_hand_size: Some(7),
+            lands_played_this_turn: 0,
+            additional_land_plays: 0,
+            drew_from_empty_library: false,
+        }
+    }
+
+    /// The number of counters of the given kind the player has.
+    pub fn counters(&self, kind: &PlayerCounter) -> usize {
+        self.counters.get(kind).cloned().unwrap_or(0)
+    }
 }
@@ -0,0 +1,154 @@
+use characteristics::Characteristics;
+use continuous_effect::Duration;
+use game::{DamageTarget, GameMutation};
+use id::Id;
+use object::ObjectFilter;
+use timestamp::Timestamp;
+
+/// The ways replacement effects can change an event before it happens.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplacementEffectKind {
+    /// This permanent enters the battlefield tapped, like a Guildgate. Only
+    /// works as an ability of the permanent itself, which applies as it enters
+    /// (rule 614.12).
+    EntersTapped,
+
+    /// Permanents matching the filter enter the battlefield tapped, like with
+    /// Frozen Aether.
+    PermanentsEnterTapped {
+        affected: ObjectFilter,
+    },
+
+    /// If the effect's controller would draw a card, these happen instead,
+    /// like with Words of Worship.
+    DrawInstead {
+        instead: Vec<GameMutation>,
+    },
+
+    /// Cards and tokens matching the filter that would be put into a graveyard
+    /// from anywhere are exiled instead, like with Rest in Peace.
+    ExileInstead {
+        affected: ObjectFilter,
+    },
+
+    /// Twice as many counters are put on permanents matching the filter, like
+    /// with Doubling Season.
+    DoubleCounters {
+        affected: ObjectFilter,
+    },
+
+    /// Damage matching the filter is prevented, like with Fog. With an
+    /// `amount`, only that much of each event is prevented, like with Urza's
+    /// Armor.
+    PreventDamage {
+        damage: DamageFilter,
+        amount: Option<usize>,
+    },
+
+    /// The next `amount` damage matching the filter is prevented, like with
+    /// Healing Salve. The shield wears down as it prevents damage, and ends
+    /// once it's used up (rule 615.7). Only works as an effect created by a
+    /// spell or ability.
+    PreventionShield {
+        damage: DamageFilter,
+        amount: usize,
+    },
+
+    /// Damage matching the filter can't be prevented, like with Leyline of
+    /// Punishment. This doesn't change any events itself. Instead, prevention
+    /// effects don't apply to the damage (rule 615.12).
+    DamageCantBePrevented {
+        damage: DamageFilter,
+    },
+
+    // TODO: Self-replacement effects, which apply before any others
+    //       (rule 614.15)
+}
+
+impl ReplacementEffectKind {
+    /// Whether this is a prevention effect, which watches for damage and stops
+    /// some or all of it (rule 615.1).
+    pub fn is_prevention(&self) -> bool {
+        matches!(*self, ReplacementEffectKind::PreventDamage { .. } | ReplacementEffectKind::PreventionShield { .. })
+    }
+
+    /// Replaces every instance of the word `from` in the permanents this
+    /// effect describes with `to`, for a text-changing effect.
+    pub fn change_text(&mut self, from: &str, to: &str) {
+        match *self {
+            ReplacementEffectKind::PermanentsEnterTapped { ref mut affected }
+            | ReplacementEffectKind::ExileInstead { ref mut affected }
+            | ReplacementEffectKind::DoubleCounters { ref mut affected } => {
+                affected.change_text(from, to);
+            },
+            ReplacementEffectKind::PreventDamage { ref mut damage, .. }
+            | ReplacementEffectKind::PreventionShield { ref mut damage, .. }
+            | ReplacementEffectKind::DamageCantBePrevented { ref mut damage } => {
+                if let Some(ref mut source) = damage.source {
+                    source.change_text(from, to);
+                }
+            },
+            ReplacementEffectKind::EntersTapped | ReplacementEffectKind::DrawInstead { .. } => {},
+        }
+    }
+}
+
+/// Describes the damage a prevention effect applies to. Every field left empty
+/// matches any damage.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DamageFilter {
+    /// Only damage dealt by this source.
+    pub source_id: Option<Id>,
+
+    /// Only damage dealt by sources fitting this description, like "creatures
+    /// you control".
+    pub source: Option<ObjectFilter>,
+
+    /// Only damage dealt to this player or object.
+    pub target: Option<DamageTarget>,
+
+    /// Only combat damage, like with Fog.
+    pub combat_only: bool,
+}
+
+impl DamageFilter {
+    /// Whether damage from the given source to the given target matches this
+    /// filter.
+    pub fn matches(&self, source_id: Id, target: DamageTarget, combat: bool) -> bool {
+        self.source_id.is_none_or(|id| id == source_id)
+            && self.target.is_none_or(|filter_target| filter_target == target)
+            && (combat || !self.combat_only)
+    }
+
+    /// Whether a source with the given characteristics fits the filter's
+    /// description of its source, if it has one. `same_controller` says
+    /// whether the source's controller also controls the effect.
+    pub fn matches_source(&self, source: Option<&Characteristics>, same_controller: bool) -> bool {
+        match self.source {
+            Some(ref filter) => source.is_some_and(|source| filter.matches(source, false, same_controller)),
+            None => true,
+        }
+    }
+}
+
+/// An effect that watches for an event and changes it as it would happen,
+/// usually written with "instead" (rule 614.1). Prevention effects work the
+/// same way (rule 615).
+///
+/// These are the replacement effects created by spells and abilities as they
+/// resolve. Permanents with replacement abilities, like Rest in Peace, create
+/// theirs for as long as the ability functions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplacementEffect {
+    pub id: Id,
+
+    /// The player the effect belongs to, who "you" means in its text.
+    pub controller: Id,
+
+    /// When the effect began, which decides the order effects apply in if
+    /// nobody chooses otherwise.
+    pub timestamp: Timestamp,
+
+    pub duration: Duration,
+    pub kind: ReplacementEffectKind,
+}
@@ -1,87 +1,292 @@
-/// These utilities are intended for use by the test suite.
+//! These utilities are intended for use by the test suite.
 
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use game::{Game, GamePhase, GameStatus};
+use card_definition::CardRegistry;
+use characteristics::{CardType, Characteristics, StatValue, Supertype};
+use continuous_effect::{ContinuousEffectKind, Duration};
+use game::{Game, GamePhase, GameStatus, PlayerAction, PlayerChoice};
 use id::{Id, get_id};
-use player::Player;
+use mana::ManaPool;
+use mana_cost::ManaCost;
+use object::Ability;
+use player::{DEFAULT_STARTING_LIFE, Player};
+use replacement_effect::ReplacementEffectKind;
 use zone::{Zone, ZoneDetails};
 
 /// A test method for quickly bootstrapping a valid two-player `Game`.
 pub fn new_two_player_game() -> Game {
+    new_game(2)
+}
+
+/// Bootstraps a valid `Game` with the given number of players, starting in the
+/// first player's precombat main phase.
+pub fn new_game(player_count: usize) -> Game {
+    new_game_with_starting_life(player_count, DEFAULT_STARTING_LIFE)
+}
+
+/// Like `new_game`, but with every player starting at the given life total,
+/// like 40 for Commander.
+pub fn new_game_with_starting_life(player_count: usize, starting_life: i32) -> Game {
     let mut game = Game {
         zones: HashMap::new(),
         objects: HashMap::new(),
         mana_pools: HashMap::new(),
         players: HashMap::new(),
         player_turn_order: Vec::new(),
-        current_phase: GamePhase::Main,
+        losers: Vec::new(),
+        starting_player_count: player_count,
+        continuous_effects: Vec::new(),
+        replacement_effects: Vec::new(),
+        last_known_information: HashMap::new(),
+        new_object_ids: HashMap::new(),
+        suspended_mutations: Vec::new(),
+        attacks: HashMap::new(),
+        adventurers: HashMap::new(),
+        loyalty_abilities_activated: Vec::new(),
+        pending_triggers: Vec::new(),
+        payment_activations: Vec::new(),
+        layer_cache: RefCell::new(None),
+        registry: Arc::new(CardRegistry::new()),
+        current_phase: GamePhase::PrecombatMain,
         current_status: GameStatus::NeedsPlayerAction,
+        turn_number: 1,
+        consecutive_passes: 0,
 
         // We'll mutate these before we return
         active_player: None,
+        next_active_player: None,
         priority_player: None,
     };
 
-    let battlefield = Zone {
-        id: get_id(),
-        details: ZoneDetails::Battlefield,
-    };
-    game.zones.insert(battlefield.id, battlefield);
+    add_zone(&mut game, ZoneDetails::Battlefield);
+    add_zone(&mut game, ZoneDetails::Stack);
+    add_zone(&mut game, ZoneDetails::Command);
+    add_zone(&mut game, ZoneDetails::Exile);
 
-    let player1 = Player {
-        id: get_id(),
-    };
-    game.active_player = Some(player1.id);
-    game.priority_player = Some(player1.id);
-    game.mana_pools.insert(player1.id, 0);
+    for _ in 0..player_count {
+        add_player(&mut game, starting_life);
+    }
 
-    let player1_hand = Zone {
-        id: get_id(),
-        details: ZoneDetails::Hand {
-            player_id: player1.id,
-        },
-    };
+    game.active_player = Some(game.player_turn_order[0]);
+    game.priority_player = game.active_player;
 
-    game.player_turn_order.push(player1.id);
-    game.players.insert(player1.id, player1);
-    game.zones.insert(player1_hand.id, player1_hand);
+    game
+}
 
-    let player2 = Player {
-        id: get_id(),
-    };
-    game.mana_pools.insert(player2.id, 0);
+/// Adds a new player to the end of the turn order, along with all of the zones
+/// they own.
+fn add_player(game: &mut Game, starting_life: i32) -> Id {
+    let player = Player::new(starting_life);
+    let player_id = player.id;
+
+    game.mana_pools.insert(player_id, ManaPool::new());
+    game.player_turn_order.push(player_id);
+    game.players.insert(player_id, player);
 
-    let player2_hand = Zone {
+    add_zone(game, ZoneDetails::Hand { player_id });
+    add_zone(game, ZoneDetails::Library { player_id });
+    add_zone(game, ZoneDetails::Graveyard { player_id });
+
+    player_id
+}
+
+fn add_zone(game: &mut Game, details: ZoneDetails) {
+    let zone = Zone {
         id: get_id(),
-        details: ZoneDetails::Hand {
-            player_id: player2.id,
-        },
+        details,
+        order: Vec::new(),
     };
 
-    game.player_turn_order.push(player2.id);
-    game.players.insert(player2.id, player2);
-    game.zones.insert(player2_hand.id, player2_hand);
-
-    game
+    game.zones.insert(zone.id, zone);
 }
 
 pub fn get_hand_id(game: &Game, target_player_id: Id) -> Id {
-    game.find_zone_id(|zone| {
-            match zone.details {
-                ZoneDetails::Hand { player_id } => player_id == target_player_id,
-                _ => false,
-            }
-        })
-        .unwrap()
+    game.hand_id(target_player_id).unwrap()
+}
+
+pub fn get_library_id(game: &Game, target_player_id: Id) -> Id {
+    game.library_id(target_player_id).unwrap()
+}
+
+pub fn get_graveyard_id(game: &Game, target_player_id: Id) -> Id {
+    game.graveyard_id(target_player_id).unwrap()
 }
 
 pub fn get_battlefield_id(game: &Game) -> Id {
-    game.find_zone_id(|zone| {
-            match zone.details {
-                ZoneDetails::Battlefield => true,
-                _ => false,
-            }
-        })
-        .unwrap()
+    game.battlefield_id().unwrap()
+}
+
+pub fn get_stack_id(game: &Game) -> Id {
+    game.stack_id().unwrap()
+}
+
+pub fn get_command_id(game: &Game) -> Id {
+    game.command_id().unwrap()
+}
+
+pub fn get_exile_id(game: &Game) -> Id {
+    game.exile_id().unwrap()
+}
+
+/// The characteristics of a Forest, minus its mana ability so that tests can
+/// give it exactly the abilities they need.
+pub fn forest() -> Characteristics {
+    Characteristics {
+        name: "Forest".to_string(),
+        supertypes: vec![Supertype::Basic],
+        card_types: vec![CardType::Land],
+        subtypes: vec!["Forest".to_string()],
+        ..Characteristics::default()
+    }
+}
+
+/// The characteristics of Grizzly Bears, a vanilla 2/2 creature.
+pub fn grizzly_bears() -> Characteristics {
+    Characteristics {
+        name: "Grizzly Bears".to_string(),
+        mana_cost: Some(ManaCost::parse("{1}{G}").unwrap()),
+        card_types: vec![CardType::Creature],
+        subtypes: vec!["Bear".to_string()],
+        power: Some(StatValue::Fixed(2)),
+        toughness: Some(StatValue::Fixed(2)),
+        ..Characteristics::default()
+    }
+}
+
+/// Adds a new card definition to the game's registry, returning its ID.
+pub fn register_card(game: &mut Game, characteristics: Characteristics) -> Id {
+    Arc::make_mut(&mut game.registry).add(characteristics)
+}
+
+/// Creates an untapped object with the given characteristics in the given
+/// zone, putting it on top if the zone is ordered.
+///
+/// Every object created this way gets its own card definition, so tests can
+/// change one object's characteristics without affecting any others.
+pub fn create_object(game: &mut Game, zone_id: Id, owner: Id, characteristics: Characteristics) -> Id {
+    let definition_id = register_card(game, characteristics);
+
+    game.create_card(definition_id, zone_id, owner)
+}
+
+/// Creates an untapped Forest with no abilities in the given zone, putting it
+/// on top if the zone is ordered.
+pub fn create_forest(game: &mut Game, zone_id: Id, owner: Id) -> Id {
+    create_object(game, zone_id, owner, forest())
+}
+
+/// Creates a vanilla creature with the given name, power, and toughness on the
+/// battlefield, under its owner's control.
+pub fn add_creature(game: &mut Game, owner: Id, name: &str, power: i32, toughness: i32) -> Id {
+    let battlefield_id = get_battlefield_id(game);
+
+    create_object(game, battlefield_id, owner, Characteristics {
+        name: name.to_string(),
+        card_types: vec![CardType::Creature],
+        power: Some(StatValue::Fixed(power)),
+        toughness: Some(StatValue::Fixed(toughness)),
+        ..Characteristics::default()
+    })
+}
+
+/// Gives an object a continuous effect that lasts until end of turn.
+pub fn add_effect(game: &mut Game, object_id: Id, kind: ContinuousEffectKind) {
+    game.add_continuous_effect(object_id, Duration::UntilEndOfTurn, kind);
+}
+
+/// Creates a replacement effect controlled by player 1 that lasts until end of
+/// turn, returning the effect's ID.
+pub fn add_replacement_effect(game: &mut Game, kind: ReplacementEffectKind) -> Id {
+    let player1_id = game.player_turn_order[0];
+
+    game.add_replacement_effect(player1_id, Duration::UntilEndOfTurn, kind)
+}
+
+pub fn is_tapped(game: &Game, object_id: Id) -> bool {
+    game.objects[&object_id].is_tapped()
+}
+
+pub fn life(game: &Game, player_id: Id) -> i32 {
+    game.players[&player_id].life
+}
+
+/// The object's power after continuous effects are applied.
+pub fn power(game: &Game, object_id: Id) -> i32 {
+    game.view_object(object_id).unwrap().power().unwrap()
+}
+
+/// The object's characteristics after continuous effects are applied.
+pub fn view(game: &Game, object_id: Id) -> Characteristics {
+    game.view_object(object_id).unwrap().characteristics.unwrap()
+}
+
+/// Gives an object a new ability, returning the ability's ID.
+pub fn add_ability(game: &mut Game, object_id: Id, ability: Ability) -> Id {
+    let ability_id = get_id();
+    let definition_id = game.objects.get(&object_id).unwrap()
+        .definition_id().unwrap();
+
+    Arc::make_mut(&mut game.registry)
+        .get_mut(definition_id).unwrap()
+        .characteristics.abilities.insert(ability_id, ability);
+
+    ability_id
+}
+
+/// Puts `count` Forests into each player's library, so that they can get
+/// through their draw steps without losing the game.
+pub fn fill_libraries(game: &mut Game, count: usize) {
+    for player_id in game.player_turn_order.clone() {
+        let library_id = get_library_id(game, player_id);
+
+        for _ in 0..count {
+            create_forest(game, library_id, player_id);
+        }
+    }
+}
+
+/// Declares no attackers or blockers for as long as the game is waiting on a
+/// player to declare them, so that tests can pass through combat.
+pub fn skip_combat_declarations(game: &mut Game) {
+    loop {
+        let (player_id, action) = match game.current_status {
+            GameStatus::NeedsPlayerChoice(PlayerChoice::DeclareAttackers { player_id }) => {
+                (player_id, PlayerAction::DeclareAttackers { attackers: Vec::new() })
+            },
+            GameStatus::NeedsPlayerChoice(PlayerChoice::DeclareBlockers { player_id }) => {
+                (player_id, PlayerAction::DeclareBlockers { blockers: Vec::new() })
+            },
+            _ => return,
+        };
+
+        game.do_player_action(player_id, &action).unwrap();
+    }
+}
+
+/// Has every player pass priority once, starting with the player who currently
+/// holds it. Any attackers or blockers the game is waiting on are declared as
+/// none first.
+pub fn pass_priority_round(game: &mut Game) {
+    for _ in 0..game.player_turn_order.len() {
+        skip_combat_declarations(game);
+
+        let player_id = game.priority_player
+            .expect("No player has priority!");
+
+        game.do_player_action(player_id, &PlayerAction::PassPriority).unwrap();
+    }
+}
+
+/// Passes priority around until the game enters the given phase.
+pub fn pass_priority_until(game: &mut Game, phase: GamePhase) {
+    loop {
+        pass_priority_round(game);
+
+        if game.current_phase == phase {
+            break;
+        }
+    }
 }
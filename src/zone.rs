@@ -4,9 +4,45 @@ use id::Id;
 #[derive(Debug, Clone)]
 pub enum ZoneDetails {
     Battlefield,
+
+    /// Holds spells and abilities waiting to resolve. The top of the stack is
+    /// the last object in its `Zone::order`.
+    Stack,
+
+    /// Holds emblems, and other objects that affect the game without being
+    /// permanents (rule 408). Shared by every player.
+    Command,
+
+    /// Holds objects that have been exiled. Shared by every player, and
+    /// unordered (rule 406).
+    Exile,
     Hand {
         player_id: Id,
     },
+    Library {
+        player_id: Id,
+    },
+    Graveyard {
+        player_id: Id,
+    },
+}
+
+impl ZoneDetails {
+    /// The player this zone belongs to, if it belongs to a specific player.
+    pub fn player_id(&self) -> Option<Id> {
+        match *self {
+            ZoneDetails::Hand { player_id }
+            | ZoneDetails::Library { player_id }
+            | ZoneDetails::Graveyard { player_id } => Some(player_id),
+            _ => None,
+        }
+    }
+
+    /// Whether the order of objects in this zone matters, like in libraries,
+    /// graveyards and the stack.
+    pub fn is_ordered(&self) -> bool {
+        matches!(*self, ZoneDetails::Stack | ZoneDetails::Library { .. } | ZoneDetails::Graveyard { .. })
+    }
 }
 
 /// Represents a single zone in the game.
@@ -15,8 +51,9 @@ pub struct Zone {
     pub id: Id,
     pub details: ZoneDetails,
 
-    // TODO: Zone order -- should that be handled in ZoneDetails or in a field
-    // of Object?
+    /// The IDs of the objects in this zone from bottom to top, for zones where
+    /// order matters. Unordered zones, like the battlefield, leave this empty.
+    pub order: Vec<Id>,
 }
 
 impl Zone {
@@ -0,0 +1,414 @@
+extern crate corrosion;
+
+use corrosion::{
+    Ability,
+    ActivatedAbility,
+    ActivationCost,
+    CardType,
+    Characteristics,
+    Effect,
+    GameStatus,
+    ManaAbility,
+    ManaCost,
+    ManaType,
+    ObjectDetails,
+    PlayerAction,
+    PlayerChoice,
+};
+
+use corrosion::utility::*;
+
+/// `{1}, {T}: Draw a card.`
+fn draw_ability() -> Ability {
+    Ability::Activated(ActivatedAbility {
+        cost: ActivationCost {
+            mana: Some(ManaCost::parse("{1}").unwrap()),
+            tap: true,
+            ..ActivationCost::default()
+        },
+        effects: vec![Effect::DrawCards { count: 1 }],
+    })
+}
+
+#[test]
+fn test_ability_uses_stack() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let library_id = get_library_id(&game, player1_id);
+    let hand_id = get_hand_id(&game, player1_id);
+    let stack_id = get_stack_id(&game);
+
+    create_forest(&mut game, library_id, player1_id);
+
+    let source_id = create_forest(&mut game, battlefield_id, player1_id);
+    let ability_id = add_ability(&mut game, source_id, draw_ability());
+
+    let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+    add_ability(&mut game, forest_id, Ability::tap_for_mana(ManaType::Green));
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateAbility {
+        object_id: source_id,
+        ability_id,
+    }).unwrap();
+
+    // Costs are paid right away: the source taps, and the Forest taps to pay
+    // for {1}.
+    assert!(game.objects.get(&source_id).unwrap().is_tapped());
+    assert!(game.objects.get(&forest_id).unwrap().is_tapped());
+    assert!(game.mana_pools.get(&player1_id).unwrap().is_empty());
+
+    // The ability waits on the stack, and its controller keeps priority.
+    let stack = game.zones.get(&stack_id).unwrap().order.clone();
+    assert_eq!(stack.len(), 1);
+
+    match game.objects.get(&stack[0]).unwrap().details {
+        ObjectDetails::Ability { source_id: ability_source_id, .. } => assert_eq!(ability_source_id, source_id),
+        _ => panic!("Expected an ability on the stack"),
+    }
+
+    assert_eq!(game.priority_player, Some(player1_id));
+
+    pass_priority_round(&mut game);
+
+    // Once resolved, the ability is gone and its controller drew a card.
+    assert!(game.stack_is_empty());
+    assert!(!game.objects.contains_key(&stack[0]));
+
+    let hand_size = game.objects.values()
+        .filter(|object| object.zone == hand_id)
+        .count();
+
+    assert_eq!(hand_size, 1);
+}
+
+#[test]
+fn test_fail_unpayable_ability() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let source_id = create_forest(&mut game, battlefield_id, player1_id);
+    let ability_id = add_ability(&mut game, source_id, draw_ability());
+
+    // The source can't tap for mana to pay for its own {T} ability.
+    add_ability(&mut game, source_id, Ability::tap_for_mana(ManaType::Green));
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateAbility {
+        object_id: source_id,
+        ability_id,
+    }).unwrap();
+
+    // The ability waits on the stack to be paid for, but nothing can pay it.
+    assert!(matches!(game.current_status, GameStatus::NeedsPlayerChoice(PlayerChoice::PayMana { .. })));
+    assert!(game.do_player_action(player1_id, &PlayerAction::FinishPayment).is_err());
+    assert!(!game.objects.get(&source_id).unwrap().is_tapped());
+
+    game.do_player_action(player1_id, &PlayerAction::CancelPayment).unwrap();
+
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+    assert!(game.stack_is_empty());
+}
+
+#[test]
+fn test_mana_ability_skips_stack() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+    let ability_id = add_ability(&mut game, forest_id, Ability::tap_for_mana(ManaType::Green));
+
+    // Mana abilities have their own action.
+    assert!(game.do_player_action(player1_id, &PlayerAction::ActivateAbility {
+        object_id: forest_id,
+        ability_id,
+    }).is_err());
+
+    assert!(game.do_player_action(player1_id, &PlayerAction::ActivateManaAbility {
+        object_id: forest_id,
+        ability_id,
+        mana_type: ManaType::Blue,
+    }).is_err());
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateManaAbility {
+        object_id: forest_id,
+        ability_id,
+        mana_type: ManaType::Green,
+    }).unwrap();
+
+    assert!(game.stack_is_empty());
+    assert_eq!(game.mana_pools.get(&player1_id).unwrap().get(ManaType::Green), 1);
+}
+
+#[test]
+fn test_mana_abilities_while_paying_without_priority() {
+    let mut game = new_two_player_game();
+
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let forest_id = create_forest(&mut game, battlefield_id, player2_id);
+    let ability_id = add_ability(&mut game, forest_id, Ability::tap_for_mana(ManaType::Green));
+
+    // Without priority, player 2 can't just activate their Forest...
+    assert!(game.do_player_action(player2_id, &PlayerAction::ActivateManaAbility {
+        object_id: forest_id,
+        ability_id,
+        mana_type: ManaType::Green,
+    }).is_err());
+
+    // ...but they can while paying a cost.
+    game.auto_pay_mana_cost(player2_id, &ManaCost::parse("{G}").unwrap(), 0).unwrap();
+
+    assert!(game.objects.get(&forest_id).unwrap().is_tapped());
+}
+
+#[test]
+fn test_pay_for_spell_with_mana_abilities() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+    let hand_id = get_hand_id(&game, player1_id);
+    let stack_id = get_stack_id(&game);
+
+    let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+    add_ability(&mut game, forest_id, Ability::tap_for_mana(ManaType::Green));
+
+    // `{T}, Sacrifice this artifact: Add one mana of any color.` Paying
+    // automatically never sacrifices anything.
+    let treasure_id = create_object(&mut game, battlefield_id, player1_id, Characteristics {
+        name: "Treasure".to_string(),
+        card_types: vec![CardType::Artifact],
+        subtypes: vec!["Treasure".to_string()],
+        ..Characteristics::default()
+    });
+    let treasure_ability_id = add_ability(&mut game, treasure_id, Ability::Mana(ManaAbility {
+        cost: ActivationCost::parse("{T}, Sacrifice this artifact").unwrap(),
+        produces: vec![ManaType::White, ManaType::Blue, ManaType::Black, ManaType::Red, ManaType::Green],
+    }));
+
+    let other_forest_id = create_forest(&mut game, battlefield_id, player2_id);
+    let other_ability_id = add_ability(&mut game, other_forest_id, Ability::tap_for_mana(ManaType::Green));
+
+    let bears_id = create_object(&mut game, hand_id, player1_id, grizzly_bears());
+
+    game.do_player_action(player1_id, &PlayerAction::CastSpell {
+        object_id: bears_id,
+        face: None,
+    }).unwrap();
+
+    // The Forest alone can't pay for it, so the spell waits on the stack.
+    let spell_id = game.zones[&stack_id].order[0];
+
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerChoice(PlayerChoice::PayMana {
+        player_id: player1_id,
+        object_id: spell_id,
+        cost: ActivationCost {
+            mana: Some(ManaCost::parse("{1}{G}").unwrap()),
+            ..ActivationCost::default()
+        },
+        cast_from: Some(hand_id),
+    }));
+
+    assert!(game.do_player_action(player1_id, &PlayerAction::FinishPayment).is_err());
+    assert!(game.do_player_action(player1_id, &PlayerAction::PassPriority).is_err());
+    assert!(game.do_player_action(player2_id, &PlayerAction::ActivateManaAbility {
+        object_id: other_forest_id,
+        ability_id: other_ability_id,
+        mana_type: ManaType::Green,
+    }).is_err());
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateManaAbility {
+        object_id: treasure_id,
+        ability_id: treasure_ability_id,
+        mana_type: ManaType::Red,
+    }).unwrap();
+
+    assert!(!game.objects.contains_key(&treasure_id));
+
+    // The rest is paid automatically.
+    game.do_player_action(player1_id, &PlayerAction::FinishPayment).unwrap();
+
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+    assert_eq!(game.priority_player, Some(player1_id));
+    assert!(game.objects[&forest_id].is_tapped());
+    assert_eq!(game.mana_pools[&player1_id].get(ManaType::Red), 0);
+
+    pass_priority_round(&mut game);
+    assert!(game.stack_is_empty());
+
+    let bears_resolved = game.objects.values()
+        .any(|object| object.zone == battlefield_id && game.characteristics(object.id).unwrap().name == "Grizzly Bears");
+
+    assert!(bears_resolved);
+}
+
+#[test]
+fn test_pay_for_ability_with_mana_abilities() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let library_id = get_library_id(&game, player1_id);
+    let hand_id = get_hand_id(&game, player1_id);
+    let stack_id = get_stack_id(&game);
+
+    create_forest(&mut game, library_id, player1_id);
+
+    let source_id = create_forest(&mut game, battlefield_id, player1_id);
+    let ability_id = add_ability(&mut game, source_id, draw_ability());
+
+    let treasure_id = create_object(&mut game, battlefield_id, player1_id, Characteristics {
+        name: "Treasure".to_string(),
+        card_types: vec![CardType::Artifact],
+        subtypes: vec!["Treasure".to_string()],
+        ..Characteristics::default()
+    });
+    let treasure_ability_id = add_ability(&mut game, treasure_id, Ability::Mana(ManaAbility {
+        cost: ActivationCost::parse("{T}, Sacrifice this artifact").unwrap(),
+        produces: vec![ManaType::White, ManaType::Blue, ManaType::Black, ManaType::Red, ManaType::Green],
+    }));
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateAbility {
+        object_id: source_id,
+        ability_id,
+    }).unwrap();
+
+    // Only the Treasure could pay, so the ability waits on the stack with
+    // none of its cost paid.
+    let ability_object_id = game.zones[&stack_id].order[0];
+
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerChoice(PlayerChoice::PayMana {
+        player_id: player1_id,
+        object_id: ability_object_id,
+        cost: ActivationCost {
+            mana: Some(ManaCost::parse("{1}").unwrap()),
+            tap: true,
+            ..ActivationCost::default()
+        },
+        cast_from: None,
+    }));
+    assert!(!game.objects[&source_id].is_tapped());
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateManaAbility {
+        object_id: treasure_id,
+        ability_id: treasure_ability_id,
+        mana_type: ManaType::Black,
+    }).unwrap();
+
+    game.do_player_action(player1_id, &PlayerAction::FinishPayment).unwrap();
+
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+    assert_eq!(game.priority_player, Some(player1_id));
+    assert!(game.objects[&source_id].is_tapped());
+    assert!(!game.objects.contains_key(&treasure_id));
+    assert!(game.mana_pools[&player1_id].is_empty());
+
+    pass_priority_round(&mut game);
+    assert!(game.stack_is_empty());
+
+    let hand_size = game.objects.values()
+        .filter(|object| object.zone == hand_id)
+        .count();
+
+    assert_eq!(hand_size, 1);
+}
+
+#[test]
+fn test_cancel_payment() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let hand_id = get_hand_id(&game, player1_id);
+
+    let bears_id = create_object(&mut game, hand_id, player1_id, grizzly_bears());
+
+    game.do_player_action(player1_id, &PlayerAction::CastSpell {
+        object_id: bears_id,
+        face: None,
+    }).unwrap();
+
+    game.do_player_action(player1_id, &PlayerAction::CancelPayment).unwrap();
+
+    // The spell goes back where it came from.
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+    assert!(game.stack_is_empty());
+
+    let in_hand = game.objects.values()
+        .filter(|object| object.zone == hand_id)
+        .count();
+
+    assert_eq!(in_hand, 1);
+    assert!(game.do_player_action(player1_id, &PlayerAction::CancelPayment).is_err());
+}
+
+#[test]
+fn test_cancel_payment_undoes_mana_abilities() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let hand_id = get_hand_id(&game, player1_id);
+
+    let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+    let ability_id = add_ability(&mut game, forest_id, Ability::tap_for_mana(ManaType::Green));
+
+    let bears_id = create_object(&mut game, hand_id, player1_id, grizzly_bears());
+
+    game.do_player_action(player1_id, &PlayerAction::CastSpell {
+        object_id: bears_id,
+        face: None,
+    }).unwrap();
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateManaAbility {
+        object_id: forest_id,
+        ability_id,
+        mana_type: ManaType::Green,
+    }).unwrap();
+
+    assert!(game.objects[&forest_id].is_tapped());
+    assert_eq!(game.mana_pools[&player1_id].get(ManaType::Green), 1);
+
+    game.do_player_action(player1_id, &PlayerAction::CancelPayment).unwrap();
+
+    // It's as if the Forest was never tapped.
+    assert!(!game.objects[&forest_id].is_tapped());
+    assert!(game.mana_pools[&player1_id].is_empty());
+    assert!(game.payment_activations.is_empty());
+}
+
+#[test]
+fn test_damage_each_opponent() {
+    let mut game = new_game(3);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let player3_id = game.player_turn_order[2];
+
+    // {T}: This deals 1 damage to each opponent.
+    let source_id = add_creature(&mut game, player1_id, "Kami of the Crescent Moon", 1, 3);
+    let ability_id = add_ability(&mut game, source_id, Ability::Activated(ActivatedAbility {
+        cost: ActivationCost {
+            tap: true,
+            ..ActivationCost::default()
+        },
+        effects: vec![Effect::DamageEachOpponent { amount: 1 }],
+    }));
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateAbility {
+        object_id: source_id,
+        ability_id,
+    }).unwrap();
+
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.players[&player1_id].life, 20);
+    assert_eq!(game.players[&player2_id].life, 19);
+    assert_eq!(game.players[&player3_id].life, 19);
+}
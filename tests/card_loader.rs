@@ -0,0 +1,467 @@
+extern crate corrosion;
+
+use std::path::Path;
+
+use corrosion::{
+    Ability,
+    CardLoadErrorKind,
+    CardRegistry,
+    CardType,
+    Color,
+    ContinuousEffectKind,
+    Effect,
+    GamePhase,
+    ManaCostParseError,
+    ManaType,
+    ObjectFilter,
+    ReplacementEffectKind,
+    StatValue,
+    Supertype,
+    Trigger,
+    load_card_file,
+    load_cards,
+};
+
+const CARDS: &str = r#"
+[[card]]
+name = "Llanowar Elves"
+mana_cost = "{G}"
+types = ["Creature"]
+subtypes = ["Elf", "Druid"]
+rules_text = "{T}: Add {G}."
+power = 1
+toughness = 1
+
+[[card.abilities]]
+kind = "mana"
+cost = "{T}"
+produces = ["G"]
+
+[[card]]
+name = "Tarmogoyf"
+mana_cost = "{1}{G}"
+types = ["Creature"]
+power = "*"
+toughness = "1+*"
+
+[[card]]
+name = "Arcane Signet"
+mana_cost = "{2}"
+types = ["Artifact"]
+
+[[card.abilities]]
+kind = "activated"
+cost = "{1}, {T}"
+effects = [{ kind = "draw_cards", count = 2 }]
+
+[[card]]
+name = "Divination"
+mana_cost = "{2}{U}"
+types = ["Sorcery"]
+
+[[card.abilities]]
+kind = "spell"
+effects = [{ kind = "draw_cards", count = 2 }]
+"#;
+
+#[test]
+fn test_load_cards() {
+    let mut registry = CardRegistry::new();
+
+    let ids = load_cards(&mut registry, "cards.toml", CARDS).unwrap();
+
+    assert_eq!(ids.len(), 4);
+    assert_eq!(registry.len(), 4);
+
+    let elves = &registry.find_by_name("Llanowar Elves").unwrap().characteristics;
+
+    assert_eq!(elves.colors(), vec![Color::Green]);
+    assert!(elves.has_card_type(CardType::Creature));
+    assert!(elves.has_subtype("Druid"));
+    assert_eq!(elves.power, Some(StatValue::Fixed(1)));
+    assert_eq!(elves.abilities.len(), 1);
+
+    match elves.abilities.values().next().unwrap() {
+        Ability::Mana(ability) => {
+            assert!(ability.cost.tap);
+            assert_eq!(ability.produces, vec![ManaType::Green]);
+        },
+        _ => panic!("Expected a mana ability"),
+    }
+
+    let tarmogoyf = &registry.find_by_name("Tarmogoyf").unwrap().characteristics;
+
+    assert_eq!(tarmogoyf.power, Some(StatValue::Variable(0)));
+    assert_eq!(tarmogoyf.toughness, Some(StatValue::Variable(1)));
+
+    let signet = &registry.find_by_name("Arcane Signet").unwrap().characteristics;
+
+    match signet.abilities.values().next().unwrap() {
+        Ability::Activated(ability) => {
+            assert!(ability.cost.tap);
+            assert_eq!(ability.cost.mana.as_ref().unwrap().mana_value(), 1);
+            assert_eq!(ability.effects, vec![Effect::DrawCards { count: 2 }]);
+        },
+        _ => panic!("Expected an activated ability"),
+    }
+
+    let divination = &registry.find_by_name("Divination").unwrap().characteristics;
+
+    assert_eq!(divination.abilities.values().next(), Some(&Ability::Spell(vec![Effect::DrawCards { count: 2 }])));
+}
+
+#[test]
+fn test_load_basic_lands() {
+    let mut registry = CardRegistry::new();
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("cards/basic_lands.toml");
+    load_card_file(&mut registry, &path).unwrap();
+
+    assert_eq!(registry.len(), 5);
+
+    let forest = &registry.find_by_name("Forest").unwrap().characteristics;
+
+    assert!(forest.has_supertype(Supertype::Basic));
+    assert!(forest.has_card_type(CardType::Land));
+    assert_eq!(forest.abilities.values().next(), Some(&Ability::tap_for_mana(ManaType::Green)));
+}
+
+#[test]
+fn test_unknown_keyword() {
+    let mut registry = CardRegistry::new();
+
+    let source = r#"
+[[card]]
+name = "Grizzly Bears"
+types = ["Creature"]
+
+[[card]]
+name = "Mystery"
+types = ["Bear"]
+"#;
+
+    let error = load_cards(&mut registry, "bears.toml", source).unwrap_err();
+
+    assert_eq!(error.kind, CardLoadErrorKind::UnknownKeyword("Bear".to_string()));
+    assert_eq!(error.line, 8);
+    assert_eq!(error.to_string(), "bears.toml:8:10: unknown keyword `Bear`");
+
+    // Nothing gets loaded from a file with errors.
+    assert!(registry.is_empty());
+
+    let source = r#"
+[[card]]
+name = "Grizzly Bears"
+
+[[card.abilities]]
+kind = "channel"
+"#;
+
+    let error = load_cards(&mut registry, "bears.toml", source).unwrap_err();
+
+    assert_eq!(error.kind, CardLoadErrorKind::UnknownKeyword("channel".to_string()));
+    assert_eq!(error.line, 6);
+}
+
+#[test]
+fn test_load_static_abilities() {
+    let mut registry = CardRegistry::new();
+
+    let source = r#"
+[[card]]
+name = "Glorious Anthem"
+mana_cost = "{1}{W}{W}"
+types = ["Enchantment"]
+
+[[card.abilities]]
+kind = "static"
+affected = { types = ["Creature"], you_control = true }
+effects = [{ kind = "modify_power_toughness", power = 1, toughness = 1 }]
+
+[[card]]
+name = "Blood Moon"
+mana_cost = "{2}{R}"
+types = ["Enchantment"]
+
+[[card.abilities]]
+kind = "static"
+affected = { types = ["Land"], excluded_supertypes = ["Basic"] }
+effects = [{ kind = "set_basic_land_type", subtype = "Mountain" }]
+
+[[card]]
+name = "Molimo, Maro-Sorcerer"
+mana_cost = "{4}{G}{G}{G}"
+types = ["Creature"]
+power = "*"
+toughness = "*"
+
+[[card.abilities]]
+kind = "static"
+effects = [{ kind = "count_power_toughness", counted = { types = ["Land"], you_control = true } }]
+"#;
+
+    load_cards(&mut registry, "statics.toml", source).unwrap();
+
+    let anthem = &registry.find_by_name("Glorious Anthem").unwrap().characteristics;
+
+    match anthem.abilities.values().next().unwrap() {
+        Ability::Static(ability) => {
+            assert_eq!(ability.affected, ObjectFilter {
+                card_types: vec![CardType::Creature],
+                you_control: true,
+                ..ObjectFilter::default()
+            });
+            assert_eq!(ability.effects, vec![ContinuousEffectKind::ModifyPowerToughness { power: 1, toughness: 1 }]);
+        },
+        _ => panic!("Expected a static ability"),
+    }
+
+    let blood_moon = &registry.find_by_name("Blood Moon").unwrap().characteristics;
+
+    match blood_moon.abilities.values().next().unwrap() {
+        Ability::Static(ability) => {
+            assert_eq!(ability.affected.excluded_supertypes, vec![Supertype::Basic]);
+
+            match ability.effects[0] {
+                ContinuousEffectKind::SetBasicLandType { ref subtype, mana_type, .. } => {
+                    assert_eq!(subtype, "Mountain");
+                    assert_eq!(mana_type, ManaType::Red);
+                },
+                ref other => panic!("Expected a basic land type effect, got {:?}", other),
+            }
+        },
+        _ => panic!("Expected a static ability"),
+    }
+
+    let molimo = &registry.find_by_name("Molimo, Maro-Sorcerer").unwrap().characteristics;
+
+    match molimo.abilities.values().next().unwrap() {
+        Ability::Static(ability) => {
+            assert_eq!(ability.effects, vec![ContinuousEffectKind::CountPowerToughness {
+                counted: ObjectFilter {
+                    card_types: vec![CardType::Land],
+                    you_control: true,
+                    ..ObjectFilter::default()
+                },
+            }]);
+        },
+        _ => panic!("Expected a static ability"),
+    }
+}
+
+#[test]
+fn test_load_triggered_abilities() {
+    let mut registry = CardRegistry::new();
+
+    let source = r#"
+[[card]]
+name = "Guttersnipe"
+mana_cost = "{2}{R}"
+types = ["Creature"]
+power = 2
+toughness = 2
+
+[[card.abilities]]
+kind = "triggered"
+trigger = "you_cast_spell"
+effects = [{ kind = "damage_each_opponent", amount = 2 }]
+
+[[card]]
+name = "Phyrexian Arena"
+mana_cost = "{1}{B}{B}"
+types = ["Enchantment"]
+
+[[card.abilities]]
+kind = "triggered"
+trigger = "beginning_of_your_step"
+step = "upkeep"
+effects = [{ kind = "draw_cards", count = 1 }]
+"#;
+
+    load_cards(&mut registry, "triggers.toml", source).unwrap();
+
+    let guttersnipe = &registry.find_by_name("Guttersnipe").unwrap().characteristics;
+
+    match guttersnipe.abilities.values().next().unwrap() {
+        Ability::Triggered(ability) => {
+            assert_eq!(ability.trigger, Trigger::YouCastSpell);
+            assert_eq!(ability.effects, vec![Effect::DamageEachOpponent { amount: 2 }]);
+        },
+        _ => panic!("Expected a triggered ability"),
+    }
+
+    let arena = &registry.find_by_name("Phyrexian Arena").unwrap().characteristics;
+
+    match arena.abilities.values().next().unwrap() {
+        Ability::Triggered(ability) => {
+            assert_eq!(ability.trigger, Trigger::BeginningOfYourStep(GamePhase::Upkeep));
+        },
+        _ => panic!("Expected a triggered ability"),
+    }
+
+    // A step trigger has to say which step.
+    let source = r#"
+[[card]]
+name = "Phyrexian Arena"
+
+[[card.abilities]]
+kind = "triggered"
+trigger = "beginning_of_your_step"
+effects = [{ kind = "draw_cards", count = 1 }]
+"#;
+
+    let error = load_cards(&mut registry, "triggers.toml", source).unwrap_err();
+
+    assert_eq!(error.kind, CardLoadErrorKind::Invalid("beginning_of_your_step needs a step"));
+    assert_eq!(error.line, 7);
+}
+
+#[test]
+fn test_load_replacement_abilities() {
+    let mut registry = CardRegistry::new();
+
+    let source = r#"
+[[card]]
+name = "Selesnya Guildgate"
+types = ["Land"]
+subtypes = ["Gate"]
+
+[[card.abilities]]
+kind = "replacement"
+replacement = "enters_tapped"
+
+[[card]]
+name = "Rest in Peace"
+mana_cost = "{1}{W}"
+types = ["Enchantment"]
+
+[[card.abilities]]
+kind = "replacement"
+replacement = "exile_instead"
+affected = {}
+"#;
+
+    load_cards(&mut registry, "replacements.toml", source).unwrap();
+
+    let guildgate = &registry.find_by_name("Selesnya Guildgate").unwrap().characteristics;
+
+    assert_eq!(guildgate.abilities.values().next(), Some(&Ability::Replacement(ReplacementEffectKind::EntersTapped)));
+
+    let rest_in_peace = &registry.find_by_name("Rest in Peace").unwrap().characteristics;
+
+    assert_eq!(rest_in_peace.abilities.values().next(), Some(&Ability::Replacement(ReplacementEffectKind::ExileInstead {
+        affected: ObjectFilter::default(),
+    })));
+
+    // Exiling instead has to say what it affects.
+    let source = r#"
+[[card]]
+name = "Rest in Peace"
+
+[[card.abilities]]
+kind = "replacement"
+replacement = "exile_instead"
+"#;
+
+    let error = load_cards(&mut registry, "replacements.toml", source).unwrap_err();
+
+    assert_eq!(error.kind, CardLoadErrorKind::Invalid("this replacement needs to say what it affects"));
+    assert_eq!(error.line, 7);
+}
+
+#[test]
+fn test_load_subtracted_stats() {
+    let mut registry = CardRegistry::new();
+
+    let source = r#"
+[[card]]
+name = "Shrunken Goyf"
+types = ["Creature"]
+power = "*-1"
+toughness = "-1+*"
+"#;
+
+    load_cards(&mut registry, "stats.toml", source).unwrap();
+
+    let card = &registry.find_by_name("Shrunken Goyf").unwrap().characteristics;
+
+    assert_eq!(card.power, Some(StatValue::Variable(-1)));
+    assert_eq!(card.toughness, Some(StatValue::Variable(-1)));
+
+    // Subtracting the variable part isn't something cards do.
+    let source = r#"
+[[card]]
+name = "Backwards Goyf"
+types = ["Creature"]
+power = "1-*"
+"#;
+
+    let error = load_cards(&mut registry, "stats.toml", source).unwrap_err();
+
+    assert_eq!(error.kind, CardLoadErrorKind::UnknownKeyword("1-*".to_string()));
+}
+
+#[test]
+fn test_unknown_key() {
+    let mut registry = CardRegistry::new();
+
+    let source = r#"
+[[card]]
+name = "Grizzly Bears"
+flavor = "Don't try to outrun one."
+"#;
+
+    let error = load_cards(&mut registry, "bears.toml", source).unwrap_err();
+
+    match error.kind {
+        CardLoadErrorKind::Format(_) => {},
+        _ => panic!("Expected a format error, got {:?}", error.kind),
+    }
+
+    assert_eq!(error.line, 4);
+}
+
+#[test]
+fn test_malformed_cost() {
+    let mut registry = CardRegistry::new();
+
+    let source = r#"
+[[card]]
+name = "Grizzly Bears"
+mana_cost = "{1}{Q}"
+"#;
+
+    let error = load_cards(&mut registry, "bears.toml", source).unwrap_err();
+
+    assert_eq!(error.kind, CardLoadErrorKind::MalformedCost {
+        cost: "{1}{Q}".to_string(),
+        error: ManaCostParseError::UnknownSymbol("Q".to_string()),
+    });
+    assert_eq!(error.line, 4);
+
+    let source = r#"
+[[card]]
+name = "Signet"
+
+[[card.abilities]]
+kind = "mana"
+cost = "{T}, {T}"
+produces = ["C"]
+"#;
+
+    let error = load_cards(&mut registry, "signet.toml", source).unwrap_err();
+
+    assert_eq!(error.line, 7);
+}
+
+#[test]
+fn test_missing_file() {
+    let mut registry = CardRegistry::new();
+
+    let error = load_card_file(&mut registry, Path::new("no/such/cards.toml")).unwrap_err();
+
+    match error.kind {
+        CardLoadErrorKind::Io(_) => {},
+        _ => panic!("Expected an IO error, got {:?}", error.kind),
+    }
+}
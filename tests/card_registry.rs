@@ -0,0 +1,79 @@
+extern crate corrosion;
+
+use std::sync::Arc;
+
+use corrosion::{
+    CardRegistry,
+    CardType,
+    Characteristics,
+    StatValue,
+};
+
+use corrosion::utility::*;
+
+fn llanowar_elves() -> Characteristics {
+    Characteristics {
+        name: "Llanowar Elves".to_string(),
+        card_types: vec![CardType::Creature],
+        subtypes: vec!["Elf".to_string(), "Druid".to_string()],
+        rules_text: "{T}: Add {G}.".to_string(),
+        power: Some(StatValue::Fixed(1)),
+        toughness: Some(StatValue::Fixed(1)),
+        ..Characteristics::default()
+    }
+}
+
+#[test]
+fn test_registry_lookup() {
+    let mut registry = CardRegistry::new();
+
+    assert!(registry.is_empty());
+
+    let elves_id = registry.add(llanowar_elves());
+    let forest_id = registry.add(forest());
+
+    assert_eq!(registry.len(), 2);
+    assert_eq!(registry.get(elves_id).unwrap().characteristics.name, "Llanowar Elves");
+    assert_eq!(registry.find_by_name("Forest").unwrap().id, forest_id);
+    assert!(registry.find_by_name("Black Lotus").is_none());
+}
+
+#[test]
+fn test_cards_share_definition() {
+    let mut registry = CardRegistry::new();
+    let elves_id = registry.add(llanowar_elves());
+
+    let mut game = new_two_player_game();
+    game.registry = Arc::new(registry);
+
+    let player1_id = game.player_turn_order[0];
+    let library_id = get_library_id(&game, player1_id);
+
+    let first_id = game.create_card(elves_id, library_id, player1_id);
+    let second_id = game.create_card(elves_id, library_id, player1_id);
+
+    assert_ne!(first_id, second_id);
+    assert_eq!(game.objects.get(&first_id).unwrap().definition_id(), Some(elves_id));
+    assert_eq!(game.objects.get(&second_id).unwrap().definition_id(), Some(elves_id));
+    assert_eq!(game.characteristics(second_id).unwrap().power, Some(StatValue::Fixed(1)));
+
+    // Cards keep their definition as they change zones.
+    let hand_id = get_hand_id(&game, player1_id);
+    let moved_id = game.move_object(first_id, hand_id).unwrap();
+
+    assert_eq!(game.characteristics(moved_id).unwrap().name, "Llanowar Elves");
+}
+
+#[test]
+fn test_clones_share_registry() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let hand_id = get_hand_id(&game, player1_id);
+
+    create_forest(&mut game, hand_id, player1_id);
+
+    let view = game.view_as_player(player1_id);
+
+    assert!(Arc::ptr_eq(&game.registry, &view.registry));
+}
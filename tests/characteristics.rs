@@ -0,0 +1,179 @@
+extern crate corrosion;
+
+use corrosion::{
+    CardType,
+    Characteristics,
+    Color,
+    Game,
+    Id,
+    ManaCost,
+    PlayerAction,
+    StatValue,
+    Supertype,
+};
+
+use corrosion::utility::*;
+
+fn divination() -> Characteristics {
+    Characteristics {
+        name: "Divination".to_string(),
+        mana_cost: Some(ManaCost::parse("{2}{U}").unwrap()),
+        card_types: vec![CardType::Sorcery],
+        rules_text: "Draw two cards.".to_string(),
+        ..Characteristics::default()
+    }
+}
+
+fn characteristics(game: &Game, object_id: Id) -> &Characteristics {
+    game.characteristics(object_id).unwrap()
+}
+
+#[test]
+fn test_colors() {
+    assert_eq!(grizzly_bears().colors(), vec![Color::Green]);
+    assert_eq!(forest().colors(), vec![]);
+
+    let hybrid = Characteristics {
+        mana_cost: Some(ManaCost::parse("{2}{G/U}{2/W}").unwrap()),
+        ..Characteristics::default()
+    };
+
+    assert_eq!(hybrid.colors(), vec![Color::White, Color::Blue, Color::Green]);
+
+    // Cards without a mana cost can get colors from a color indicator.
+    let indicated = Characteristics {
+        color_indicator: vec![Color::Red, Color::Black],
+        ..Characteristics::default()
+    };
+
+    assert_eq!(indicated.colors(), vec![Color::Black, Color::Red]);
+}
+
+#[test]
+fn test_types() {
+    let forest = forest();
+
+    assert!(forest.has_supertype(Supertype::Basic));
+    assert!(forest.has_card_type(CardType::Land));
+    assert!(forest.has_subtype("Forest"));
+    assert!(forest.is_permanent());
+
+    assert!(grizzly_bears().is_permanent());
+    assert!(!divination().is_permanent());
+}
+
+#[test]
+fn test_variable_stats() {
+    let tarmogoyf = Characteristics {
+        power: Some(StatValue::Variable(0)),
+        toughness: Some(StatValue::Variable(1)),
+        ..Characteristics::default()
+    };
+
+    assert_eq!(tarmogoyf.power.unwrap().value(), 0);
+    assert_eq!(tarmogoyf.toughness.unwrap().value(), 1);
+}
+
+#[test]
+fn test_creature_spell_has_power() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let stack_id = get_stack_id(&game);
+    let battlefield_id = get_battlefield_id(&game);
+
+    // Like Essence Backlash, we should be able to see a creature spell's power
+    // while it's on the stack.
+    let spell_id = create_object(&mut game, stack_id, player1_id, grizzly_bears());
+
+    assert_eq!(characteristics(&game, spell_id).power, Some(StatValue::Fixed(2)));
+
+    pass_priority_round(&mut game);
+
+    let bears_id = game.objects.values()
+        .find(|object| object.zone == battlefield_id)
+        .unwrap()
+        .id;
+
+    assert_eq!(characteristics(&game, bears_id).name, "Grizzly Bears");
+    assert_eq!(characteristics(&game, bears_id).toughness, Some(StatValue::Fixed(2)));
+}
+
+#[test]
+fn test_nonpermanent_spell_goes_to_graveyard() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let stack_id = get_stack_id(&game);
+    let graveyard_id = get_graveyard_id(&game, player1_id);
+
+    create_object(&mut game, stack_id, player1_id, divination());
+
+    pass_priority_round(&mut game);
+
+    assert!(game.stack_is_empty());
+    assert_eq!(game.zones.get(&graveyard_id).unwrap().order.len(), 1);
+}
+
+#[test]
+fn test_only_lands_can_be_played() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let hand_id = get_hand_id(&game, player1_id);
+
+    let bears_id = create_object(&mut game, hand_id, player1_id, grizzly_bears());
+
+    let result = game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: bears_id,
+        face: None,
+    });
+
+    assert!(result.is_err());
+    assert_eq!(game.objects.get(&bears_id).unwrap().zone, hand_id);
+}
+
+#[test]
+fn test_viewed_stats_are_fixed() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let battle_id = create_object(&mut game, battlefield_id, player1_id, Characteristics {
+        name: "Test Battle".to_string(),
+        card_types: vec![CardType::Battle],
+        defense: Some(StatValue::Variable(3)),
+        ..Characteristics::default()
+    });
+
+    let view = game.view_object(battle_id).unwrap();
+    assert_eq!(view.characteristics.unwrap().defense, Some(StatValue::Fixed(3)));
+}
+
+#[test]
+fn test_face_down() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let bears_id = create_object(&mut game, battlefield_id, player1_id, grizzly_bears());
+
+    // Face-down permanents are nameless 2/2 creatures with nothing else.
+    assert!(game.turn_face_down(bears_id));
+
+    let view = game.view_object(bears_id).unwrap();
+    let face_down = view.characteristics.unwrap();
+
+    assert_eq!(face_down, Characteristics::face_down());
+    assert_eq!(face_down.name, "");
+    assert_eq!(face_down.mana_cost, None);
+    assert!(face_down.subtypes.is_empty());
+
+    // Turning it face up gives it its printed characteristics back.
+    assert!(game.turn_face_up(bears_id));
+
+    let view = game.view_object(bears_id).unwrap();
+    assert_eq!(view.characteristics.unwrap().name, "Grizzly Bears");
+}
@@ -0,0 +1,177 @@
+extern crate corrosion;
+
+use corrosion::{
+    DamageFilter,
+    Duration,
+    GamePhase,
+    GameStatus,
+    PlayerAction,
+    PlayerChoice,
+    ReplacementEffectKind,
+};
+
+use corrosion::utility::*;
+
+#[test]
+fn test_unblocked_attacker() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    let bears_id = add_creature(&mut game, player1_id, "Grizzly Bears", 2, 2);
+
+    // The active player is asked to declare attackers as the step begins.
+    pass_priority_until(&mut game, GamePhase::DeclareAttackers);
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerChoice(PlayerChoice::DeclareAttackers {
+        player_id: player1_id,
+    }));
+
+    game.do_player_action(player1_id, &PlayerAction::DeclareAttackers {
+        attackers: vec![(bears_id, player2_id)],
+    }).unwrap();
+
+    // Attacking taps the creature, and the step carries on as usual.
+    assert!(game.objects[&bears_id].is_tapped());
+    assert_eq!(game.attacks[&bears_id].defending_player, player2_id);
+    assert_eq!(game.priority_player, Some(player1_id));
+
+    // Player 2 has nothing to block with.
+    pass_priority_round(&mut game);
+    assert_eq!(game.current_phase, GamePhase::DeclareBlockers);
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+
+    pass_priority_round(&mut game);
+    assert_eq!(game.current_phase, GamePhase::CombatDamage);
+    assert_eq!(game.players[&player2_id].life, 18);
+
+    pass_priority_until(&mut game, GamePhase::PostcombatMain);
+    assert!(game.attacks.is_empty());
+}
+
+#[test]
+fn test_blocked_attacker() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let attacker_id = add_creature(&mut game, player1_id, "Hill Giant", 3, 3);
+    let wall_id = add_creature(&mut game, player2_id, "Wall of Wood", 0, 3);
+    let bears_id = add_creature(&mut game, player2_id, "Grizzly Bears", 2, 2);
+
+    pass_priority_until(&mut game, GamePhase::DeclareAttackers);
+    game.do_player_action(player1_id, &PlayerAction::DeclareAttackers {
+        attackers: vec![(attacker_id, player2_id)],
+    }).unwrap();
+
+    pass_priority_round(&mut game);
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerChoice(PlayerChoice::DeclareBlockers {
+        player_id: player2_id,
+    }));
+
+    game.do_player_action(player2_id, &PlayerAction::DeclareBlockers {
+        blockers: vec![(bears_id, attacker_id), (wall_id, attacker_id)],
+    }).unwrap();
+
+    assert!(game.attacks[&attacker_id].blocked);
+    assert_eq!(game.priority_player, Some(player1_id));
+
+    // The attacker assigns lethal damage to the first blocker and the rest to
+    // the second, and the blockers deal damage back. None reaches player 2.
+    pass_priority_round(&mut game);
+    assert_eq!(game.current_phase, GamePhase::CombatDamage);
+
+    assert!(!game.objects.contains_key(&bears_id));
+    assert_eq!(game.objects[&wall_id].damage, 1);
+    assert_eq!(game.objects[&attacker_id].damage, 2);
+    assert_eq!(game.players[&player2_id].life, 20);
+
+    // The bears died, so they're no longer blocking.
+    assert_eq!(game.attacks[&attacker_id].blockers, vec![wall_id]);
+
+    let on_battlefield = game.objects.values()
+        .filter(|object| object.zone == battlefield_id)
+        .count();
+
+    assert_eq!(on_battlefield, 2);
+}
+
+#[test]
+fn test_combat_damage_prevented() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    let bears_id = add_creature(&mut game, player1_id, "Grizzly Bears", 2, 2);
+
+    // Prevent all combat damage that would be dealt this turn.
+    game.add_replacement_effect(player2_id, Duration::UntilEndOfTurn, ReplacementEffectKind::PreventDamage {
+        damage: DamageFilter {
+            combat_only: true,
+            ..DamageFilter::default()
+        },
+        amount: None,
+    });
+
+    pass_priority_until(&mut game, GamePhase::DeclareAttackers);
+    game.do_player_action(player1_id, &PlayerAction::DeclareAttackers {
+        attackers: vec![(bears_id, player2_id)],
+    }).unwrap();
+
+    pass_priority_until(&mut game, GamePhase::CombatDamage);
+    assert_eq!(game.players[&player2_id].life, 20);
+}
+
+#[test]
+fn test_illegal_declarations() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    let bears_id = add_creature(&mut game, player1_id, "Grizzly Bears", 2, 2);
+    let tapped_id = add_creature(&mut game, player1_id, "Tapped Bears", 2, 2);
+    let blocker_id = add_creature(&mut game, player2_id, "Wall of Wood", 0, 3);
+
+    game.objects.get_mut(&tapped_id).unwrap().set_tapped(true);
+
+    // Declarations are only allowed when the game asks for them.
+    assert!(game.do_player_action(player1_id, &PlayerAction::DeclareAttackers {
+        attackers: vec![(bears_id, player2_id)],
+    }).is_err());
+
+    pass_priority_until(&mut game, GamePhase::DeclareAttackers);
+
+    let illegal_attacks = vec![
+        vec![(bears_id, player1_id)],
+        vec![(tapped_id, player2_id)],
+        vec![(blocker_id, player2_id)],
+        vec![(bears_id, player2_id), (bears_id, player2_id)],
+    ];
+
+    for attackers in illegal_attacks {
+        assert!(game.do_player_action(player1_id, &PlayerAction::DeclareAttackers { attackers }).is_err());
+    }
+
+    assert!(game.do_player_action(player2_id, &PlayerAction::DeclareAttackers {
+        attackers: Vec::new(),
+    }).is_err());
+
+    game.do_player_action(player1_id, &PlayerAction::DeclareAttackers {
+        attackers: vec![(bears_id, player2_id)],
+    }).unwrap();
+
+    pass_priority_round(&mut game);
+
+    let illegal_blocks = vec![
+        vec![(blocker_id, tapped_id)],
+        vec![(bears_id, bears_id)],
+    ];
+
+    for blockers in illegal_blocks {
+        assert!(game.do_player_action(player2_id, &PlayerAction::DeclareBlockers { blockers }).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 extern crate corrosion;
 
-use corrosion::{PlayerAction, GameStatus};
+use corrosion::{GamePhase, GameResult, GameStatus, PlayerAction, PlayerChoice};
 
 use corrosion::utility::*;
 
@@ -9,8 +9,194 @@ fn test_concede() {
     let mut game = new_two_player_game();
 
     let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
 
     game.do_player_action(player1_id, &PlayerAction::Concede).unwrap();
 
-    assert_eq!(game.current_status, GameStatus::Ended);
+    assert_eq!(game.current_status, GameStatus::Ended(GameResult {
+        winners: vec![player2_id],
+        losers: vec![player1_id],
+    }));
+
+    // Nothing else can happen once the game is over.
+    assert!(game.do_player_action(player2_id, &PlayerAction::PassPriority).is_err());
+}
+
+#[test]
+fn test_concede_without_priority() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    game.do_player_action(player2_id, &PlayerAction::Concede).unwrap();
+
+    match game.current_status {
+        GameStatus::Ended(ref result) => {
+            assert_eq!(result.winners, vec![player1_id]);
+            assert!(!result.is_draw());
+        },
+        _ => panic!("Game should have ended"),
+    }
+}
+
+#[test]
+fn test_multiplayer_concede() {
+    let mut game = new_game(3);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let player3_id = game.player_turn_order[2];
+
+    let hand_id = get_hand_id(&game, player2_id);
+    let battlefield_id = get_battlefield_id(&game);
+
+    let card_id = create_forest(&mut game, hand_id, player2_id);
+    let permanent_id = create_forest(&mut game, battlefield_id, player2_id);
+
+    // Player 2 has priority when they leave, so it moves on to player 3.
+    game.do_player_action(player1_id, &PlayerAction::PassPriority).unwrap();
+    game.do_player_action(player2_id, &PlayerAction::Concede).unwrap();
+
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+    assert_eq!(game.player_turn_order, vec![player1_id, player3_id]);
+    assert_eq!(game.losers, vec![player2_id]);
+    assert_eq!(game.priority_player, Some(player3_id));
+
+    // Everything of theirs left with them.
+    assert!(!game.objects.contains_key(&card_id));
+    assert!(!game.objects.contains_key(&permanent_id));
+
+    // The remaining players keep playing, starting a fresh round of passes.
+    game.do_player_action(player3_id, &PlayerAction::PassPriority).unwrap();
+    game.do_player_action(player1_id, &PlayerAction::PassPriority).unwrap();
+
+    assert_eq!(game.current_phase, GamePhase::BeginningOfCombat);
+    assert!(game.do_player_action(player2_id, &PlayerAction::Concede).is_err());
+
+    game.do_player_action(player1_id, &PlayerAction::Concede).unwrap();
+
+    assert_eq!(game.current_status, GameStatus::Ended(GameResult {
+        winners: vec![player3_id],
+        losers: vec![player2_id, player1_id],
+    }));
+}
+
+#[test]
+fn test_controlled_objects_exiled_on_concede() {
+    let mut game = new_game(3);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    let exile_id = get_exile_id(&game);
+    let bears_id = add_creature(&mut game, player1_id, "Grizzly Bears", 2, 2);
+
+    game.objects.get_mut(&bears_id).unwrap().controller = player2_id;
+
+    game.do_player_action(player2_id, &PlayerAction::Concede).unwrap();
+
+    // Player 2 controlled it without owning it, so it's exiled rather than
+    // leaving the game with them (rule 800.4a).
+    let bears = game.objects.values()
+        .find(|object| object.owner == player1_id && object.zone == exile_id)
+        .expect("Bears should have been exiled");
+
+    assert_eq!(bears.controller, player1_id);
+    assert!(!game.objects.contains_key(&bears_id));
+}
+
+#[test]
+fn test_active_player_concedes() {
+    let mut game = new_game(3);
+    fill_libraries(&mut game, 1);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    game.do_player_action(player1_id, &PlayerAction::Concede).unwrap();
+
+    // The rest of the turn plays out without an active player, and the next
+    // player in turn order receives priority instead (rule 800.4h).
+    assert_eq!(game.active_player, None);
+    assert_eq!(game.priority_player, Some(player2_id));
+    assert_eq!(game.current_phase, GamePhase::PrecombatMain);
+    assert_eq!(game.turn_number, 1);
+
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.current_phase, GamePhase::BeginningOfCombat);
+    assert_eq!(game.priority_player, Some(player2_id));
+    assert_eq!(game.turn_number, 1);
+
+    // The next turn is player 2's.
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+
+    assert_eq!(game.active_player, Some(player2_id));
+    assert_eq!(game.priority_player, Some(player2_id));
+    assert_eq!(game.turn_number, 2);
+}
+
+#[test]
+fn test_concede_during_choice() {
+    let mut game = new_game(3);
+    fill_libraries(&mut game, 1);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let hand_id = get_hand_id(&game, player1_id);
+
+    for _ in 0..8 {
+        create_forest(&mut game, hand_id, player1_id);
+    }
+
+    pass_priority_until(&mut game, GamePhase::End);
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerChoice(PlayerChoice::Discard {
+        player_id: player1_id,
+        count: 1,
+    }));
+
+    // Nobody's left to make the choice, so the game carries on without it.
+    game.do_player_action(player1_id, &PlayerAction::Concede).unwrap();
+
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+    assert_eq!(game.active_player, Some(player2_id));
+    assert_eq!(game.current_phase, GamePhase::Upkeep);
+    assert_eq!(game.turn_number, 2);
+}
+
+#[test]
+fn test_simultaneous_loss_is_draw() {
+    let mut game = new_two_player_game();
+
+    let player_ids = game.player_turn_order.clone();
+
+    game.players_lose(&player_ids);
+
+    match game.current_status {
+        GameStatus::Ended(ref result) => {
+            assert!(result.is_draw());
+            assert_eq!(result.losers, player_ids);
+        },
+        _ => panic!("Game should have ended"),
+    }
+}
+
+#[test]
+fn test_player_wins() {
+    let mut game = new_game(4);
+
+    let player3_id = game.player_turn_order[2];
+
+    game.player_wins(player3_id);
+
+    match game.current_status {
+        GameStatus::Ended(ref result) => {
+            assert_eq!(result.winners, vec![player3_id]);
+            assert_eq!(result.losers.len(), 3);
+        },
+        _ => panic!("Game should have ended"),
+    }
 }
@@ -0,0 +1,233 @@
+extern crate corrosion;
+
+use corrosion::{
+    Ability,
+    ActivatedAbility,
+    ActivationCost,
+    Duration,
+    Effect,
+    Game,
+    GamePhase,
+    Id,
+    ManaType,
+    PlayerAction,
+};
+
+use corrosion::utility::*;
+
+/// Puts a Forest that taps for green onto the battlefield, returning the IDs of
+/// the Forest and its mana ability.
+fn add_forest(game: &mut Game, owner: Id) -> (Id, Id) {
+    let battlefield_id = get_battlefield_id(game);
+
+    let forest_id = create_forest(game, battlefield_id, owner);
+    let ability_id = add_ability(game, forest_id, Ability::tap_for_mana(ManaType::Green));
+
+    (forest_id, ability_id)
+}
+
+fn tap_for_green(game: &mut Game, player_id: Id, forest_id: Id, ability_id: Id) -> bool {
+    game.do_player_action(player_id, &PlayerAction::ActivateManaAbility {
+        object_id: forest_id,
+        ability_id,
+        mana_type: ManaType::Green,
+    }).is_ok()
+}
+
+#[test]
+fn test_cannot_use_opponents_permanents() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    let (forest_id, mana_ability_id) = add_forest(&mut game, player1_id);
+    let draw_ability_id = add_ability(&mut game, forest_id, Ability::Activated(ActivatedAbility {
+        cost: ActivationCost::default(),
+        effects: vec![Effect::DrawCards { count: 1 }],
+    }));
+
+    game.do_player_action(player1_id, &PlayerAction::PassPriority).unwrap();
+
+    assert!(!tap_for_green(&mut game, player2_id, forest_id, mana_ability_id));
+
+    let result = game.do_player_action(player2_id, &PlayerAction::ActivateAbility {
+        object_id: forest_id,
+        ability_id: draw_ability_id,
+    });
+
+    assert!(result.is_err());
+    assert!(!game.objects.get(&forest_id).unwrap().is_tapped());
+    assert!(game.stack_is_empty());
+}
+
+#[test]
+fn test_cannot_play_opponents_land() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let hand_id = get_hand_id(&game, player2_id);
+
+    let forest_id = create_forest(&mut game, hand_id, player2_id);
+
+    let result = game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: forest_id,
+        face: None,
+    });
+
+    assert!(result.is_err());
+    assert_eq!(game.objects.get(&forest_id).unwrap().zone, hand_id);
+}
+
+#[test]
+fn test_threaten() {
+    let mut game = new_two_player_game();
+    fill_libraries(&mut game, 1);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    let (forest_id, ability_id) = add_forest(&mut game, player2_id);
+
+    game.gain_control(player1_id, forest_id, Duration::UntilEndOfTurn).unwrap();
+
+    assert_eq!(game.controller(forest_id), Some(player1_id));
+    assert_eq!(game.view_object(forest_id).unwrap().object.controller, player1_id);
+
+    // The owner doesn't change, only the controller.
+    assert_eq!(game.objects.get(&forest_id).unwrap().owner, player2_id);
+
+    assert!(tap_for_green(&mut game, player1_id, forest_id, ability_id));
+
+    // Control goes back to player 2 during the cleanup step, in time for them
+    // to untap it.
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+
+    assert_eq!(game.active_player, Some(player2_id));
+    assert_eq!(game.controller(forest_id), Some(player2_id));
+    assert!(!game.objects.get(&forest_id).unwrap().is_tapped());
+    assert!(game.continuous_effects.is_empty());
+}
+
+#[test]
+fn test_gain_control_effect() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let (stolen_id, _) = add_forest(&mut game, player2_id);
+
+    let source_id = create_forest(&mut game, battlefield_id, player1_id);
+    let ability_id = add_ability(&mut game, source_id, Ability::Activated(ActivatedAbility {
+        cost: ActivationCost::default(),
+        effects: vec![Effect::GainControl {
+            object_id: stolen_id,
+            duration: Duration::Indefinite,
+        }],
+    }));
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateAbility {
+        object_id: source_id,
+        ability_id,
+    }).unwrap();
+
+    assert_eq!(game.controller(stolen_id), Some(player2_id));
+
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.controller(stolen_id), Some(player1_id));
+}
+
+#[test]
+fn test_mind_control() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+    let graveyard_id = get_graveyard_id(&game, player1_id);
+
+    let (stolen_id, _) = add_forest(&mut game, player2_id);
+    let aura_id = create_forest(&mut game, battlefield_id, player1_id);
+
+    game.gain_control(player1_id, stolen_id, Duration::WhileOnBattlefield(aura_id)).unwrap();
+
+    assert_eq!(game.controller(stolen_id), Some(player1_id));
+
+    // Control lasts through turns...
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+
+    assert_eq!(game.controller(stolen_id), Some(player1_id));
+
+    // ...but not past the Aura leaving the battlefield.
+    game.move_object(aura_id, graveyard_id);
+
+    assert_eq!(game.controller(stolen_id), Some(player2_id));
+}
+
+#[test]
+fn test_newest_control_effect_wins() {
+    let mut game = new_game(3);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let player3_id = game.player_turn_order[2];
+
+    let (forest_id, _) = add_forest(&mut game, player1_id);
+
+    game.gain_control(player2_id, forest_id, Duration::Indefinite).unwrap();
+    game.gain_control(player3_id, forest_id, Duration::UntilEndOfTurn).unwrap();
+
+    assert_eq!(game.controller(forest_id), Some(player3_id));
+
+    // Player 3 leaving ends the effect giving them control.
+    game.players_lose(&[player3_id]);
+
+    assert_eq!(game.controller(forest_id), Some(player2_id));
+}
+
+#[test]
+fn test_leaving_game_with_stolen_permanents() {
+    let mut game = new_game(3);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let player3_id = game.player_turn_order[2];
+
+    let (stolen_id, _) = add_forest(&mut game, player3_id);
+    let (lost_id, _) = add_forest(&mut game, player2_id);
+
+    game.gain_control(player2_id, stolen_id, Duration::Indefinite).unwrap();
+    game.gain_control(player1_id, lost_id, Duration::Indefinite).unwrap();
+
+    game.players_lose(&[player2_id]);
+
+    // Player 3 gets their Forest back, but player 2's own Forest leaves with
+    // them even though player 1 controlled it.
+    assert_eq!(game.controller(stolen_id), Some(player3_id));
+    assert!(!game.objects.contains_key(&lost_id));
+}
+
+#[test]
+fn test_changing_zones_resets_control() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let graveyard_id = get_graveyard_id(&game, player2_id);
+
+    let (forest_id, _) = add_forest(&mut game, player2_id);
+
+    game.gain_control(player1_id, forest_id, Duration::Indefinite).unwrap();
+
+    let new_id = game.move_object(forest_id, graveyard_id).unwrap();
+
+    assert_eq!(game.controller(new_id), Some(player2_id));
+    assert!(game.continuous_effects.is_empty());
+
+    // Only permanents can be stolen.
+    assert!(game.gain_control(player1_id, new_id, Duration::Indefinite).is_none());
+}
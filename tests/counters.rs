@@ -0,0 +1,233 @@
+extern crate corrosion;
+
+use corrosion::{
+    CardType,
+    Characteristics,
+    Game,
+    GamePhase,
+    Id,
+    ObjectCounter,
+    StatValue,
+};
+
+use corrosion::utility::*;
+
+fn planeswalker() -> Characteristics {
+    Characteristics {
+        name: "Test Walker".to_string(),
+        card_types: vec![CardType::Planeswalker],
+        loyalty: Some(StatValue::Fixed(3)),
+        ..Characteristics::default()
+    }
+}
+
+fn power_and_toughness(game: &Game, object_id: Id) -> (i32, i32) {
+    let view = game.view_object(object_id).unwrap();
+
+    (view.power().unwrap(), view.toughness().unwrap())
+}
+
+fn objects_in_zone(game: &Game, zone_id: Id) -> usize {
+    game.objects.values()
+        .filter(|object| object.zone == zone_id)
+        .count()
+}
+
+#[test]
+fn test_add_and_remove_counters() {
+    let mut game = new_two_player_game();
+
+    let player_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let bears_id = create_object(&mut game, battlefield_id, player_id, grizzly_bears());
+
+    game.add_counters(bears_id, ObjectCounter::Charge, 3);
+    assert_eq!(game.objects[&bears_id].counters(&ObjectCounter::Charge), 3);
+
+    assert!(game.remove_counters(bears_id, ObjectCounter::Charge, 2).is_ok());
+    assert_eq!(game.objects[&bears_id].counters(&ObjectCounter::Charge), 1);
+
+    // Removing more counters than there are fails without removing any.
+    assert!(game.remove_counters(bears_id, ObjectCounter::Charge, 2).is_err());
+    assert_eq!(game.objects[&bears_id].counters(&ObjectCounter::Charge), 1);
+
+    assert!(game.remove_counters(bears_id, ObjectCounter::Charge, 1).is_ok());
+    assert!(game.objects[&bears_id].counters.is_empty());
+}
+
+#[test]
+fn test_named_counters() {
+    let mut game = new_two_player_game();
+
+    let player_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let bears_id = create_object(&mut game, battlefield_id, player_id, grizzly_bears());
+
+    let fade = ObjectCounter::Named("fade".to_string());
+    let age = ObjectCounter::Named("age".to_string());
+
+    game.add_counters(bears_id, fade.clone(), 2);
+
+    assert_eq!(game.objects[&bears_id].counters(&fade), 2);
+    assert_eq!(game.objects[&bears_id].counters(&age), 0);
+}
+
+#[test]
+fn test_counters_modify_power_and_toughness() {
+    let mut game = new_two_player_game();
+
+    let player_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let bears_id = create_object(&mut game, battlefield_id, player_id, grizzly_bears());
+
+    assert_eq!(power_and_toughness(&game, bears_id), (2, 2));
+
+    game.add_counters(bears_id, ObjectCounter::PlusOnePlusOne, 2);
+    assert_eq!(power_and_toughness(&game, bears_id), (4, 4));
+
+    // The card's own characteristics don't change.
+    let printed = game.characteristics(bears_id).unwrap();
+    assert_eq!(printed.power, Some(StatValue::Fixed(2)));
+}
+
+#[test]
+fn test_plus_and_minus_counters_cancel() {
+    let mut game = new_two_player_game();
+
+    let player_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let bears_id = create_object(&mut game, battlefield_id, player_id, grizzly_bears());
+
+    game.add_counters(bears_id, ObjectCounter::PlusOnePlusOne, 3);
+    game.add_counters(bears_id, ObjectCounter::MinusOneMinusOne, 1);
+    game.process_state_based_actions();
+
+    assert_eq!(game.objects[&bears_id].counters(&ObjectCounter::PlusOnePlusOne), 2);
+    assert_eq!(game.objects[&bears_id].counters(&ObjectCounter::MinusOneMinusOne), 0);
+    assert_eq!(power_and_toughness(&game, bears_id), (4, 4));
+}
+
+#[test]
+fn test_counters_only_cancel_on_battlefield() {
+    let mut game = new_two_player_game();
+
+    let player_id = game.player_turn_order[0];
+    let graveyard_id = get_graveyard_id(&game, player_id);
+    let bears_id = create_object(&mut game, graveyard_id, player_id, grizzly_bears());
+
+    {
+        let counters = &mut game.objects.get_mut(&bears_id).unwrap().counters;
+        counters.insert(ObjectCounter::PlusOnePlusOne, 1);
+        counters.insert(ObjectCounter::MinusOneMinusOne, 1);
+    }
+
+    game.process_state_based_actions();
+
+    assert_eq!(game.objects[&bears_id].counters(&ObjectCounter::PlusOnePlusOne), 1);
+    assert_eq!(game.objects[&bears_id].counters(&ObjectCounter::MinusOneMinusOne), 1);
+}
+
+#[test]
+fn test_zero_toughness_creature_dies() {
+    let mut game = new_two_player_game();
+
+    let player_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let graveyard_id = get_graveyard_id(&game, player_id);
+    let bears_id = create_object(&mut game, battlefield_id, player_id, grizzly_bears());
+
+    game.add_counters(bears_id, ObjectCounter::MinusOneMinusOne, 1);
+    game.process_state_based_actions();
+
+    assert_eq!(power_and_toughness(&game, bears_id), (1, 1));
+
+    game.add_counters(bears_id, ObjectCounter::MinusOneMinusOne, 1);
+    game.process_state_based_actions();
+
+    assert!(!game.objects.contains_key(&bears_id));
+    assert_eq!(objects_in_zone(&game, graveyard_id), 1);
+}
+
+#[test]
+fn test_lethal_damage_destroys_creature() {
+    let mut game = new_two_player_game();
+
+    let player_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let graveyard_id = get_graveyard_id(&game, player_id);
+    let bears_id = create_object(&mut game, battlefield_id, player_id, grizzly_bears());
+
+    game.objects.get_mut(&bears_id).unwrap().damage = 2;
+    game.process_state_based_actions();
+
+    assert!(!game.objects.contains_key(&bears_id));
+    assert_eq!(objects_in_zone(&game, graveyard_id), 1);
+}
+
+#[test]
+fn test_planeswalker_loyalty() {
+    let mut game = new_two_player_game();
+
+    let player_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let graveyard_id = get_graveyard_id(&game, player_id);
+    let hand_id = get_hand_id(&game, player_id);
+    let walker_id = create_object(&mut game, hand_id, player_id, planeswalker());
+
+    // Planeswalkers enter with loyalty counters equal to their printed loyalty.
+    let walker_id = game.move_object(walker_id, battlefield_id).unwrap();
+
+    assert_eq!(game.objects[&walker_id].counters(&ObjectCounter::Loyalty), 3);
+    assert_eq!(game.view_object(walker_id).unwrap().loyalty(), Some(3));
+
+    assert!(game.remove_counters(walker_id, ObjectCounter::Loyalty, 2).is_ok());
+    assert_eq!(game.view_object(walker_id).unwrap().loyalty(), Some(1));
+
+    assert!(game.remove_counters(walker_id, ObjectCounter::Loyalty, 1).is_ok());
+    game.process_state_based_actions();
+
+    assert!(!game.objects.contains_key(&walker_id));
+    assert_eq!(objects_in_zone(&game, graveyard_id), 1);
+}
+
+#[test]
+fn test_stun_counter_replaces_untap() {
+    let mut game = new_two_player_game();
+    fill_libraries(&mut game, 5);
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+
+    game.objects.get_mut(&forest_id).unwrap().set_tapped(true);
+    game.add_counters(forest_id, ObjectCounter::Stun, 1);
+
+    // Player 1's next untap step removes the stun counter instead.
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+
+    assert_eq!(game.active_player, Some(player1_id));
+    assert!(game.objects[&forest_id].is_tapped());
+    assert_eq!(game.objects[&forest_id].counters(&ObjectCounter::Stun), 0);
+
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+
+    assert!(!game.objects[&forest_id].is_tapped());
+}
+
+#[test]
+fn test_counters_lost_on_zone_change() {
+    let mut game = new_two_player_game();
+
+    let player_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let hand_id = get_hand_id(&game, player_id);
+    let bears_id = create_object(&mut game, battlefield_id, player_id, grizzly_bears());
+
+    game.add_counters(bears_id, ObjectCounter::PlusOnePlusOne, 1);
+
+    let new_bears_id = game.move_object(bears_id, hand_id).unwrap();
+
+    assert!(game.objects[&new_bears_id].counters.is_empty());
+}
@@ -0,0 +1,314 @@
+extern crate corrosion;
+
+use corrosion::{
+    Ability,
+    CardType,
+    Characteristics,
+    ContinuousEffectKind,
+    Game,
+    Id,
+    ManaCost,
+    ManaType,
+    ObjectFilter,
+    StaticAbility,
+    StatValue,
+    Supertype,
+
+    get_id,
+};
+
+use corrosion::utility::*;
+
+/// Puts a permanent with the given static ability onto the battlefield under
+/// player 1's control.
+fn add_static(game: &mut Game, characteristics: Characteristics, affected: ObjectFilter, effects: Vec<ContinuousEffectKind>) -> Id {
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(game);
+
+    let object_id = create_object(game, battlefield_id, player1_id, characteristics);
+    add_ability(game, object_id, Ability::Static(StaticAbility {
+        affected,
+        effects,
+    }));
+
+    object_id
+}
+
+fn enchantment(name: &str) -> Characteristics {
+    Characteristics {
+        name: name.to_string(),
+        mana_cost: Some(ManaCost::parse("{2}{W}{W}").unwrap()),
+        card_types: vec![CardType::Enchantment],
+        ..Characteristics::default()
+    }
+}
+
+fn land(name: &str, subtype: &str) -> Characteristics {
+    Characteristics {
+        name: name.to_string(),
+        card_types: vec![CardType::Land],
+        subtypes: vec![subtype.to_string()],
+        ..Characteristics::default()
+    }
+}
+
+fn blood_moon(game: &mut Game) -> Id {
+    add_static(game, enchantment("Blood Moon"), ObjectFilter {
+        card_types: vec![CardType::Land],
+        excluded_supertypes: vec![Supertype::Basic],
+        ..ObjectFilter::default()
+    }, vec![
+        ContinuousEffectKind::SetBasicLandType {
+            subtype: "Mountain".to_string(),
+            ability_id: get_id(),
+            mana_type: ManaType::Red,
+        },
+    ])
+}
+
+fn urborg(game: &mut Game) -> Id {
+    let characteristics = Characteristics {
+        name: "Urborg, Tomb of Yawgmoth".to_string(),
+        supertypes: vec![Supertype::Legendary],
+        card_types: vec![CardType::Land],
+        ..Characteristics::default()
+    };
+
+    add_static(game, characteristics, ObjectFilter {
+        card_types: vec![CardType::Land],
+        ..ObjectFilter::default()
+    }, vec![
+        ContinuousEffectKind::AddTypes {
+            card_types: Vec::new(),
+            subtypes: vec!["Swamp".to_string()],
+        },
+    ])
+}
+
+fn humility(game: &mut Game) -> Id {
+    add_static(game, enchantment("Humility"), ObjectFilter {
+        card_types: vec![CardType::Creature],
+        ..ObjectFilter::default()
+    }, vec![
+        ContinuousEffectKind::RemoveAllAbilities,
+        ContinuousEffectKind::SetPowerToughness { power: 1, toughness: 1 },
+    ])
+}
+
+fn opalescence(game: &mut Game) -> Id {
+    add_static(game, enchantment("Opalescence"), ObjectFilter {
+        card_types: vec![CardType::Enchantment],
+        excluded_subtypes: vec!["Aura".to_string()],
+        other: true,
+        ..ObjectFilter::default()
+    }, vec![
+        ContinuousEffectKind::AddTypes {
+            card_types: vec![CardType::Creature],
+            subtypes: Vec::new(),
+        },
+        ContinuousEffectKind::SetPowerToughnessToManaValue,
+    ])
+}
+
+#[test]
+fn test_blood_moon_and_urborg() {
+    // The result is the same whichever came first.
+    for &blood_moon_first in &[true, false] {
+        let mut game = new_two_player_game();
+
+        let player1_id = game.player_turn_order[0];
+        let battlefield_id = get_battlefield_id(&game);
+        let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+
+        let urborg_id = if blood_moon_first {
+            blood_moon(&mut game);
+            urborg(&mut game)
+        } else {
+            let urborg_id = urborg(&mut game);
+            blood_moon(&mut game);
+            urborg_id
+        };
+
+        // Blood Moon takes Urborg's ability away, so Urborg's effect depends
+        // on Blood Moon's and never gets to apply.
+        let urborg = view(&game, urborg_id);
+        assert_eq!(urborg.subtypes, vec!["Mountain".to_string()]);
+        assert_eq!(urborg.abilities.len(), 1);
+
+        assert!(!view(&game, forest_id).has_subtype("Swamp"));
+
+        let sources = game.available_mana_sources(player1_id);
+        let urborg_source = sources.iter()
+            .find(|source| source.object_id == urborg_id)
+            .unwrap();
+
+        assert_eq!(urborg_source.produces, vec![ManaType::Red]);
+    }
+}
+
+#[test]
+fn test_urborg_without_blood_moon() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+    let urborg_id = urborg(&mut game);
+
+    assert!(view(&game, forest_id).has_subtype("Swamp"));
+    assert!(view(&game, urborg_id).has_subtype("Swamp"));
+
+    // Effects from static abilities end once their source leaves.
+    let graveyard_id = get_graveyard_id(&game, player1_id);
+    game.move_object(urborg_id, graveyard_id).unwrap();
+
+    assert!(!view(&game, forest_id).has_subtype("Swamp"));
+}
+
+#[test]
+fn test_humility_and_opalescence() {
+    // Neither effect depends on the other, so timestamp order decides
+    // Humility's size.
+    for &humility_first in &[true, false] {
+        let mut game = new_two_player_game();
+
+        let player1_id = game.player_turn_order[0];
+        let battlefield_id = get_battlefield_id(&game);
+
+        let bears_id = create_object(&mut game, battlefield_id, player1_id, Characteristics {
+            name: "Grizzly Bears".to_string(),
+            card_types: vec![CardType::Creature],
+            power: Some(StatValue::Fixed(2)),
+            toughness: Some(StatValue::Fixed(2)),
+            ..Characteristics::default()
+        });
+
+        let (humility_id, opalescence_id) = if humility_first {
+            let humility_id = humility(&mut game);
+            (humility_id, opalescence(&mut game))
+        } else {
+            let opalescence_id = opalescence(&mut game);
+            (humility(&mut game), opalescence_id)
+        };
+
+        // Opalescence makes Humility a creature in layer 4, so Humility loses
+        // its own ability in layer 6. It still sets power and toughness in
+        // layer 7b, since it had already started applying.
+        let humility = game.view_object(humility_id).unwrap();
+        assert!(humility.characteristics.as_ref().unwrap().has_card_type(CardType::Creature));
+        assert!(humility.characteristics.as_ref().unwrap().abilities.is_empty());
+
+        let expected_size = if humility_first { 4 } else { 1 };
+        assert_eq!((humility.power(), humility.toughness()), (Some(expected_size), Some(expected_size)));
+
+        // Opalescence doesn't affect itself, so it keeps its ability.
+        let opalescence = view(&game, opalescence_id);
+        assert!(!opalescence.has_card_type(CardType::Creature));
+        assert_eq!(opalescence.abilities.len(), 1);
+
+        let bears = game.view_object(bears_id).unwrap();
+        assert_eq!((bears.power(), bears.toughness()), (Some(1), Some(1)));
+    }
+}
+
+#[test]
+fn test_dependency_beats_timestamp() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let mountain_id = create_object(&mut game, battlefield_id, player1_id, land("Mountain", "Mountain"));
+    let other_mountain_id = create_object(&mut game, battlefield_id, player2_id, land("Mountain", "Mountain"));
+
+    // Living Lands: All Forests are 1/1 creatures that are still lands.
+    add_static(&mut game, enchantment("Living Lands"), ObjectFilter {
+        subtypes: vec!["Forest".to_string()],
+        ..ObjectFilter::default()
+    }, vec![
+        ContinuousEffectKind::AddTypes {
+            card_types: vec![CardType::Creature],
+            subtypes: Vec::new(),
+        },
+        ContinuousEffectKind::SetPowerToughness { power: 1, toughness: 1 },
+    ]);
+
+    // Prismatic Omen: Lands you control are every basic land type in addition
+    // to their other types.
+    add_static(&mut game, enchantment("Prismatic Omen"), ObjectFilter {
+        card_types: vec![CardType::Land],
+        you_control: true,
+        ..ObjectFilter::default()
+    }, vec![
+        ContinuousEffectKind::AddTypes {
+            card_types: Vec::new(),
+            subtypes: ["Plains", "Island", "Swamp", "Mountain", "Forest"].iter()
+                .map(|subtype| subtype.to_string())
+                .collect(),
+        },
+    ]);
+
+    // Prismatic Omen changes what Living Lands applies to, so it applies
+    // first despite being newer.
+    let mountain = game.view_object(mountain_id).unwrap();
+    assert!(mountain.characteristics.as_ref().unwrap().has_subtype("Forest"));
+    assert!(mountain.characteristics.as_ref().unwrap().has_card_type(CardType::Creature));
+    assert_eq!((mountain.power(), mountain.toughness()), (Some(1), Some(1)));
+
+    let other_mountain = view(&game, other_mountain_id);
+    assert!(!other_mountain.has_subtype("Forest"));
+    assert!(!other_mountain.has_card_type(CardType::Creature));
+}
+
+#[test]
+fn test_dependency_loop() {
+    for &islands_first in &[true, false] {
+        let mut game = new_two_player_game();
+
+        let player1_id = game.player_turn_order[0];
+        let battlefield_id = get_battlefield_id(&game);
+
+        let island_id = create_object(&mut game, battlefield_id, player1_id, land("Island", "Island"));
+        let forest_id = create_object(&mut game, battlefield_id, player1_id, land("Forest", "Forest"));
+
+        let islands_are_forests = |game: &mut Game| {
+            add_static(game, enchantment("Islands Are Forests"), ObjectFilter {
+                subtypes: vec!["Island".to_string()],
+                ..ObjectFilter::default()
+            }, vec![
+                ContinuousEffectKind::SetTypes {
+                    card_types: vec![CardType::Land],
+                    subtypes: vec!["Forest".to_string()],
+                },
+            ])
+        };
+
+        let forests_are_islands = |game: &mut Game| {
+            add_static(game, enchantment("Forests Are Islands"), ObjectFilter {
+                subtypes: vec!["Forest".to_string()],
+                ..ObjectFilter::default()
+            }, vec![
+                ContinuousEffectKind::SetTypes {
+                    card_types: vec![CardType::Land],
+                    subtypes: vec!["Island".to_string()],
+                },
+            ])
+        };
+
+        if islands_first {
+            islands_are_forests(&mut game);
+            forests_are_islands(&mut game);
+        } else {
+            forests_are_islands(&mut game);
+            islands_are_forests(&mut game);
+        }
+
+        // Each effect changes what the other applies to, so they're applied
+        // in timestamp order, and the newer one turns both lands.
+        let expected = if islands_first { "Island" } else { "Forest" };
+
+        assert_eq!(view(&game, island_id).subtypes, vec![expected.to_string()]);
+        assert_eq!(view(&game, forest_id).subtypes, vec![expected.to_string()]);
+    }
+}
@@ -0,0 +1,124 @@
+extern crate corrosion;
+
+use corrosion::{
+    ContinuousEffectKind,
+    Duration,
+    GamePhase,
+};
+
+use corrosion::utility::*;
+
+#[test]
+fn test_until_end_of_turn() {
+    let mut game = new_two_player_game();
+    fill_libraries(&mut game, 2);
+
+    let player1_id = game.player_turn_order[0];
+    let bears_id = add_creature(&mut game, player1_id, "Grizzly Bears", 3, 3);
+
+    game.add_continuous_effect(bears_id, Duration::UntilEndOfTurn, ContinuousEffectKind::ModifyPowerToughness {
+        power: 3,
+        toughness: 3,
+    });
+
+    pass_priority_until(&mut game, GamePhase::End);
+    assert_eq!(power(&game, bears_id), 6);
+
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+    assert_eq!(power(&game, bears_id), 3);
+}
+
+#[test]
+fn test_until_your_next_turn() {
+    let mut game = new_two_player_game();
+    fill_libraries(&mut game, 2);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let bears_id = add_creature(&mut game, player1_id, "Grizzly Bears", 3, 3);
+
+    game.add_continuous_effect(bears_id, Duration::UntilYourNextTurn(player1_id), ContinuousEffectKind::ModifyPowerToughness {
+        power: 3,
+        toughness: 3,
+    });
+
+    // The effect lasts through the opponent's turn...
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+    assert_eq!(game.active_player, Some(player2_id));
+    assert_eq!(power(&game, bears_id), 6);
+
+    // ...and ends as player 1's next turn begins.
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+    assert_eq!(game.active_player, Some(player1_id));
+    assert_eq!(power(&game, bears_id), 3);
+    assert!(game.continuous_effects.is_empty());
+}
+
+#[test]
+fn test_while_controlled() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    let olivia_id = add_creature(&mut game, player1_id, "Olivia Voldaren", 3, 3);
+    let vampire_id = add_creature(&mut game, player2_id, "Vampire", 3, 3);
+
+    // Gain control of target Vampire for as long as you control Olivia
+    // Voldaren.
+    game.gain_control(player1_id, vampire_id, Duration::WhileControlled {
+        controller: player1_id,
+        object_id: olivia_id,
+    }).unwrap();
+
+    assert_eq!(game.controller(vampire_id), Some(player1_id));
+
+    // Stealing Olivia back returns the Vampire too.
+    let steal_id = game.gain_control(player2_id, olivia_id, Duration::UntilEndOfTurn).unwrap();
+
+    assert_eq!(game.controller(olivia_id), Some(player2_id));
+    assert_eq!(game.controller(vampire_id), Some(player2_id));
+    assert_eq!(game.continuous_effects.len(), 1);
+    assert_eq!(game.continuous_effects[0].id, steal_id);
+}
+
+#[test]
+fn test_while_controlled_source_leaves() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let graveyard_id = get_graveyard_id(&game, player1_id);
+
+    let olivia_id = add_creature(&mut game, player1_id, "Olivia Voldaren", 3, 3);
+    let vampire_id = add_creature(&mut game, player2_id, "Vampire", 3, 3);
+
+    game.gain_control(player1_id, vampire_id, Duration::WhileControlled {
+        controller: player1_id,
+        object_id: olivia_id,
+    }).unwrap();
+
+    game.move_object(olivia_id, graveyard_id).unwrap();
+
+    assert_eq!(game.controller(vampire_id), Some(player2_id));
+    assert!(game.continuous_effects.is_empty());
+}
+
+#[test]
+fn test_condition_already_false() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let hand_id = get_hand_id(&game, player1_id);
+
+    let aura_id = create_forest(&mut game, hand_id, player1_id);
+    let vampire_id = add_creature(&mut game, player2_id, "Vampire", 3, 3);
+
+    // An effect lasting for as long as something that isn't true does
+    // nothing.
+    game.gain_control(player1_id, vampire_id, Duration::WhileOnBattlefield(aura_id)).unwrap();
+
+    assert_eq!(game.controller(vampire_id), Some(player2_id));
+    assert!(game.continuous_effects.is_empty());
+}
@@ -0,0 +1,276 @@
+extern crate corrosion;
+
+use corrosion::{
+    Ability,
+    ActivatedAbility,
+    ActivationCost,
+    CardType,
+    Characteristics,
+    Effect,
+    Game,
+    GamePhase,
+    Id,
+    ManaType,
+    ObjectCounter,
+    PlayerAction,
+    StatValue,
+    Trigger,
+    TriggeredAbility,
+};
+
+use corrosion::utility::*;
+
+/// A planeswalker with `[+1]: Draw a card.` and `[-3]: You get an emblem.`,
+/// returning the IDs of the planeswalker and both abilities.
+fn add_planeswalker(game: &mut Game, owner: Id, emblem_id: Id) -> (Id, Id, Id) {
+    let hand_id = get_hand_id(game, owner);
+    let battlefield_id = get_battlefield_id(game);
+
+    let walker_id = create_object(game, hand_id, owner, Characteristics {
+        name: "Test Walker".to_string(),
+        card_types: vec![CardType::Planeswalker],
+        loyalty: Some(StatValue::Fixed(3)),
+        ..Characteristics::default()
+    });
+
+    let plus_id = add_ability(game, walker_id, Ability::Activated(ActivatedAbility {
+        cost: ActivationCost::parse("[+1]").unwrap(),
+        effects: vec![Effect::DrawCards { count: 1 }],
+    }));
+
+    let minus_id = add_ability(game, walker_id, Ability::Activated(ActivatedAbility {
+        cost: ActivationCost::parse("[\u{2212}3]").unwrap(),
+        effects: vec![Effect::CreateEmblem { definition_id: emblem_id }],
+    }));
+
+    let walker_id = game.move_object(walker_id, battlefield_id).unwrap();
+
+    (walker_id, plus_id, minus_id)
+}
+
+fn emblem() -> Characteristics {
+    Characteristics {
+        name: "Test Walker Emblem".to_string(),
+        rules_text: "Creatures you control get +1/+1.".to_string(),
+        ..Characteristics::default()
+    }
+}
+
+#[test]
+fn test_parse_loyalty_costs() {
+    assert_eq!(ActivationCost::parse("[+2]").unwrap().loyalty, Some(2));
+    assert_eq!(ActivationCost::parse("[0]").unwrap().loyalty, Some(0));
+    assert_eq!(ActivationCost::parse("[-7]").unwrap().loyalty, Some(-7));
+    assert!(ActivationCost::parse("[+X]").is_err());
+    assert!(ActivationCost::parse("[+1], [+1]").is_err());
+}
+
+#[test]
+fn test_planeswalker_creates_emblem() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let command_id = get_command_id(&game);
+
+    let emblem_definition_id = register_card(&mut game, emblem());
+    let (walker_id, _, minus_id) = add_planeswalker(&mut game, player1_id, emblem_definition_id);
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateAbility {
+        object_id: walker_id,
+        ability_id: minus_id,
+    }).unwrap();
+
+    // Loyalty is paid as part of the cost, so the walker dies before the
+    // ability resolves.
+    assert!(!game.objects.contains_key(&walker_id));
+
+    pass_priority_round(&mut game);
+
+    let emblem = game.objects.values()
+        .find(|object| object.zone == command_id)
+        .unwrap();
+
+    assert!(emblem.is_emblem());
+    assert!(!emblem.is_card());
+    assert_eq!(emblem.owner, player1_id);
+    assert!(game.abilities_function(emblem.id));
+    assert_eq!(game.characteristics(emblem.id).unwrap().name, "Test Walker Emblem");
+
+    // Emblems stay in the command zone for the rest of the game.
+    let emblem_id = emblem.id;
+    let graveyard_id = get_graveyard_id(&game, player1_id);
+
+    assert_eq!(game.move_object(emblem_id, graveyard_id), None);
+    assert!(game.objects.contains_key(&emblem_id));
+}
+
+#[test]
+fn test_loyalty_costs() {
+    let mut game = new_two_player_game();
+    fill_libraries(&mut game, 5);
+
+    let player1_id = game.player_turn_order[0];
+
+    let emblem_definition_id = register_card(&mut game, emblem());
+    let (walker_id, plus_id, _) = add_planeswalker(&mut game, player1_id, emblem_definition_id);
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateAbility {
+        object_id: walker_id,
+        ability_id: plus_id,
+    }).unwrap();
+
+    assert_eq!(game.view_object(walker_id).unwrap().loyalty(), Some(4));
+
+    let (other_walker_id, _, minus_id) = add_planeswalker(&mut game, player1_id, emblem_definition_id);
+    game.remove_counters(other_walker_id, ObjectCounter::Loyalty, 1).unwrap();
+
+    // Two loyalty isn't enough to pay for [-3].
+    let result = game.do_player_action(player1_id, &PlayerAction::ActivateAbility {
+        object_id: other_walker_id,
+        ability_id: minus_id,
+    });
+
+    assert!(result.is_err());
+    assert_eq!(game.view_object(other_walker_id).unwrap().loyalty(), Some(2));
+}
+
+#[test]
+fn test_loyalty_timing() {
+    let mut game = new_two_player_game();
+    fill_libraries(&mut game, 5);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    let emblem_definition_id = register_card(&mut game, emblem());
+    let (walker_id, plus_id, minus_id) = add_planeswalker(&mut game, player1_id, emblem_definition_id);
+    let (other_walker_id, other_plus_id, _) = add_planeswalker(&mut game, player2_id, emblem_definition_id);
+
+    let activate = |game: &mut Game, player_id, object_id, ability_id| {
+        game.do_player_action(player_id, &PlayerAction::ActivateAbility {
+            object_id,
+            ability_id,
+        }).is_ok()
+    };
+
+    // Only at sorcery speed: not while the stack isn't empty...
+    assert!(activate(&mut game, player1_id, walker_id, plus_id));
+    assert!(!activate(&mut game, player1_id, other_walker_id, other_plus_id));
+    pass_priority_round(&mut game);
+
+    // ...and only once each turn per permanent.
+    assert!(!activate(&mut game, player1_id, walker_id, minus_id));
+
+    // Not during another player's turn, either.
+    game.do_player_action(player1_id, &PlayerAction::PassPriority).unwrap();
+    assert!(!activate(&mut game, player2_id, other_walker_id, other_plus_id));
+    game.do_player_action(player2_id, &PlayerAction::PassPriority).unwrap();
+
+    pass_priority_until(&mut game, GamePhase::PrecombatMain);
+    assert_eq!(game.active_player, Some(player2_id));
+    assert!(activate(&mut game, player2_id, other_walker_id, other_plus_id));
+}
+
+/// Gives the player an emblem with the given triggered ability, returning the
+/// emblem's ID.
+fn add_triggered_emblem(game: &mut Game, owner: Id, trigger: Trigger, effect: Effect) -> Id {
+    let definition_id = register_card(game, emblem());
+    let emblem_id = game.create_emblem(definition_id, owner).unwrap();
+
+    add_ability(game, emblem_id, Ability::Triggered(TriggeredAbility {
+        trigger,
+        effects: vec![effect],
+    }));
+
+    emblem_id
+}
+
+#[test]
+fn test_emblem_upkeep_trigger() {
+    let mut game = new_two_player_game();
+    fill_libraries(&mut game, 5);
+
+    let player1_id = game.player_turn_order[0];
+    let hand_id = get_hand_id(&game, player1_id);
+
+    // At the beginning of your upkeep, draw a card.
+    add_triggered_emblem(&mut game, player1_id, Trigger::BeginningOfYourStep(GamePhase::Upkeep), Effect::DrawCards {
+        count: 1,
+    });
+
+    let hand_size = |game: &Game| {
+        game.objects.values()
+            .filter(|object| object.zone == hand_id)
+            .count()
+    };
+
+    // Nothing happens during the other player's upkeep.
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+    assert_ne!(game.active_player, Some(player1_id));
+    assert!(game.stack_is_empty());
+
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+    assert_eq!(game.active_player, Some(player1_id));
+    assert!(!game.stack_is_empty());
+    assert_eq!(hand_size(&game), 0);
+
+    pass_priority_round(&mut game);
+    assert!(game.stack_is_empty());
+    assert_eq!(game.current_phase, GamePhase::Upkeep);
+    assert_eq!(hand_size(&game), 1);
+
+    // The draw step's draw still happens as usual.
+    pass_priority_round(&mut game);
+    assert_eq!(game.current_phase, GamePhase::Draw);
+    assert_eq!(hand_size(&game), 2);
+}
+
+#[test]
+fn test_emblem_cast_trigger() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let hand_id = get_hand_id(&game, player1_id);
+    let battlefield_id = get_battlefield_id(&game);
+
+    // Whenever you cast a spell, you gain 1 life.
+    add_triggered_emblem(&mut game, player1_id, Trigger::YouCastSpell, Effect::GainLife {
+        amount: 1,
+    });
+
+    for _ in 0..2 {
+        let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+        add_ability(&mut game, forest_id, Ability::tap_for_mana(ManaType::Green));
+    }
+
+    let bears_id = create_object(&mut game, hand_id, player1_id, grizzly_bears());
+
+    game.do_player_action(player1_id, &PlayerAction::CastSpell {
+        object_id: bears_id,
+        face: None,
+    }).unwrap();
+
+    // The trigger goes on the stack above the spell, so it resolves first.
+    let stack_id = get_stack_id(&game);
+    assert_eq!(game.zones[&stack_id].order.len(), 2);
+    assert_eq!(game.players[&player1_id].life, 20);
+
+    pass_priority_round(&mut game);
+    assert_eq!(game.zones[&stack_id].order.len(), 1);
+    assert_eq!(game.players[&player1_id].life, 21);
+}
+
+#[test]
+fn test_abilities_function() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let hand_id = get_hand_id(&game, player1_id);
+
+    let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+    let other_forest_id = create_forest(&mut game, hand_id, player1_id);
+
+    assert!(game.abilities_function(forest_id));
+    assert!(!game.abilities_function(other_forest_id));
+}
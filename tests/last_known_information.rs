@@ -0,0 +1,168 @@
+extern crate corrosion;
+
+use corrosion::{
+    Ability,
+    ActivatedAbility,
+    ActivationCost,
+    DamageFilter,
+    Duration,
+    Effect,
+    ObjectCounter,
+    PlayerAction,
+    ReplacementEffectKind,
+};
+
+use corrosion::utility::*;
+
+#[test]
+fn test_play_land_links_objects() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let hand_id = get_hand_id(&game, player1_id);
+    let forest_id = create_forest(&mut game, hand_id, player1_id);
+
+    game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: forest_id,
+        face: None,
+    }).unwrap();
+
+    let info = game.last_known_information(forest_id).unwrap();
+    let new_forest_id = game.new_object_ids[&forest_id];
+
+    assert_eq!(info.view.object.zone, hand_id);
+    assert_eq!(game.objects[&new_forest_id].zone, get_battlefield_id(&game));
+    assert_eq!(game.current_object_id(forest_id), Some(new_forest_id));
+}
+
+#[test]
+fn test_links_outlast_snapshots() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let hand_id = get_hand_id(&game, player1_id);
+    let forest_id = create_forest(&mut game, hand_id, player1_id);
+
+    game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: forest_id,
+        face: None,
+    }).unwrap();
+
+    let new_forest_id = game.current_object_id(forest_id).unwrap();
+
+    pass_priority_round(&mut game);
+
+    // The snapshot is forgotten once the step changes, but the Forest can
+    // still be followed.
+    assert!(game.last_known_information(forest_id).is_none());
+    assert_eq!(game.current_object_id(forest_id), Some(new_forest_id));
+}
+
+#[test]
+fn test_snapshot_includes_effects() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+    let graveyard_id = get_graveyard_id(&game, player2_id);
+
+    let bears_id = create_object(&mut game, battlefield_id, player2_id, grizzly_bears());
+    game.add_counters(bears_id, ObjectCounter::PlusOnePlusOne, 1);
+    game.gain_control(player1_id, bears_id, Duration::Indefinite).unwrap();
+
+    let new_bears_id = game.move_object(bears_id, graveyard_id).unwrap();
+
+    // The snapshot remembers the counters and the control change, even though
+    // the new object has neither.
+    let view = game.view_object_or_last_known(bears_id).unwrap();
+    assert_eq!(view.power(), Some(3));
+    assert_eq!(view.object.controller, player1_id);
+
+    let new_view = game.view_object_or_last_known(new_bears_id).unwrap();
+    assert_eq!(new_view.power(), Some(2));
+    assert_eq!(new_view.object.controller, player2_id);
+}
+
+#[test]
+fn test_dying_creature_leaves_snapshot() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let graveyard_id = get_graveyard_id(&game, player1_id);
+
+    let bears_id = create_object(&mut game, battlefield_id, player1_id, grizzly_bears());
+    game.add_counters(bears_id, ObjectCounter::MinusOneMinusOne, 2);
+    game.process_state_based_actions();
+
+    let info = game.last_known_information(bears_id).unwrap();
+    assert_eq!(info.view.object.zone, battlefield_id);
+    assert_eq!(info.view.toughness(), Some(0));
+
+    let new_bears_id = game.current_object_id(bears_id).unwrap();
+    assert_eq!(game.objects[&new_bears_id].zone, graveyard_id);
+}
+
+#[test]
+fn test_objects_leaving_the_game() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let forest_id = create_forest(&mut game, battlefield_id, player2_id);
+
+    game.do_player_action(player2_id, &PlayerAction::Concede).unwrap();
+
+    assert!(game.last_known_information(forest_id).is_some());
+    assert_eq!(game.current_object_id(forest_id), None);
+    assert!(game.players.contains_key(&player1_id));
+}
+
+#[test]
+fn test_forgotten_once_stack_is_empty() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    // Sacrifice this creature: You gain 2 life.
+    let source_id = add_creature(&mut game, player1_id, "Bottle Gnomes", 1, 3);
+    let ability_id = add_ability(&mut game, source_id, Ability::Activated(ActivatedAbility {
+        cost: ActivationCost {
+            sacrifice: true,
+            ..ActivationCost::default()
+        },
+        effects: vec![Effect::GainLife { amount: 2 }],
+    }));
+
+    // An effect watching for damage from another departed object keeps that
+    // object's snapshot around.
+    let other_id = add_creature(&mut game, player2_id, "Grizzly Bears", 2, 2);
+    game.add_replacement_effect(player1_id, Duration::UntilEndOfTurn, ReplacementEffectKind::PreventDamage {
+        damage: DamageFilter {
+            source_id: Some(other_id),
+            ..DamageFilter::default()
+        },
+        amount: None,
+    });
+
+    let graveyard_id = get_graveyard_id(&game, player2_id);
+    game.move_object(other_id, graveyard_id);
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateAbility {
+        object_id: source_id,
+        ability_id,
+    }).unwrap();
+
+    // The ability on the stack might still need to look back at its source.
+    assert!(game.last_known_information(source_id).is_some());
+
+    pass_priority_round(&mut game);
+
+    assert!(game.stack_is_empty());
+    assert!(game.last_known_information(source_id).is_none());
+    assert!(game.last_known_information(other_id).is_some());
+}
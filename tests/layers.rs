@@ -0,0 +1,322 @@
+extern crate corrosion;
+
+use corrosion::{
+    Ability,
+    CardType,
+    Characteristics,
+    Color,
+    ContinuousEffectKind,
+    Duration,
+    Game,
+    Id,
+    ManaType,
+    ObjectCounter,
+    ObjectFilter,
+    PlayerAction,
+    StatValue,
+    StaticAbility,
+    Supertype,
+
+    get_id,
+};
+
+use corrosion::utility::*;
+
+fn power_and_toughness(game: &Game, object_id: Id) -> (i32, i32) {
+    let view = game.view_object(object_id).unwrap();
+
+    (view.power().unwrap(), view.toughness().unwrap())
+}
+
+#[test]
+fn test_spreading_seas() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let mountain_id = create_object(&mut game, battlefield_id, player1_id, Characteristics {
+        name: "Mountain".to_string(),
+        supertypes: vec![Supertype::Basic],
+        card_types: vec![CardType::Land],
+        subtypes: vec!["Mountain".to_string()],
+        ..Characteristics::default()
+    });
+    let red_ability_id = add_ability(&mut game, mountain_id, Ability::tap_for_mana(ManaType::Red));
+
+    // Enchanted land is an Island.
+    let blue_ability_id = get_id();
+
+    add_effect(&mut game, mountain_id, ContinuousEffectKind::SetTypes {
+        card_types: vec![CardType::Land],
+        subtypes: vec!["Island".to_string()],
+    });
+    add_effect(&mut game, mountain_id, ContinuousEffectKind::RemoveAllAbilities);
+    add_effect(&mut game, mountain_id, ContinuousEffectKind::AddAbility {
+        ability_id: blue_ability_id,
+        ability: Ability::tap_for_mana(ManaType::Blue),
+    });
+
+    let view = game.view_object(mountain_id).unwrap();
+    let characteristics = view.characteristics.unwrap();
+
+    assert!(characteristics.has_supertype(Supertype::Basic));
+    assert!(characteristics.has_subtype("Island"));
+    assert!(!characteristics.has_subtype("Mountain"));
+    assert_eq!(characteristics.abilities.len(), 1);
+
+    // It taps for blue mana only!
+    let sources = game.available_mana_sources(player1_id);
+    assert_eq!(sources.len(), 1);
+    assert_eq!(sources[0].produces, vec![ManaType::Blue]);
+
+    let tap_for_red = game.do_player_action(player1_id, &PlayerAction::ActivateManaAbility {
+        object_id: mountain_id,
+        ability_id: red_ability_id,
+        mana_type: ManaType::Red,
+    });
+    assert!(tap_for_red.is_err());
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateManaAbility {
+        object_id: mountain_id,
+        ability_id: blue_ability_id,
+        mana_type: ManaType::Blue,
+    }).unwrap();
+
+    assert_eq!(game.mana_pools[&player1_id].get(ManaType::Blue), 1);
+}
+
+#[test]
+fn test_layers_beat_timestamps() {
+    let mut game = new_two_player_game();
+    let player1_id = game.player_turn_order[0];
+
+    let bears_id = add_creature(&mut game, player1_id, "Grizzly Bears", 2, 2);
+
+    // Giant Growth, then Turn to Frog. Setting power and toughness happens in
+    // an earlier layer than modifying them, no matter which came first.
+    add_effect(&mut game, bears_id, ContinuousEffectKind::ModifyPowerToughness { power: 3, toughness: 3 });
+    add_effect(&mut game, bears_id, ContinuousEffectKind::SetPowerToughness { power: 1, toughness: 1 });
+
+    assert_eq!(power_and_toughness(&game, bears_id), (4, 4));
+}
+
+#[test]
+fn test_timestamps_within_a_layer() {
+    let mut game = new_two_player_game();
+    let player1_id = game.player_turn_order[0];
+
+    let bears_id = add_creature(&mut game, player1_id, "Grizzly Bears", 2, 2);
+
+    add_effect(&mut game, bears_id, ContinuousEffectKind::SetPowerToughness { power: 0, toughness: 1 });
+    add_effect(&mut game, bears_id, ContinuousEffectKind::SetPowerToughness { power: 4, toughness: 4 });
+
+    assert_eq!(power_and_toughness(&game, bears_id), (4, 4));
+
+    add_effect(&mut game, bears_id, ContinuousEffectKind::AddColors { colors: vec![Color::Red] });
+    add_effect(&mut game, bears_id, ContinuousEffectKind::SetColors { colors: vec![Color::White] });
+
+    let characteristics = game.view_object(bears_id).unwrap().characteristics.unwrap();
+    assert_eq!(characteristics.colors(), vec![Color::White]);
+}
+
+#[test]
+fn test_counters_and_switching() {
+    let mut game = new_two_player_game();
+    let player1_id = game.player_turn_order[0];
+
+    let wall_id = add_creature(&mut game, player1_id, "Wall", 0, 3);
+
+    // Switching happens last, after counters and other modifications.
+    add_effect(&mut game, wall_id, ContinuousEffectKind::SwitchPowerToughness);
+    add_effect(&mut game, wall_id, ContinuousEffectKind::ModifyPowerToughness { power: 2, toughness: 0 });
+    game.add_counters(wall_id, ObjectCounter::PlusOnePlusOne, 1);
+
+    assert_eq!(power_and_toughness(&game, wall_id), (4, 3));
+}
+
+#[test]
+fn test_text_and_types() {
+    let mut game = new_two_player_game();
+    let player1_id = game.player_turn_order[0];
+
+    let bears_id = add_creature(&mut game, player1_id, "Grizzly Bears", 2, 2);
+
+    add_effect(&mut game, bears_id, ContinuousEffectKind::AddTypes {
+        card_types: vec![CardType::Artifact],
+        subtypes: vec!["Golem".to_string()],
+    });
+
+    let characteristics = game.view_object(bears_id).unwrap().characteristics.unwrap();
+    assert!(characteristics.has_card_type(CardType::Artifact));
+    assert!(characteristics.has_card_type(CardType::Creature));
+    assert!(characteristics.has_subtype("Golem"));
+
+    // The card itself doesn't change.
+    assert!(!game.characteristics(bears_id).unwrap().has_card_type(CardType::Artifact));
+}
+
+#[test]
+fn test_change_text() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let walk_id = create_object(&mut game, battlefield_id, player1_id, Characteristics {
+        name: "Zombie Walker".to_string(),
+        subtypes: vec!["Zombie".to_string()],
+        rules_text: "Swampwalk".to_string(),
+        ..grizzly_bears()
+    });
+
+    add_effect(&mut game, walk_id, ContinuousEffectKind::ChangeText {
+        from: "Swamp".to_string(),
+        to: "Island".to_string(),
+    });
+
+    let characteristics = game.view_object(walk_id).unwrap().characteristics.unwrap();
+    assert_eq!(characteristics.rules_text, "Islandwalk");
+}
+
+#[test]
+fn test_change_text_in_abilities() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+
+    // All Swamps are 1/1 creatures that are still lands.
+    let bell_id = create_object(&mut game, battlefield_id, player1_id, Characteristics {
+        name: "Kormus Bell".to_string(),
+        card_types: vec![CardType::Artifact],
+        rules_text: "All Swamps are 1/1 creatures that are still lands.".to_string(),
+        ..Characteristics::default()
+    });
+    add_ability(&mut game, bell_id, Ability::Static(StaticAbility {
+        affected: ObjectFilter {
+            subtypes: vec!["Swamp".to_string()],
+            ..ObjectFilter::default()
+        },
+        effects: vec![
+            ContinuousEffectKind::AddTypes {
+                card_types: vec![CardType::Creature],
+                subtypes: Vec::new(),
+            },
+            ContinuousEffectKind::SetPowerToughness { power: 1, toughness: 1 },
+        ],
+    }));
+
+    let swamp_id = create_object(&mut game, battlefield_id, player1_id, Characteristics {
+        name: "Swamp".to_string(),
+        card_types: vec![CardType::Land],
+        subtypes: vec!["Swamp".to_string()],
+        ..Characteristics::default()
+    });
+    let island_id = create_object(&mut game, battlefield_id, player1_id, Characteristics {
+        name: "Island".to_string(),
+        card_types: vec![CardType::Land],
+        subtypes: vec!["Island".to_string()],
+        ..Characteristics::default()
+    });
+
+    let is_creature = |game: &Game, object_id: Id| {
+        game.view_object(object_id).unwrap()
+            .characteristics.unwrap()
+            .has_card_type(CardType::Creature)
+    };
+
+    assert!(is_creature(&game, swamp_id));
+    assert!(!is_creature(&game, island_id));
+
+    // The new word changes what the ability does, not just its text.
+    add_effect(&mut game, bell_id, ContinuousEffectKind::ChangeText {
+        from: "Swamp".to_string(),
+        to: "Island".to_string(),
+    });
+
+    assert!(!is_creature(&game, swamp_id));
+    assert!(is_creature(&game, island_id));
+    assert_eq!(power_and_toughness(&game, island_id), (1, 1));
+}
+
+#[test]
+fn test_characteristic_defining_ability() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+    let hand_id = get_hand_id(&game, player1_id);
+
+    // Its power and toughness are each equal to the number of lands you
+    // control.
+    let molimo_id = create_object(&mut game, hand_id, player1_id, Characteristics {
+        name: "Molimo, Maro-Sorcerer".to_string(),
+        card_types: vec![CardType::Creature],
+        power: Some(StatValue::Variable(0)),
+        toughness: Some(StatValue::Variable(0)),
+        ..Characteristics::default()
+    });
+    add_ability(&mut game, molimo_id, Ability::Static(StaticAbility {
+        affected: ObjectFilter::default(),
+        effects: vec![ContinuousEffectKind::CountPowerToughness {
+            counted: ObjectFilter {
+                card_types: vec![CardType::Land],
+                you_control: true,
+                ..ObjectFilter::default()
+            },
+        }],
+    }));
+
+    create_forest(&mut game, battlefield_id, player1_id);
+    create_forest(&mut game, battlefield_id, player1_id);
+    create_forest(&mut game, battlefield_id, player2_id);
+
+    // The ability works from every zone.
+    assert_eq!(power_and_toughness(&game, molimo_id), (2, 2));
+
+    let molimo_id = game.move_object(molimo_id, battlefield_id).unwrap();
+    assert_eq!(power_and_toughness(&game, molimo_id), (2, 2));
+
+    // Effects in later layers apply on top of it.
+    add_effect(&mut game, molimo_id, ContinuousEffectKind::ModifyPowerToughness { power: 1, toughness: 0 });
+    create_forest(&mut game, battlefield_id, player1_id);
+
+    assert_eq!(power_and_toughness(&game, molimo_id), (4, 3));
+
+    // Setting its power and toughness overrides it, since that happens in a
+    // later layer.
+    add_effect(&mut game, molimo_id, ContinuousEffectKind::SetPowerToughness { power: 0, toughness: 1 });
+    assert_eq!(power_and_toughness(&game, molimo_id), (1, 1));
+}
+
+#[test]
+fn test_copy_effects() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    let bears_id = add_creature(&mut game, player1_id, "Grizzly Bears", 2, 2);
+    let clone_id = add_creature(&mut game, player1_id, "Clone", 0, 0);
+
+    // Effects in later layers still apply on top of the copy, even though
+    // they started first.
+    add_effect(&mut game, clone_id, ContinuousEffectKind::ModifyPowerToughness { power: 1, toughness: 1 });
+    game.become_copy(clone_id, bears_id, Duration::Indefinite).unwrap();
+
+    // Later changes to the original aren't copied.
+    add_effect(&mut game, bears_id, ContinuousEffectKind::ModifyPowerToughness { power: 5, toughness: 5 });
+
+    assert_eq!(game.view_object(clone_id).unwrap().characteristics.unwrap().name, "Grizzly Bears");
+    assert_eq!(power_and_toughness(&game, clone_id), (3, 3));
+
+    // A copy of the Clone copies what it's copying.
+    let token_id = game.create_token_copy(clone_id, player2_id).unwrap();
+
+    assert_eq!(game.copiable_values(token_id).unwrap().name, "Grizzly Bears");
+    assert_eq!(power_and_toughness(&game, token_id), (2, 2));
+    assert_eq!(game.view_object(token_id).unwrap().object.controller, player2_id);
+    assert_eq!(game.view_object(clone_id).unwrap().object.controller, player1_id);
+}
@@ -0,0 +1,95 @@
+extern crate corrosion;
+
+use corrosion::{
+    DEFAULT_STARTING_LIFE,
+    Game,
+    Id,
+    PlayerCounter,
+    get_id,
+};
+
+use corrosion::utility::*;
+
+fn counters(game: &Game, player_id: Id, kind: &PlayerCounter) -> usize {
+    game.players.get(&player_id).unwrap().counters(kind)
+}
+
+#[test]
+fn test_starting_life() {
+    let game = new_two_player_game();
+
+    for player_id in &game.player_turn_order {
+        assert_eq!(life(&game, *player_id), DEFAULT_STARTING_LIFE);
+    }
+
+    let game = new_game_with_starting_life(4, 40);
+
+    for player_id in &game.player_turn_order {
+        assert_eq!(life(&game, *player_id), 40);
+    }
+}
+
+#[test]
+fn test_gain_and_lose_life() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    game.gain_life(player1_id, 3);
+    assert_eq!(life(&game, player1_id), 23);
+
+    // Life totals can drop below zero.
+    game.lose_life(player1_id, 30);
+    assert_eq!(life(&game, player1_id), -7);
+
+    assert_eq!(life(&game, player2_id), 20);
+}
+
+#[test]
+fn test_pay_life() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+
+    game.pay_life(player1_id, 5).unwrap();
+    assert_eq!(life(&game, player1_id), 15);
+
+    // Players can't pay more life than they have.
+    assert!(!game.can_pay_life(player1_id, 16));
+    assert!(game.pay_life(player1_id, 16).is_err());
+    assert_eq!(life(&game, player1_id), 15);
+
+    // Paying all of it is fine, and paying 0 life always is.
+    game.pay_life(player1_id, 15).unwrap();
+    assert_eq!(life(&game, player1_id), 0);
+
+    game.lose_life(player1_id, 2);
+    game.pay_life(player1_id, 0).unwrap();
+
+    assert!(!game.can_pay_life(get_id(), 0));
+}
+
+#[test]
+fn test_player_counters() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+
+    game.add_player_counters(player1_id, PlayerCounter::Poison, 3);
+    game.add_player_counters(player1_id, PlayerCounter::Energy, 4);
+    game.add_player_counters(player1_id, PlayerCounter::Named("rad".to_string()), 2);
+
+    assert_eq!(counters(&game, player1_id, &PlayerCounter::Poison), 3);
+    assert_eq!(counters(&game, player1_id, &PlayerCounter::Energy), 4);
+    assert_eq!(counters(&game, player1_id, &PlayerCounter::Experience), 0);
+    assert_eq!(counters(&game, player1_id, &PlayerCounter::Named("rad".to_string())), 2);
+
+    // Paying energy fails unless the player has enough.
+    assert!(game.remove_player_counters(player1_id, PlayerCounter::Energy, 5).is_err());
+    assert_eq!(counters(&game, player1_id, &PlayerCounter::Energy), 4);
+
+    game.remove_player_counters(player1_id, PlayerCounter::Energy, 4).unwrap();
+    assert_eq!(counters(&game, player1_id, &PlayerCounter::Energy), 0);
+    assert!(!game.players.get(&player1_id).unwrap().counters.contains_key(&PlayerCounter::Energy));
+}
@@ -0,0 +1,56 @@
+extern crate corrosion;
+
+use corrosion::{
+    Color,
+    ManaCost,
+    ManaCostParseError,
+    ManaSymbol,
+};
+
+#[test]
+fn test_parse_symbols() {
+    let cost = ManaCost::parse("{2}{G}{G}").unwrap();
+
+    assert_eq!(cost.symbols, vec![
+        ManaSymbol::Generic(2),
+        ManaSymbol::Colored(Color::Green),
+        ManaSymbol::Colored(Color::Green),
+    ]);
+    assert_eq!(cost.mana_value(), 4);
+
+    let cost = ManaCost::parse("{X}{R}").unwrap();
+
+    assert_eq!(cost.symbols, vec![ManaSymbol::Variable, ManaSymbol::Colored(Color::Red)]);
+    assert!(cost.has_variable());
+    assert_eq!(cost.mana_value(), 1);
+}
+
+#[test]
+fn test_parse_hybrid_symbols() {
+    assert_eq!(ManaCost::parse("{W/U}").unwrap().symbols, vec![ManaSymbol::Hybrid(Color::White, Color::Blue)]);
+    assert_eq!(ManaCost::parse("{G/P}").unwrap().symbols, vec![ManaSymbol::Phyrexian(Color::Green)]);
+    assert_eq!(ManaCost::parse("{2/B}").unwrap().symbols, vec![ManaSymbol::MonoHybrid(Color::Black)]);
+    assert_eq!(ManaCost::parse("{C}").unwrap().symbols, vec![ManaSymbol::Colorless]);
+
+    assert_eq!(ManaCost::parse("{2/B}{2/B}{2/B}").unwrap().mana_value(), 6);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(ManaCost::parse("{Q}"), Err(ManaCostParseError::UnknownSymbol("Q".to_string())));
+    assert_eq!(ManaCost::parse("{W/W}"), Err(ManaCostParseError::UnknownSymbol("W/W".to_string())));
+    assert_eq!(ManaCost::parse("{3/G}"), Err(ManaCostParseError::UnknownSymbol("3/G".to_string())));
+    assert_eq!(ManaCost::parse("{+5}"), Err(ManaCostParseError::UnknownSymbol("+5".to_string())));
+    assert_eq!(ManaCost::parse("2G"), Err(ManaCostParseError::Malformed));
+    assert_eq!(ManaCost::parse("{2}{G"), Err(ManaCostParseError::Malformed));
+    assert_eq!(ManaCost::parse("{{G}}"), Err(ManaCostParseError::Malformed));
+}
+
+#[test]
+fn test_round_trip() {
+    for source in &["{2}{G}{G}", "{X}{R}", "{W/U}{W/U}", "{G/P}", "{2/B}{C}", "{0}"] {
+        let cost: ManaCost = source.parse().unwrap();
+
+        assert_eq!(cost.to_string(), *source);
+    }
+}
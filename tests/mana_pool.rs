@@ -0,0 +1,70 @@
+extern crate corrosion;
+
+use corrosion::{
+    ManaError,
+    ManaPool,
+    ManaType,
+    get_id,
+};
+
+use corrosion::utility::*;
+
+#[test]
+fn test_pool_tracks_types_separately() {
+    let mut pool = ManaPool::new();
+
+    pool.add(ManaType::White, 2);
+    pool.add(ManaType::Colorless, 1);
+
+    assert_eq!(pool.get(ManaType::White), 2);
+    assert_eq!(pool.get(ManaType::Colorless), 1);
+    assert_eq!(pool.get(ManaType::Green), 0);
+    assert_eq!(pool.total(), 3);
+
+    pool.remove(ManaType::White, 1).unwrap();
+
+    assert_eq!(pool.get(ManaType::White), 1);
+}
+
+#[test]
+fn test_remove_too_much() {
+    let mut pool = ManaPool::new();
+
+    pool.add(ManaType::Red, 1);
+
+    assert_eq!(pool.remove(ManaType::Red, 2), Err(ManaError::NotEnoughMana));
+    assert_eq!(pool.remove(ManaType::Blue, 1), Err(ManaError::NotEnoughMana));
+
+    // Failed removals leave the pool alone.
+    assert_eq!(pool.get(ManaType::Red), 1);
+}
+
+#[test]
+fn test_missing_pool() {
+    let mut game = new_two_player_game();
+
+    let missing_player_id = get_id();
+
+    assert_eq!(game.add_mana(missing_player_id, ManaType::Black, 1), Err(ManaError::PoolNotFound));
+    assert_eq!(game.remove_mana(missing_player_id, ManaType::Black, 1), Err(ManaError::PoolNotFound));
+}
+
+#[test]
+fn test_pools_empty_between_steps() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    game.add_mana(player1_id, ManaType::Blue, 2).unwrap();
+    game.add_mana(player2_id, ManaType::Colorless, 1).unwrap();
+
+    // Mana stays around while priority passes within a step.
+    game.remove_mana(player1_id, ManaType::Blue, 1).unwrap();
+    assert_eq!(game.mana_pools.get(&player1_id).unwrap().get(ManaType::Blue), 1);
+
+    pass_priority_round(&mut game);
+
+    assert!(game.mana_pools.get(&player1_id).unwrap().is_empty());
+    assert!(game.mana_pools.get(&player2_id).unwrap().is_empty());
+}
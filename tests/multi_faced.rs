@@ -0,0 +1,355 @@
+extern crate corrosion;
+
+use std::sync::Arc;
+
+use corrosion::{
+    Ability,
+    CardLayout,
+    CardType,
+    Face,
+    Game,
+    Id,
+    ManaType,
+    PlayerAction,
+
+    load_cards,
+};
+
+use corrosion::utility::*;
+
+const CARDS: &str = r#"
+[[card]]
+name = "Fire"
+layout = "split"
+fuse = true
+mana_cost = "{1}{G}"
+types = ["Instant"]
+
+[card.back]
+name = "Ice"
+mana_cost = "{1}{G}"
+types = ["Instant"]
+
+[[card]]
+name = "Wear"
+layout = "split"
+mana_cost = "{1}{G}"
+types = ["Instant"]
+
+[card.back]
+name = "Tear"
+mana_cost = "{G}"
+types = ["Instant"]
+
+[[card]]
+name = "Village Ironsmith"
+layout = "transform"
+mana_cost = "{1}{G}"
+types = ["Creature"]
+subtypes = ["Human", "Werewolf"]
+power = 1
+toughness = 1
+
+[card.back]
+name = "Ironfang"
+color_indicator = ["G"]
+types = ["Creature"]
+subtypes = ["Werewolf"]
+power = 3
+toughness = 1
+
+[[card]]
+name = "Kazandu Mammoth"
+layout = "modal_dfc"
+mana_cost = "{2}{G}{G}"
+types = ["Creature"]
+subtypes = ["Elephant"]
+power = 6
+toughness = 6
+
+[card.back]
+name = "Kazandu Valley"
+types = ["Land"]
+
+[[card]]
+name = "Beanstalk Giant"
+layout = "adventure"
+mana_cost = "{6}{G}"
+types = ["Creature"]
+subtypes = ["Giant"]
+power = "*"
+toughness = "*"
+
+[card.back]
+name = "Fertile Footsteps"
+mana_cost = "{2}{G}"
+types = ["Sorcery"]
+subtypes = ["Adventure"]
+
+[[card]]
+name = "Budoka Gardener"
+layout = "flip"
+mana_cost = "{1}{G}"
+types = ["Creature"]
+subtypes = ["Human", "Monk"]
+power = 2
+toughness = 1
+
+[card.back]
+name = "Dokai, Weaver of Life"
+supertypes = ["Legendary"]
+types = ["Creature"]
+subtypes = ["Human", "Monk"]
+power = 3
+toughness = 3
+"#;
+
+/// Sets up a two player game with the test cards loaded and the given number
+/// of Forests that tap for green on player 1's battlefield.
+fn setup(forest_count: usize) -> Game {
+    let mut game = new_two_player_game();
+    fill_libraries(&mut game, 5);
+
+    load_cards(Arc::make_mut(&mut game.registry), "multi_faced.toml", CARDS).unwrap();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+
+    for _ in 0..forest_count {
+        let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+        add_ability(&mut game, forest_id, Ability::tap_for_mana(ManaType::Green));
+    }
+
+    game
+}
+
+/// Puts the card with the given name into player 1's hand.
+fn draw_named(game: &mut Game, name: &str) -> Id {
+    let player1_id = game.player_turn_order[0];
+    let hand_id = get_hand_id(game, player1_id);
+    let definition_id = game.registry.find_by_name(name).unwrap().id;
+
+    game.create_card(definition_id, hand_id, player1_id)
+}
+
+fn name(game: &Game, object_id: Id) -> String {
+    game.view_object(object_id).unwrap().characteristics.unwrap().name
+}
+
+fn cast(game: &mut Game, object_id: Id, face: Option<Face>) -> bool {
+    let player1_id = game.player_turn_order[0];
+
+    game.do_player_action(player1_id, &PlayerAction::CastSpell {
+        object_id,
+        face,
+    }).is_ok()
+}
+
+/// The only object in the given zone.
+fn only_object_in(game: &Game, zone_id: Id) -> Id {
+    let object_ids: Vec<Id> = game.objects.values()
+        .filter(|object| object.zone == zone_id)
+        .map(|object| object.id)
+        .collect();
+
+    assert_eq!(object_ids.len(), 1);
+    object_ids[0]
+}
+
+#[test]
+fn test_load_faces() {
+    let game = setup(0);
+
+    let fire_ice = game.registry.find_by_name("Ice").unwrap();
+    assert_eq!(fire_ice.layout, CardLayout::Split { fuse: true });
+    assert_eq!(fire_ice.characteristics.name, "Fire // Ice");
+    assert_eq!(fire_ice.face(Face::Back).unwrap().name, "Ice");
+
+    let ironsmith = game.registry.find_by_name("Village Ironsmith").unwrap();
+    assert_eq!(ironsmith.layout, CardLayout::Transform);
+    assert_eq!(ironsmith.characteristics.name, "Village Ironsmith");
+}
+
+#[test]
+fn test_invalid_layouts() {
+    let mut game = setup(0);
+    let registry = Arc::make_mut(&mut game.registry);
+
+    let missing_back = "[[card]]\nname = \"Fire\"\nlayout = \"split\"\n";
+    assert!(load_cards(registry, "cards.toml", missing_back).is_err());
+
+    let normal_with_back = "[[card]]\nname = \"Fire\"\n\n[card.back]\nname = \"Ice\"\n";
+    assert!(load_cards(registry, "cards.toml", normal_with_back).is_err());
+
+    let fused_adventure = "[[card]]\nname = \"Giant\"\nlayout = \"adventure\"\nfuse = true\n\n[card.back]\nname = \"Steps\"\n";
+    assert!(load_cards(registry, "cards.toml", fused_adventure).is_err());
+}
+
+#[test]
+fn test_split_card() {
+    let mut game = setup(2);
+
+    let player1_id = game.player_turn_order[0];
+    let stack_id = get_stack_id(&game);
+    let graveyard_id = get_graveyard_id(&game, player1_id);
+
+    // Outside the stack, split cards have both halves' characteristics.
+    let card_id = draw_named(&mut game, "Wear");
+    assert_eq!(name(&game, card_id), "Wear // Tear");
+
+    let mana_value = game.characteristics(card_id).unwrap().mana_cost.as_ref().unwrap().mana_value();
+    assert_eq!(mana_value, 3);
+
+    // Without fuse, a half has to be picked.
+    assert!(!cast(&mut game, card_id, None));
+    assert!(cast(&mut game, card_id, Some(Face::Back)));
+
+    let spell_id = only_object_in(&game, stack_id);
+    assert_eq!(name(&game, spell_id), "Tear");
+
+    pass_priority_round(&mut game);
+
+    let card_id = only_object_in(&game, graveyard_id);
+    assert_eq!(name(&game, card_id), "Wear // Tear");
+}
+
+#[test]
+fn test_fuse() {
+    let mut game = setup(4);
+
+    let stack_id = get_stack_id(&game);
+    let card_id = draw_named(&mut game, "Fire");
+
+    assert!(cast(&mut game, card_id, None));
+
+    // Casting both halves costs both halves' mana.
+    let spell_id = only_object_in(&game, stack_id);
+    assert_eq!(name(&game, spell_id), "Fire // Ice");
+    assert!(game.available_mana_sources(game.player_turn_order[0]).is_empty());
+}
+
+#[test]
+fn test_transform() {
+    let mut game = setup(2);
+
+    let battlefield_id = get_battlefield_id(&game);
+    let card_id = draw_named(&mut game, "Village Ironsmith");
+
+    // The back face can't be cast.
+    assert!(!cast(&mut game, card_id, Some(Face::Back)));
+    assert!(cast(&mut game, card_id, None));
+
+    pass_priority_round(&mut game);
+
+    let permanent_id = game.current_object_id(card_id).unwrap();
+    assert_eq!(game.objects[&permanent_id].zone, battlefield_id);
+    assert_eq!(name(&game, permanent_id), "Village Ironsmith");
+
+    assert!(game.transform(permanent_id));
+    assert_eq!(name(&game, permanent_id), "Ironfang");
+    assert_eq!(game.view_object(permanent_id).unwrap().power(), Some(3));
+
+    assert!(game.transform(permanent_id));
+    assert_eq!(name(&game, permanent_id), "Village Ironsmith");
+
+    // Only transforming double-faced permanents can transform.
+    let other_id = draw_named(&mut game, "Kazandu Mammoth");
+    assert!(!game.transform(other_id));
+}
+
+#[test]
+fn test_modal_double_faced_land() {
+    let mut game = setup(0);
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let card_id = draw_named(&mut game, "Kazandu Mammoth");
+
+    // The front face isn't a land.
+    let result = game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: card_id,
+        face: None,
+    });
+    assert!(result.is_err());
+
+    game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: card_id,
+        face: Some(Face::Back),
+    }).unwrap();
+
+    let land_id = game.current_object_id(card_id).unwrap();
+
+    assert_eq!(game.objects[&land_id].zone, battlefield_id);
+    assert_eq!(game.objects[&land_id].face, Some(Face::Back));
+    assert_eq!(name(&game, land_id), "Kazandu Valley");
+}
+
+#[test]
+fn test_adventure() {
+    let mut game = setup(10);
+
+    let stack_id = get_stack_id(&game);
+    let exile_id = get_exile_id(&game);
+    let card_id = draw_named(&mut game, "Beanstalk Giant");
+
+    assert_eq!(name(&game, card_id), "Beanstalk Giant");
+    assert!(cast(&mut game, card_id, Some(Face::Back)));
+
+    let spell_id = only_object_in(&game, stack_id);
+    let characteristics = game.characteristics(spell_id).unwrap();
+
+    assert_eq!(characteristics.name, "Fertile Footsteps");
+    assert!(characteristics.has_card_type(CardType::Sorcery));
+
+    // The Adventure is exiled as it resolves, rather than put into the
+    // graveyard (rule 715.4).
+    pass_priority_round(&mut game);
+
+    let card_id = only_object_in(&game, exile_id);
+    assert_eq!(name(&game, card_id), "Beanstalk Giant");
+
+    // It can't go on another adventure, but the creature can be cast from
+    // exile.
+    assert!(!cast(&mut game, card_id, Some(Face::Back)));
+    assert!(cast(&mut game, card_id, None));
+
+    let spell_id = only_object_in(&game, stack_id);
+    assert_eq!(name(&game, spell_id), "Beanstalk Giant");
+    assert!(game.adventurers.is_empty());
+}
+
+#[test]
+fn test_exiled_card_not_castable() {
+    let mut game = setup(7);
+
+    let exile_id = get_exile_id(&game);
+    let card_id = draw_named(&mut game, "Beanstalk Giant");
+    let card_id = game.move_object(card_id, exile_id).unwrap();
+
+    // Only cards that went on an adventure can be cast from exile.
+    assert!(!cast(&mut game, card_id, None));
+}
+
+#[test]
+fn test_flip() {
+    let mut game = setup(0);
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let hand_id = get_hand_id(&game, player1_id);
+
+    let card_id = draw_named(&mut game, "Budoka Gardener");
+
+    // Cards can only flip on the battlefield.
+    assert!(!game.flip(card_id));
+
+    let permanent_id = game.move_object(card_id, battlefield_id).unwrap();
+
+    assert!(game.flip(permanent_id));
+    assert!(!game.flip(permanent_id));
+    assert_eq!(name(&game, permanent_id), "Dokai, Weaver of Life");
+
+    // Flipped status is lost when it leaves the battlefield.
+    let card_id = game.move_object(permanent_id, hand_id).unwrap();
+    assert_eq!(name(&game, card_id), "Budoka Gardener");
+}
@@ -0,0 +1,249 @@
+extern crate corrosion;
+
+use corrosion::{
+    Ability,
+    Game,
+    Id,
+    ManaCost,
+    ManaPool,
+    ManaSource,
+    ManaType,
+    get_id,
+    pays_exactly,
+    solve_payment,
+};
+
+use corrosion::utility::*;
+
+fn source(produces: Vec<ManaType>) -> ManaSource {
+    ManaSource {
+        object_id: get_id(),
+        ability_id: get_id(),
+        produces,
+    }
+}
+
+fn cost(source: &str) -> ManaCost {
+    ManaCost::parse(source).unwrap()
+}
+
+#[test]
+fn test_pay_from_pool() {
+    let mut pool = ManaPool::new();
+    pool.add(ManaType::Green, 2);
+    pool.add(ManaType::Red, 2);
+
+    let plan = solve_payment(&cost("{2}{G}{G}"), 0, &pool, &[], 0).unwrap();
+
+    assert!(plan.activations.is_empty());
+    assert_eq!(plan.mana, pool);
+
+    assert!(solve_payment(&cost("{2}{G}{G}{G}"), 0, &pool, &[], 0).is_none());
+}
+
+#[test]
+fn test_pay_with_sources() {
+    let mut pool = ManaPool::new();
+    pool.add(ManaType::Colorless, 1);
+
+    let sources = vec![
+        source(vec![ManaType::White, ManaType::Blue]),
+        source(vec![ManaType::Blue]),
+        source(vec![ManaType::Green]),
+    ];
+
+    // The floating colorless mana pays the generic part, and the single-color
+    // source is preferred over the dual.
+    let plan = solve_payment(&cost("{1}{U}"), 0, &pool, &sources, 0).unwrap();
+
+    assert_eq!(plan.activations.len(), 1);
+    assert_eq!(plan.activations[0].object_id, sources[1].object_id);
+    assert_eq!(plan.activations[0].mana_type, ManaType::Blue);
+    assert_eq!(plan.mana.get(ManaType::Colorless), 1);
+    assert_eq!(plan.mana.get(ManaType::Blue), 1);
+
+    // Needing both blue and white means the dual has to produce white.
+    let plan = solve_payment(&cost("{W}{U}"), 0, &ManaPool::new(), &sources, 0).unwrap();
+
+    let dual = plan.activations.iter()
+        .find(|activation| activation.object_id == sources[0].object_id)
+        .unwrap();
+
+    assert_eq!(dual.mana_type, ManaType::White);
+
+    assert!(solve_payment(&cost("{W}{W}"), 0, &ManaPool::new(), &sources, 0).is_none());
+}
+
+#[test]
+fn test_pay_variable() {
+    let sources = vec![
+        source(vec![ManaType::Red]),
+        source(vec![ManaType::Red]),
+        source(vec![ManaType::Red]),
+    ];
+
+    let plan = solve_payment(&cost("{X}{R}"), 2, &ManaPool::new(), &sources, 0).unwrap();
+
+    assert_eq!(plan.activations.len(), 3);
+    assert!(solve_payment(&cost("{X}{R}"), 3, &ManaPool::new(), &sources, 0).is_none());
+}
+
+#[test]
+fn test_pay_hybrid() {
+    let sources = vec![source(vec![ManaType::Black]), source(vec![ManaType::Green])];
+
+    let plan = solve_payment(&cost("{B/G}{B/G}"), 0, &ManaPool::new(), &sources, 0).unwrap();
+
+    assert_eq!(plan.mana.get(ManaType::Black), 1);
+    assert_eq!(plan.mana.get(ManaType::Green), 1);
+}
+
+#[test]
+fn test_pay_mono_hybrid() {
+    let mut pool = ManaPool::new();
+    pool.add(ManaType::Red, 2);
+
+    // {2/B} can be paid with two mana of any type...
+    let plan = solve_payment(&cost("{2/B}"), 0, &pool, &[], 0).unwrap();
+    assert_eq!(plan.mana.get(ManaType::Red), 2);
+
+    // ...or with a single black mana.
+    let sources = vec![source(vec![ManaType::Black])];
+    let plan = solve_payment(&cost("{2/B}"), 0, &ManaPool::new(), &sources, 0).unwrap();
+    assert_eq!(plan.mana.get(ManaType::Black), 1);
+}
+
+#[test]
+fn test_pay_many_mono_hybrid() {
+    let source = ["{2/B}"; 70].concat();
+
+    let mut pool = ManaPool::new();
+    pool.add(ManaType::Black, 60);
+    pool.add(ManaType::Red, 20);
+
+    // 60 are paid with black mana and the other 10 with two red mana each.
+    let plan = solve_payment(&cost(&source), 0, &pool, &[], 0).unwrap();
+
+    assert_eq!(plan.mana, pool);
+    assert!(pays_exactly(&cost(&source), 0, &pool, 0));
+
+    pool.remove(ManaType::Red, 1).unwrap();
+    assert!(solve_payment(&cost(&source), 0, &pool, &[], 0).is_none());
+}
+
+#[test]
+fn test_pay_phyrexian() {
+    let sources = vec![source(vec![ManaType::Green])];
+
+    // Mana is preferred over life.
+    let plan = solve_payment(&cost("{G/P}"), 0, &ManaPool::new(), &sources, 20).unwrap();
+    assert_eq!(plan.life, 0);
+    assert_eq!(plan.activations.len(), 1);
+
+    let plan = solve_payment(&cost("{G/P}{G/P}"), 0, &ManaPool::new(), &sources, 20).unwrap();
+    assert_eq!(plan.life, 2);
+
+    assert!(solve_payment(&cost("{G/P}{G/P}"), 0, &ManaPool::new(), &sources, 1).is_none());
+}
+
+#[test]
+fn test_pays_exactly() {
+    let mut mana = ManaPool::new();
+    mana.add(ManaType::Green, 3);
+
+    assert!(pays_exactly(&cost("{1}{G}{G}"), 0, &mana, 0));
+    assert!(!pays_exactly(&cost("{G}{G}"), 0, &mana, 0));
+    assert!(!pays_exactly(&cost("{3}{G}"), 0, &mana, 0));
+    assert!(pays_exactly(&cost("{1}{G}{G/P}"), 0, &mana, 0));
+    assert!(!pays_exactly(&cost("{1}{G}{G/P}{G/P}"), 0, &mana, 0));
+    assert!(pays_exactly(&cost("{1}{G}{G/P}{G/P}"), 0, &mana, 2));
+}
+
+fn add_forests(game: &mut Game, player_id: Id, count: usize) -> Vec<Id> {
+    let battlefield_id = get_battlefield_id(game);
+
+    (0..count)
+        .map(|_| {
+            let forest_id = create_forest(game, battlefield_id, player_id);
+            add_ability(game, forest_id, Ability::tap_for_mana(ManaType::Green));
+
+            forest_id
+        })
+        .collect()
+}
+
+#[test]
+fn test_auto_pay() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let forest_ids = add_forests(&mut game, player1_id, 3);
+
+    game.add_mana(player1_id, ManaType::Green, 1).unwrap();
+
+    let plan = game.auto_pay_mana_cost(player1_id, &cost("{1}{G}"), 0).unwrap();
+
+    // The floating mana was spent first, so only one Forest was tapped.
+    assert_eq!(plan.activations.len(), 1);
+    assert!(game.mana_pools.get(&player1_id).unwrap().is_empty());
+
+    let tapped_count = forest_ids.iter()
+        .filter(|&&forest_id| is_tapped(&game, forest_id))
+        .count();
+
+    assert_eq!(tapped_count, 1);
+
+    // Two Forests left can't pay for three mana, and nothing gets tapped.
+    assert!(game.auto_pay_mana_cost(player1_id, &cost("{2}{G}"), 0).is_err());
+
+    let tapped_count = forest_ids.iter()
+        .filter(|&&forest_id| is_tapped(&game, forest_id))
+        .count();
+
+    assert_eq!(tapped_count, 1);
+}
+
+#[test]
+fn test_pay_with_bad_plan() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let forest_ids = add_forests(&mut game, player1_id, 2);
+
+    let plan = game.find_payment(player1_id, &cost("{G}{G}"), 0).unwrap();
+
+    // A plan for a different cost is rejected outright.
+    assert!(game.pay_mana_cost(player1_id, &cost("{G}"), 0, &plan).is_err());
+
+    // Player 2 can't activate player 1's Forests.
+    assert!(game.pay_mana_cost(player2_id, &cost("{G}{G}"), 0, &plan).is_err());
+
+    for &forest_id in &forest_ids {
+        assert!(!is_tapped(&game, forest_id));
+    }
+
+    game.pay_mana_cost(player1_id, &cost("{G}{G}"), 0, &plan).unwrap();
+
+    for &forest_id in &forest_ids {
+        assert!(is_tapped(&game, forest_id));
+    }
+}
+
+#[test]
+fn test_auto_pay_phyrexian_with_life() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+
+    let plan = game.auto_pay_mana_cost(player1_id, &cost("{B/P}"), 0).unwrap();
+
+    assert_eq!(plan.life, 2);
+    assert_eq!(game.players.get(&player1_id).unwrap().life, 18);
+
+    // A player with 1 life can't pay 2 life.
+    game.players.get_mut(&player1_id).unwrap().life = 1;
+
+    assert!(game.auto_pay_mana_cost(player1_id, &cost("{B/P}"), 0).is_err());
+    assert_eq!(game.players.get(&player1_id).unwrap().life, 1);
+}
@@ -5,7 +5,9 @@ use std::collections::HashMap;
 use corrosion::{
     Object,
     ObjectDetails,
+    PermanentStatus,
     Game,
+    GamePhase,
     Id,
     PlayerAction,
 
@@ -22,15 +24,23 @@ fn setup() -> (Game, Id) {
 
     let hand_id = get_hand_id(&game, player1_id);
 
+    let definition_id = register_card(&mut game, forest());
+
     let forest_id = get_id();
     let forest = Object {
         id: forest_id,
         zone: hand_id,
         timestamp: get_timestamp(),
-        details: ObjectDetails::Forest {
-            tapped: false,
+        owner: player1_id,
+        controller: player1_id,
+        damage: 0,
+        status: PermanentStatus::default(),
+        counters: HashMap::new(),
+        face: None,
+        attached_to: None,
+        details: ObjectDetails::Card {
+            definition_id,
         },
-        abilities: HashMap::new(),
     };
     game.objects.insert(forest_id, forest);
 
@@ -48,10 +58,11 @@ fn test_success() {
 
     game.do_player_action(player1_id, &PlayerAction::PlayLand {
         object_id: forest_id,
+        face: None,
     }).unwrap();
 
     // Objects change idobject when they change zones
-    assert!(game.objects.get(&forest_id).is_none());
+    assert!(!game.objects.contains_key(&forest_id));
     assert_eq!(game.objects.len(), 1);
 
     let new_forest = game.objects.values().next().unwrap();
@@ -67,11 +78,130 @@ fn test_fail_wrong_player() {
 
     let result = game.do_player_action(player2_id, &PlayerAction::PlayLand {
         object_id: forest_id,
+        face: None,
     });
 
     assert!(result.is_err());
 
     // The object didn't move!
-    assert!(game.objects.get(&forest_id).is_some());
+    assert!(game.objects.contains_key(&forest_id));
     assert_eq!(game.objects.len(), 1);
 }
+
+#[test]
+fn test_fail_not_your_turn() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let hand_id = get_hand_id(&game, player2_id);
+
+    let forest_id = create_forest(&mut game, hand_id, player2_id);
+
+    // Player 2 has priority, but it's still player 1's turn.
+    game.do_player_action(player1_id, &PlayerAction::PassPriority).unwrap();
+
+    let result = game.do_player_action(player2_id, &PlayerAction::PlayLand {
+        object_id: forest_id,
+        face: None,
+    });
+
+    assert!(result.is_err());
+    assert!(game.objects.contains_key(&forest_id));
+}
+
+#[test]
+fn test_fail_outside_main_phase() {
+    let (mut game, forest_id) = setup();
+
+    let player1_id = game.player_turn_order[0];
+
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.current_phase, GamePhase::BeginningOfCombat);
+
+    let result = game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: forest_id,
+        face: None,
+    });
+
+    assert!(result.is_err());
+    assert!(game.objects.contains_key(&forest_id));
+}
+
+#[test]
+fn test_fail_stack_not_empty() {
+    let (mut game, forest_id) = setup();
+
+    let player1_id = game.player_turn_order[0];
+    let stack_id = get_stack_id(&game);
+
+    create_forest(&mut game, stack_id, player1_id);
+
+    let result = game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: forest_id,
+        face: None,
+    });
+
+    assert!(result.is_err());
+    assert!(game.objects.contains_key(&forest_id));
+}
+
+#[test]
+fn test_one_land_per_turn() {
+    let (mut game, forest_id) = setup();
+    fill_libraries(&mut game, 1);
+
+    let player1_id = game.player_turn_order[0];
+    let hand_id = get_hand_id(&game, player1_id);
+
+    let second_forest_id = create_forest(&mut game, hand_id, player1_id);
+
+    game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: forest_id,
+        face: None,
+    }).unwrap();
+
+    let result = game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: second_forest_id,
+        face: None,
+    });
+
+    assert!(result.is_err());
+    assert!(game.objects.contains_key(&second_forest_id));
+
+    // The count resets once player 1's next turn comes around.
+    pass_priority_until(&mut game, GamePhase::PrecombatMain);
+    pass_priority_until(&mut game, GamePhase::PrecombatMain);
+
+    assert_eq!(game.active_player, Some(player1_id));
+
+    game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: second_forest_id,
+        face: None,
+    }).unwrap();
+}
+
+#[test]
+fn test_additional_land_play() {
+    let (mut game, forest_id) = setup();
+
+    let player1_id = game.player_turn_order[0];
+    let hand_id = get_hand_id(&game, player1_id);
+
+    let second_forest_id = create_forest(&mut game, hand_id, player1_id);
+
+    game.players.get_mut(&player1_id).unwrap().additional_land_plays += 1;
+
+    assert_eq!(game.land_plays_allowed(player1_id), 2);
+
+    game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: forest_id,
+        face: None,
+    }).unwrap();
+
+    game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: second_forest_id,
+        face: None,
+    }).unwrap();
+}
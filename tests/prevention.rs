@@ -0,0 +1,325 @@
+extern crate corrosion;
+
+use corrosion::{
+    Ability,
+    ActivatedAbility,
+    ActivationCost,
+    CardType,
+    Characteristics,
+    DamageFilter,
+    DamageTarget,
+    Effect,
+    ObjectCounter,
+    ObjectFilter,
+    PlayerAction,
+    ReplacementEffectKind,
+    StatValue,
+};
+
+use corrosion::utility::*;
+
+#[test]
+fn test_damage() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let source_id = add_creature(&mut game, player1_id, "Prodigal Pyromancer", 2, 5);
+    let bears_id = add_creature(&mut game, player2_id, "Grizzly Bears", 2, 5);
+
+    // Planeswalkers get their loyalty counters as they enter the battlefield.
+    let hand_id = get_hand_id(&game, player2_id);
+    let walker_id = create_object(&mut game, hand_id, player2_id, Characteristics {
+        name: "Test Walker".to_string(),
+        card_types: vec![CardType::Planeswalker],
+        loyalty: Some(StatValue::Fixed(3)),
+        ..Characteristics::default()
+    });
+    let walker_id = game.move_object(walker_id, battlefield_id).unwrap();
+
+    // Damage to players is lost life, damage to creatures is marked on them,
+    // and damage to planeswalkers removes loyalty counters.
+    game.deal_damage(source_id, DamageTarget::Player(player2_id), 3);
+    game.deal_damage(source_id, DamageTarget::Object(bears_id), 2);
+    game.deal_damage(source_id, DamageTarget::Object(walker_id), 1);
+
+    assert_eq!(life(&game, player2_id), 17);
+    assert_eq!(game.objects[&bears_id].damage, 2);
+    assert_eq!(game.objects[&walker_id].counters(&ObjectCounter::Loyalty), 2);
+}
+
+#[test]
+fn test_prevention_shield() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let source_id = add_creature(&mut game, player2_id, "Prodigal Pyromancer", 2, 5);
+
+    // Prevent the next 3 damage that would be dealt to you this turn.
+    add_replacement_effect(&mut game, ReplacementEffectKind::PreventionShield {
+        damage: DamageFilter {
+            target: Some(DamageTarget::Player(player1_id)),
+            ..DamageFilter::default()
+        },
+        amount: 3,
+    });
+
+    game.deal_damage(source_id, DamageTarget::Player(player1_id), 2);
+    assert_eq!(life(&game, player1_id), 20);
+    assert_eq!(game.replacement_effects.len(), 1);
+
+    // Only 1 is left of the shield, so the rest of the damage gets through.
+    game.deal_damage(source_id, DamageTarget::Player(player1_id), 2);
+    assert_eq!(life(&game, player1_id), 19);
+    assert!(game.replacement_effects.is_empty());
+
+    game.deal_damage(source_id, DamageTarget::Player(player1_id), 2);
+    assert_eq!(life(&game, player1_id), 17);
+}
+
+#[test]
+fn test_fog() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    let attacker_id = add_creature(&mut game, player2_id, "Grizzly Bears", 2, 5);
+    let blocker_id = add_creature(&mut game, player1_id, "Wall", 2, 5);
+
+    // Prevent all combat damage that would be dealt this turn.
+    add_replacement_effect(&mut game, ReplacementEffectKind::PreventDamage {
+        damage: DamageFilter {
+            combat_only: true,
+            ..DamageFilter::default()
+        },
+        amount: None,
+    });
+
+    game.deal_combat_damage(attacker_id, DamageTarget::Object(blocker_id), 2);
+    game.deal_combat_damage(blocker_id, DamageTarget::Object(attacker_id), 2);
+    game.deal_combat_damage(attacker_id, DamageTarget::Player(player1_id), 2);
+
+    assert_eq!(game.objects[&blocker_id].damage, 0);
+    assert_eq!(game.objects[&attacker_id].damage, 0);
+    assert_eq!(life(&game, player1_id), 20);
+
+    // Noncombat damage is still dealt.
+    game.deal_damage(attacker_id, DamageTarget::Player(player1_id), 2);
+    assert_eq!(life(&game, player1_id), 18);
+}
+
+#[test]
+fn test_prevent_damage_from_source() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    let source_id = add_creature(&mut game, player2_id, "Prodigal Pyromancer", 2, 5);
+    let other_source_id = add_creature(&mut game, player2_id, "Prodigal Sorcerer", 2, 5);
+    let bears_id = add_creature(&mut game, player1_id, "Grizzly Bears", 2, 5);
+
+    // Prevent all damage the chosen source would deal this turn.
+    add_replacement_effect(&mut game, ReplacementEffectKind::PreventDamage {
+        damage: DamageFilter {
+            source_id: Some(source_id),
+            ..DamageFilter::default()
+        },
+        amount: None,
+    });
+
+    game.deal_damage(source_id, DamageTarget::Object(bears_id), 1);
+    game.deal_damage(source_id, DamageTarget::Player(player1_id), 1);
+    assert_eq!(game.objects[&bears_id].damage, 0);
+    assert_eq!(life(&game, player1_id), 20);
+
+    game.deal_damage(other_source_id, DamageTarget::Object(bears_id), 1);
+    assert_eq!(game.objects[&bears_id].damage, 1);
+}
+
+#[test]
+fn test_prevention_ability() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+
+    // If a source would deal damage to you, prevent 1 of that damage.
+    let armor_id = create_object(&mut game, battlefield_id, player1_id, Characteristics {
+        name: "Urza's Armor".to_string(),
+        card_types: vec![CardType::Artifact],
+        ..Characteristics::default()
+    });
+    add_ability(&mut game, armor_id, Ability::Replacement(ReplacementEffectKind::PreventDamage {
+        damage: DamageFilter {
+            target: Some(DamageTarget::Player(player1_id)),
+            ..DamageFilter::default()
+        },
+        amount: Some(1),
+    }));
+
+    // {T}: This deals 1 damage to player 1.
+    let pinger_id = add_creature(&mut game, player2_id, "Prodigal Pyromancer", 2, 5);
+    let ability_id = add_ability(&mut game, pinger_id, Ability::Activated(ActivatedAbility {
+        cost: ActivationCost {
+            tap: true,
+            ..ActivationCost::default()
+        },
+        effects: vec![Effect::DealDamage {
+            target: DamageTarget::Player(player1_id),
+            amount: 1,
+        }],
+    }));
+
+    // Damage from abilities is prevented like any other.
+    game.do_player_action(player1_id, &PlayerAction::PassPriority).unwrap();
+    game.do_player_action(player2_id, &PlayerAction::ActivateAbility {
+        object_id: pinger_id,
+        ability_id,
+    }).unwrap();
+    pass_priority_round(&mut game);
+
+    assert!(game.stack_is_empty());
+    assert_eq!(life(&game, player1_id), 20);
+
+    // Each event is only reduced by 1.
+    game.deal_combat_damage(pinger_id, DamageTarget::Player(player1_id), 3);
+    assert_eq!(life(&game, player1_id), 18);
+}
+
+#[test]
+fn test_spell_damage_prevented() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let stack_id = get_stack_id(&game);
+    let graveyard_id = get_graveyard_id(&game, player2_id);
+
+    // Prevent the next 1 damage that would be dealt to you this turn.
+    add_replacement_effect(&mut game, ReplacementEffectKind::PreventionShield {
+        damage: DamageFilter {
+            target: Some(DamageTarget::Player(player1_id)),
+            ..DamageFilter::default()
+        },
+        amount: 1,
+    });
+
+    // Shock deals 2 damage to player 1.
+    let shock_id = create_object(&mut game, stack_id, player2_id, Characteristics {
+        name: "Shock".to_string(),
+        card_types: vec![CardType::Instant],
+        ..Characteristics::default()
+    });
+    add_ability(&mut game, shock_id, Ability::Spell(vec![Effect::DealDamage {
+        target: DamageTarget::Player(player1_id),
+        amount: 2,
+    }]));
+
+    // Damage from spells is prevented like any other, and the spell is put
+    // into its owner's graveyard afterwards.
+    pass_priority_round(&mut game);
+
+    assert!(game.stack_is_empty());
+    assert_eq!(life(&game, player1_id), 19);
+    assert!(game.replacement_effects.is_empty());
+
+    let in_graveyard = game.objects.values()
+        .filter(|object| object.zone == graveyard_id)
+        .count();
+
+    assert_eq!(in_graveyard, 1);
+}
+
+#[test]
+fn test_prevent_damage_from_departed_source() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    // Prevent all damage that creatures would deal to you this turn.
+    add_replacement_effect(&mut game, ReplacementEffectKind::PreventDamage {
+        damage: DamageFilter {
+            source: Some(ObjectFilter {
+                card_types: vec![CardType::Creature],
+                ..ObjectFilter::default()
+            }),
+            target: Some(DamageTarget::Player(player1_id)),
+            ..DamageFilter::default()
+        },
+        amount: None,
+    });
+
+    // Sacrifice this creature: It deals 2 damage to player 1.
+    let source_id = add_creature(&mut game, player2_id, "Mogg Fanatic", 1, 1);
+    let ability_id = add_ability(&mut game, source_id, Ability::Activated(ActivatedAbility {
+        cost: ActivationCost {
+            sacrifice: true,
+            ..ActivationCost::default()
+        },
+        effects: vec![Effect::DealDamage {
+            target: DamageTarget::Player(player1_id),
+            amount: 2,
+        }],
+    }));
+
+    game.do_player_action(player1_id, &PlayerAction::PassPriority).unwrap();
+    game.do_player_action(player2_id, &PlayerAction::ActivateAbility {
+        object_id: source_id,
+        ability_id,
+    }).unwrap();
+
+    // The source is gone by the time the ability resolves, but it was a
+    // creature when it last existed.
+    assert!(!game.objects.contains_key(&source_id));
+
+    pass_priority_round(&mut game);
+
+    assert!(game.stack_is_empty());
+    assert_eq!(life(&game, player1_id), 20);
+
+    // Damage from other sources still gets through.
+    let battlefield_id = get_battlefield_id(&game);
+    let land_id = create_forest(&mut game, battlefield_id, player2_id);
+
+    game.deal_damage(land_id, DamageTarget::Player(player1_id), 2);
+    assert_eq!(life(&game, player1_id), 18);
+}
+
+#[test]
+fn test_damage_cant_be_prevented() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let source_id = add_creature(&mut game, player2_id, "Prodigal Pyromancer", 2, 5);
+
+    add_replacement_effect(&mut game, ReplacementEffectKind::PreventionShield {
+        damage: DamageFilter::default(),
+        amount: 3,
+    });
+
+    // Damage the chosen source would deal this turn can't be prevented.
+    add_replacement_effect(&mut game, ReplacementEffectKind::DamageCantBePrevented {
+        damage: DamageFilter {
+            source_id: Some(source_id),
+            ..DamageFilter::default()
+        },
+    });
+
+    game.deal_damage(source_id, DamageTarget::Player(player1_id), 2);
+    assert_eq!(life(&game, player1_id), 18);
+
+    // The shield isn't used up by damage it couldn't prevent.
+    let other_source_id = add_creature(&mut game, player2_id, "Prodigal Sorcerer", 2, 5);
+
+    game.deal_damage(other_source_id, DamageTarget::Player(player1_id), 3);
+    assert_eq!(life(&game, player1_id), 18);
+    assert_eq!(game.replacement_effects.len(), 1);
+}
@@ -1,11 +1,10 @@
 extern crate corrosion;
 
-use corrosion::PlayerAction;
+use corrosion::{GamePhase, PlayerAction};
 
 use corrosion::utility::*;
 
-/// Tests passing of priority and turn cycling. Currently assumes only one
-/// phase.
+/// Tests passing of priority between players within a single step.
 #[test]
 fn test_priority() {
     let mut game = new_two_player_game();
@@ -13,26 +12,31 @@ fn test_priority() {
     let player1_id = game.player_turn_order[0];
     let player2_id = game.player_turn_order[1];
 
+    assert_eq!(game.current_phase, GamePhase::PrecombatMain);
     assert_eq!(game.active_player, Some(player1_id));
     assert_eq!(game.priority_player, Some(player1_id));
 
     game.do_player_action(player1_id, &PlayerAction::PassPriority).unwrap();
 
+    assert_eq!(game.current_phase, GamePhase::PrecombatMain);
     assert_eq!(game.active_player, Some(player1_id));
     assert_eq!(game.priority_player, Some(player2_id));
 
     game.do_player_action(player2_id, &PlayerAction::PassPriority).unwrap();
 
-    assert_eq!(game.active_player, Some(player2_id));
-    assert_eq!(game.priority_player, Some(player2_id));
+    assert_eq!(game.current_phase, GamePhase::BeginningOfCombat);
+    assert_eq!(game.active_player, Some(player1_id));
+    assert_eq!(game.priority_player, Some(player1_id));
+}
 
-    game.do_player_action(player2_id, &PlayerAction::PassPriority).unwrap();
+#[test]
+fn test_fail_pass_without_priority() {
+    let mut game = new_two_player_game();
 
-    assert_eq!(game.active_player, Some(player2_id));
-    assert_eq!(game.priority_player, Some(player1_id));
+    let player2_id = game.player_turn_order[1];
 
-    game.do_player_action(player1_id, &PlayerAction::PassPriority).unwrap();
+    let result = game.do_player_action(player2_id, &PlayerAction::PassPriority);
 
-    assert_eq!(game.active_player, Some(player1_id));
-    assert_eq!(game.priority_player, Some(player1_id));
+    assert!(result.is_err());
+    assert_eq!(game.current_phase, GamePhase::PrecombatMain);
 }
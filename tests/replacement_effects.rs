@@ -0,0 +1,271 @@
+extern crate corrosion;
+
+use corrosion::{
+    Ability,
+    CardType,
+    Characteristics,
+    Duration,
+    Game,
+    GameMutation,
+    GameStatus,
+    Id,
+    ObjectCounter,
+    ObjectFilter,
+    PlayerAction,
+    PlayerChoice,
+    ReplacementEffectKind,
+};
+
+use corrosion::utility::*;
+
+fn add_enchantment(game: &mut Game, zone_id: Id, name: &str, kind: ReplacementEffectKind) -> Id {
+    let player1_id = game.player_turn_order[0];
+
+    let object_id = create_object(game, zone_id, player1_id, Characteristics {
+        name: name.to_string(),
+        card_types: vec![CardType::Enchantment],
+        ..Characteristics::default()
+    });
+    add_ability(game, object_id, Ability::Replacement(kind));
+
+    object_id
+}
+
+fn objects_in(game: &Game, zone_id: Id) -> usize {
+    game.objects.values()
+        .filter(|object| object.zone == zone_id)
+        .count()
+}
+
+#[test]
+fn test_enters_tapped() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let hand_id = get_hand_id(&game, player1_id);
+    let battlefield_id = get_battlefield_id(&game);
+
+    let gate_id = create_object(&mut game, hand_id, player1_id, Characteristics {
+        name: "Selesnya Guildgate".to_string(),
+        card_types: vec![CardType::Land],
+        subtypes: vec!["Gate".to_string()],
+        ..Characteristics::default()
+    });
+    add_ability(&mut game, gate_id, Ability::Replacement(ReplacementEffectKind::EntersTapped));
+
+    // The ability works as the land enters, even though it isn't on the
+    // battlefield yet.
+    game.do_player_action(player1_id, &PlayerAction::PlayLand {
+        object_id: gate_id,
+        face: None,
+    }).unwrap();
+
+    let new_gate_id = game.objects.values()
+        .find(|object| object.zone == battlefield_id)
+        .map(|object| object.id)
+        .unwrap();
+
+    assert!(is_tapped(&game, new_gate_id));
+
+    // It doesn't affect anything else.
+    let forest_id = create_forest(&mut game, hand_id, player1_id);
+    let new_forest_id = game.move_object(forest_id, battlefield_id).unwrap();
+
+    assert!(!is_tapped(&game, new_forest_id));
+}
+
+#[test]
+fn test_permanents_enter_tapped() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+
+    // Creatures enter the battlefield tapped.
+    add_enchantment(&mut game, battlefield_id, "Sleeping Potion", ReplacementEffectKind::PermanentsEnterTapped {
+        affected: ObjectFilter {
+            card_types: vec![CardType::Creature],
+            ..ObjectFilter::default()
+        },
+    });
+
+    let hand_id = get_hand_id(&game, player2_id);
+    let bears_id = create_object(&mut game, hand_id, player2_id, grizzly_bears());
+    let forest_id = create_forest(&mut game, hand_id, player2_id);
+
+    let new_bears_id = game.move_object(bears_id, battlefield_id).unwrap();
+    let new_forest_id = game.move_object(forest_id, battlefield_id).unwrap();
+
+    assert!(is_tapped(&game, new_bears_id));
+    assert!(!is_tapped(&game, new_forest_id));
+
+    // Effects from permanents only work while they're on the battlefield.
+    let other_hand_id = get_hand_id(&game, player1_id);
+    add_enchantment(&mut game, other_hand_id, "Frozen Aether", ReplacementEffectKind::PermanentsEnterTapped {
+        affected: ObjectFilter::default(),
+    });
+
+    let forest_id = create_forest(&mut game, hand_id, player2_id);
+    let new_forest_id = game.move_object(forest_id, battlefield_id).unwrap();
+
+    assert!(!is_tapped(&game, new_forest_id));
+}
+
+#[test]
+fn test_draw_instead() {
+    let mut game = new_two_player_game();
+    fill_libraries(&mut game, 2);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let hand_id = get_hand_id(&game, player1_id);
+
+    // If you would draw a card, you gain 5 life instead.
+    game.add_replacement_effect(player1_id, Duration::UntilEndOfTurn, ReplacementEffectKind::DrawInstead {
+        instead: vec![GameMutation::GainLife { player_id: player1_id, amount: 5 }],
+    });
+
+    assert_eq!(game.draw_card(player1_id), None);
+    assert_eq!(game.players[&player1_id].life, 25);
+    assert_eq!(objects_in(&game, hand_id), 0);
+
+    // Other players draw as usual.
+    assert!(game.draw_card(player2_id).is_some());
+    assert_eq!(game.players[&player2_id].life, 20);
+}
+
+#[test]
+fn test_replacements_apply_once() {
+    let mut game = new_two_player_game();
+    fill_libraries(&mut game, 5);
+
+    let player1_id = game.player_turn_order[0];
+    let hand_id = get_hand_id(&game, player1_id);
+
+    // If you would draw a card, draw two cards instead. The draws it makes
+    // instead aren't replaced again.
+    game.add_replacement_effect(player1_id, Duration::UntilEndOfTurn, ReplacementEffectKind::DrawInstead {
+        instead: vec![
+            GameMutation::DrawCard { player_id: player1_id },
+            GameMutation::DrawCard { player_id: player1_id },
+        ],
+    });
+
+    game.draw_card(player1_id);
+    assert_eq!(objects_in(&game, hand_id), 2);
+}
+
+#[test]
+fn test_affected_player_chooses() {
+    for &choose_newer in &[false, true] {
+        let mut game = new_two_player_game();
+        fill_libraries(&mut game, 2);
+
+        let player1_id = game.player_turn_order[0];
+        let player2_id = game.player_turn_order[1];
+
+        let older_id = game.add_replacement_effect(player1_id, Duration::UntilEndOfTurn, ReplacementEffectKind::DrawInstead {
+            instead: vec![GameMutation::GainLife { player_id: player1_id, amount: 5 }],
+        });
+        let newer_id = game.add_replacement_effect(player1_id, Duration::UntilEndOfTurn, ReplacementEffectKind::DrawInstead {
+            instead: vec![GameMutation::LoseLife { player_id: player1_id, amount: 5 }],
+        });
+
+        // The draw waits for the player to choose.
+        game.draw_card(player1_id);
+        assert_eq!(game.current_status, GameStatus::NeedsPlayerChoice(PlayerChoice::ReplacementOrder {
+            player_id: player1_id,
+            effect_ids: vec![older_id, newer_id],
+        }));
+        assert_eq!(game.players[&player1_id].life, 20);
+
+        assert!(game.do_player_action(player2_id, &PlayerAction::ChooseReplacement { effect_id: newer_id }).is_err());
+        assert!(game.do_player_action(player1_id, &PlayerAction::PassPriority).is_err());
+
+        let chosen_id = if choose_newer { newer_id } else { older_id };
+        game.do_player_action(player1_id, &PlayerAction::ChooseReplacement { effect_id: chosen_id }).unwrap();
+        assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+
+        // Whichever effect applies first replaces the draw, so the other one
+        // has nothing left to replace.
+        let expected_life = if choose_newer { 15 } else { 25 };
+        assert_eq!(game.players[&player1_id].life, expected_life);
+    }
+}
+
+#[test]
+fn test_replacement_choice_not_pending() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let effect_id = game.add_replacement_effect(player1_id, Duration::UntilEndOfTurn, ReplacementEffectKind::DrawInstead {
+        instead: Vec::new(),
+    });
+
+    assert!(game.do_player_action(player1_id, &PlayerAction::ChooseReplacement { effect_id }).is_err());
+}
+
+#[test]
+fn test_exile_instead() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+    let exile_id = get_exile_id(&game);
+
+    let rest_in_peace_id = add_enchantment(&mut game, battlefield_id, "Rest in Peace", ReplacementEffectKind::ExileInstead {
+        affected: ObjectFilter::default(),
+    });
+
+    // It works on cards going to any graveyard, from anywhere.
+    let bears_id = create_object(&mut game, battlefield_id, player2_id, grizzly_bears());
+    let hand_id = get_hand_id(&game, player1_id);
+    let forest_id = create_forest(&mut game, hand_id, player1_id);
+
+    let new_bears_id = game.move_object(bears_id, get_graveyard_id(&game, player2_id)).unwrap();
+    let new_forest_id = game.move_object(forest_id, get_graveyard_id(&game, player1_id)).unwrap();
+
+    assert_eq!(game.objects[&new_bears_id].zone, exile_id);
+    assert_eq!(game.objects[&new_forest_id].zone, exile_id);
+
+    // Moving to other zones is unchanged.
+    let forest_id = create_forest(&mut game, hand_id, player1_id);
+    let new_forest_id = game.move_object(forest_id, battlefield_id).unwrap();
+    assert_eq!(game.objects[&new_forest_id].zone, battlefield_id);
+
+    // Once it's gone, cards go to the graveyard again.
+    let graveyard_id = get_graveyard_id(&game, player1_id);
+    let new_rest_in_peace_id = game.move_object(rest_in_peace_id, graveyard_id).unwrap();
+    assert_eq!(game.objects[&new_rest_in_peace_id].zone, exile_id);
+
+    let new_forest_id = game.move_object(new_forest_id, graveyard_id).unwrap();
+    assert_eq!(game.objects[&new_forest_id].zone, graveyard_id);
+    assert_eq!(objects_in(&game, exile_id), 3);
+}
+
+#[test]
+fn test_double_counters() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let bears_id = create_object(&mut game, battlefield_id, player1_id, grizzly_bears());
+
+    let doubling_season = || ReplacementEffectKind::DoubleCounters {
+        affected: ObjectFilter {
+            you_control: true,
+            ..ObjectFilter::default()
+        },
+    };
+
+    add_enchantment(&mut game, battlefield_id, "Doubling Season", doubling_season());
+    game.add_counters(bears_id, ObjectCounter::PlusOnePlusOne, 1);
+    assert_eq!(game.objects[&bears_id].counters(&ObjectCounter::PlusOnePlusOne), 2);
+
+    // Each one doubles the counters once.
+    add_enchantment(&mut game, battlefield_id, "Doubling Season", doubling_season());
+    game.add_counters(bears_id, ObjectCounter::PlusOnePlusOne, 1);
+    assert_eq!(game.objects[&bears_id].counters(&ObjectCounter::PlusOnePlusOne), 6);
+}
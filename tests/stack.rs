@@ -0,0 +1,122 @@
+extern crate corrosion;
+
+use std::collections::HashMap;
+
+use corrosion::{
+    Ability,
+    Characteristics,
+    GamePhase,
+    ManaType,
+    Object,
+    ObjectDetails,
+    PermanentStatus,
+    PlayerAction,
+
+    get_id,
+    get_timestamp,
+};
+
+use corrosion::utility::*;
+
+#[test]
+fn test_resolve_in_order() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let stack_id = get_stack_id(&game);
+    let battlefield_id = get_battlefield_id(&game);
+
+    let bottom_id = create_forest(&mut game, stack_id, player1_id);
+    let top_id = create_forest(&mut game, stack_id, player1_id);
+
+    assert!(!game.stack_is_empty());
+
+    // The top of the stack resolves first, and the active player gets
+    // priority afterwards.
+    pass_priority_round(&mut game);
+
+    assert!(!game.objects.contains_key(&top_id));
+    assert!(game.objects.contains_key(&bottom_id));
+    assert_eq!(game.zones.get(&stack_id).unwrap().order, vec![bottom_id]);
+    assert_eq!(game.current_phase, GamePhase::PrecombatMain);
+    assert_eq!(game.priority_player, Some(player1_id));
+
+    pass_priority_round(&mut game);
+
+    assert!(game.stack_is_empty());
+    assert_eq!(game.current_phase, GamePhase::PrecombatMain);
+
+    let on_battlefield = game.objects.values()
+        .filter(|object| object.zone == battlefield_id)
+        .count();
+
+    assert_eq!(on_battlefield, 2);
+
+    // Only passing on an empty stack moves the game along.
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.current_phase, GamePhase::BeginningOfCombat);
+}
+
+#[test]
+fn test_actions_interrupt_passing() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let stack_id = get_stack_id(&game);
+    let battlefield_id = get_battlefield_id(&game);
+
+    let spell_id = create_forest(&mut game, stack_id, player1_id);
+
+    let forest_ability_id = get_id();
+    let definition_id = register_card(&mut game, Characteristics {
+        abilities: {
+            let mut abilities = HashMap::new();
+            abilities.insert(forest_ability_id, Ability::tap_for_mana(ManaType::Green));
+
+            abilities
+        },
+        ..forest()
+    });
+
+    let forest_id = get_id();
+    let forest = Object {
+        id: forest_id,
+        zone: battlefield_id,
+        timestamp: get_timestamp(),
+        owner: player2_id,
+        controller: player2_id,
+        damage: 0,
+        status: PermanentStatus::default(),
+        counters: HashMap::new(),
+        face: None,
+        attached_to: None,
+        details: ObjectDetails::Card {
+            definition_id,
+        },
+    };
+    game.objects.insert(forest_id, forest);
+
+    game.do_player_action(player1_id, &PlayerAction::PassPriority).unwrap();
+
+    game.do_player_action(player2_id, &PlayerAction::ActivateManaAbility {
+        object_id: forest_id,
+        ability_id: forest_ability_id,
+        mana_type: ManaType::Green,
+    }).unwrap();
+
+    // Player 2 acted after player 1 passed, so player 1 needs to pass again
+    // before the spell resolves.
+    game.do_player_action(player2_id, &PlayerAction::PassPriority).unwrap();
+
+    assert!(game.objects.contains_key(&spell_id));
+    assert_eq!(game.priority_player, Some(player1_id));
+
+    game.do_player_action(player1_id, &PlayerAction::PassPriority).unwrap();
+
+    assert!(!game.objects.contains_key(&spell_id));
+    assert!(game.stack_is_empty());
+    assert_eq!(game.priority_player, Some(player1_id));
+    assert_eq!(game.current_phase, GamePhase::PrecombatMain);
+}
@@ -0,0 +1,243 @@
+extern crate corrosion;
+
+use corrosion::{
+    Ability,
+    ActivatedAbility,
+    ActivationCost,
+    CardType,
+    Characteristics,
+    Effect,
+    GameResult,
+    GameStatus,
+    ObjectFilter,
+    PlayerAction,
+    PlayerChoice,
+    PlayerCounter,
+    StatValue,
+    Supertype,
+};
+
+use corrosion::utility::*;
+
+#[test]
+fn test_zero_life() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    game.lose_life(player2_id, 20);
+
+    // Nothing happens until state-based actions are checked.
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+
+    game.do_player_action(player1_id, &PlayerAction::PassPriority).unwrap();
+
+    assert_eq!(game.current_status, GameStatus::Ended(GameResult {
+        winners: vec![player1_id],
+        losers: vec![player2_id],
+    }));
+}
+
+#[test]
+fn test_poison_counters() {
+    let mut game = new_two_player_game();
+
+    let player2_id = game.player_turn_order[1];
+
+    game.add_player_counters(player2_id, PlayerCounter::Poison, 9);
+    game.process_state_based_actions();
+
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+
+    game.add_player_counters(player2_id, PlayerCounter::Poison, 1);
+    game.process_state_based_actions();
+
+    assert!(game.losers.contains(&player2_id));
+}
+
+#[test]
+fn test_simultaneous_losses_draw() {
+    let mut game = new_game(3);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let player3_id = game.player_turn_order[2];
+
+    game.lose_life(player2_id, 25);
+    game.lose_life(player3_id, 20);
+    game.process_state_based_actions();
+
+    assert_eq!(game.losers, vec![player2_id, player3_id]);
+    assert_eq!(game.current_status, GameStatus::Ended(GameResult {
+        winners: vec![player1_id],
+        losers: vec![player2_id, player3_id],
+    }));
+
+    let mut game = new_two_player_game();
+
+    for player_id in game.player_turn_order.clone() {
+        game.lose_life(player_id, 20);
+    }
+
+    game.process_state_based_actions();
+
+    match game.current_status {
+        GameStatus::Ended(ref result) => assert!(result.is_draw()),
+        _ => panic!("Game should have ended"),
+    }
+}
+
+#[test]
+fn test_multiplayer_loss_continues_game() {
+    let mut game = new_game(3);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let player3_id = game.player_turn_order[2];
+
+    game.lose_life(player3_id, 20);
+    game.do_player_action(player1_id, &PlayerAction::PassPriority).unwrap();
+
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+    assert_eq!(game.player_turn_order, vec![player1_id, player2_id]);
+    assert_eq!(game.priority_player, Some(player2_id));
+}
+
+#[test]
+fn test_drawing_from_empty_library_on_resolution() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+
+    // One card in the library, but the ability draws two.
+    fill_libraries(&mut game, 1);
+
+    let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+    let ability_id = add_ability(&mut game, forest_id, Ability::Activated(ActivatedAbility {
+        cost: ActivationCost::default(),
+        effects: vec![Effect::DrawCards { count: 2 }],
+    }));
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateAbility {
+        object_id: forest_id,
+        ability_id,
+    }).unwrap();
+
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.current_status, GameStatus::Ended(GameResult {
+        winners: vec![player2_id],
+        losers: vec![player1_id],
+    }));
+}
+
+#[test]
+fn test_legend_rule() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+    let graveyard_id = get_graveyard_id(&game, player1_id);
+
+    let legend = || Characteristics {
+        name: "Isamaru, Hound of Konda".to_string(),
+        supertypes: vec![Supertype::Legendary],
+        card_types: vec![CardType::Creature],
+        power: Some(StatValue::Fixed(2)),
+        toughness: Some(StatValue::Fixed(2)),
+        ..Characteristics::default()
+    };
+
+    let first_id = create_object(&mut game, battlefield_id, player1_id, legend());
+    let other_player_id = create_object(&mut game, battlefield_id, player2_id, legend());
+    game.process_state_based_actions();
+
+    // Each player can control one legend with the same name.
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+
+    let second_id = create_object(&mut game, battlefield_id, player1_id, legend());
+    game.process_state_based_actions();
+
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerChoice(PlayerChoice::KeepLegend {
+        player_id: player1_id,
+        object_ids: vec![first_id, second_id],
+    }));
+
+    // Only the legends' controller chooses, and only between those legends.
+    assert!(game.do_player_action(player2_id, &PlayerAction::KeepLegend {
+        object_id: first_id,
+    }).is_err());
+
+    assert!(game.do_player_action(player1_id, &PlayerAction::KeepLegend {
+        object_id: other_player_id,
+    }).is_err());
+
+    game.do_player_action(player1_id, &PlayerAction::KeepLegend {
+        object_id: second_id,
+    }).unwrap();
+
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+    assert_eq!(game.objects[&second_id].zone, battlefield_id);
+    assert_eq!(game.objects[&other_player_id].zone, battlefield_id);
+    assert!(!game.objects.contains_key(&first_id));
+    assert_eq!(game.zones[&graveyard_id].order.len(), 1);
+}
+
+#[test]
+fn test_illegal_auras() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let graveyard_id = get_graveyard_id(&game, player1_id);
+
+    let bears_id = create_object(&mut game, battlefield_id, player1_id, Characteristics {
+        name: "Grizzly Bears".to_string(),
+        card_types: vec![CardType::Creature],
+        power: Some(StatValue::Fixed(2)),
+        toughness: Some(StatValue::Fixed(2)),
+        ..Characteristics::default()
+    });
+    let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+
+    let add_aura = |game: &mut _| {
+        let aura_id = create_object(game, battlefield_id, player1_id, Characteristics {
+            name: "Holy Strength".to_string(),
+            card_types: vec![CardType::Enchantment],
+            subtypes: vec!["Aura".to_string()],
+            ..Characteristics::default()
+        });
+        add_ability(game, aura_id, Ability::Enchant(ObjectFilter {
+            card_types: vec![CardType::Creature],
+            ..ObjectFilter::default()
+        }));
+
+        aura_id
+    };
+
+    let legal_id = add_aura(&mut game);
+    let unattached_id = add_aura(&mut game);
+    let on_land_id = add_aura(&mut game);
+
+    assert!(game.attach(legal_id, bears_id));
+    assert!(game.attach(on_land_id, forest_id));
+    game.process_state_based_actions();
+
+    // Auras that aren't attached to anything, or are attached to something
+    // they can't enchant, go to the graveyard.
+    assert_eq!(game.objects[&legal_id].zone, battlefield_id);
+    assert!(!game.objects.contains_key(&unattached_id));
+    assert!(!game.objects.contains_key(&on_land_id));
+    assert_eq!(game.zones[&graveyard_id].order.len(), 2);
+
+    // So do Auras whose permanent has left the battlefield.
+    game.move_object(bears_id, graveyard_id);
+    game.process_state_based_actions();
+
+    assert!(!game.objects.contains_key(&legal_id));
+    assert_eq!(game.zones[&graveyard_id].order.len(), 4);
+}
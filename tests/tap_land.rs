@@ -4,8 +4,11 @@ use std::collections::HashMap;
 
 use corrosion::{
     Ability,
+    Characteristics,
+    ManaType,
     Object,
     ObjectDetails,
+    PermanentStatus,
     PlayerAction,
 
     get_id,
@@ -18,47 +21,79 @@ use corrosion::utility::*;
 fn test_success() {
     let mut game = new_two_player_game();
 
+    let player1_id = game.player_turn_order[0];
     let battlefield_id = get_battlefield_id(&game);
     let forest_ability_id = get_id();
 
     let forest_abilities = {
         let mut abilities = HashMap::new();
-        abilities.insert(forest_ability_id, Ability::AddGreen);
+        abilities.insert(forest_ability_id, Ability::tap_for_mana(ManaType::Green));
 
         abilities
     };
 
+    let definition_id = register_card(&mut game, Characteristics {
+        abilities: forest_abilities,
+        ..forest()
+    });
+
     let forest_id = get_id();
     {
         let forest = Object {
             id: forest_id,
             zone: battlefield_id,
             timestamp: get_timestamp(),
-            details: ObjectDetails::Forest {
-                tapped: false,
+            owner: player1_id,
+            controller: player1_id,
+            damage: 0,
+            status: PermanentStatus::default(),
+            counters: HashMap::new(),
+            face: None,
+            attached_to: None,
+            details: ObjectDetails::Card {
+                definition_id,
             },
-            abilities: forest_abilities,
         };
         game.objects.insert(forest_id, forest);
     }
 
     assert_eq!(game.objects.len(), 1);
 
-    let player1_id = game.player_turn_order[0];
-
-    game.do_player_action(player1_id, &PlayerAction::ActivateAbility {
+    game.do_player_action(player1_id, &PlayerAction::ActivateManaAbility {
         object_id: forest_id,
         ability_id: forest_ability_id,
+        mana_type: ManaType::Green,
     }).unwrap();
 
     // Did the land tap?
     let forest = game.objects.get(&forest_id).unwrap();
 
-    match forest.details {
-        ObjectDetails::Forest { tapped } => assert!(tapped),
-    }
+    assert!(forest.is_tapped());
 
     // Did we get that mana we paid for?
-    let mana = *game.mana_pools.get(&player1_id).unwrap();
-    assert_eq!(mana, 1);
+    let mana_pool = game.mana_pools.get(&player1_id).unwrap();
+    assert_eq!(mana_pool.get(ManaType::Green), 1);
+    assert_eq!(mana_pool.total(), 1);
+}
+
+#[test]
+fn test_status_reset_on_zone_change() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let hand_id = get_hand_id(&game, player1_id);
+
+    let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+
+    {
+        let forest = game.objects.get_mut(&forest_id).unwrap();
+        forest.set_tapped(true);
+        forest.status.flipped = true;
+    }
+
+    let forest_id = game.move_object(forest_id, hand_id).unwrap();
+    let forest_id = game.move_object(forest_id, battlefield_id).unwrap();
+
+    assert_eq!(game.objects.get(&forest_id).unwrap().status, PermanentStatus::default());
 }
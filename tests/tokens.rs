@@ -0,0 +1,179 @@
+extern crate corrosion;
+
+use std::path::Path;
+use std::sync::Arc;
+
+use corrosion::{
+    Ability,
+    Color,
+    Game,
+    Id,
+    ManaType,
+    ObjectCounter,
+    PlayerAction,
+
+    load_card_file,
+};
+
+use corrosion::utility::*;
+
+/// Loads the common token definitions into the game's registry.
+fn load_tokens(game: &mut Game) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("cards/tokens.toml");
+
+    load_card_file(Arc::make_mut(&mut game.registry), &path).unwrap();
+}
+
+fn create_named_token(game: &mut Game, name: &str, controller: Id) -> Id {
+    let definition_id = game.registry.find_by_name(name).unwrap().id;
+
+    game.create_token(definition_id, controller).unwrap()
+}
+
+fn ability_id(game: &Game, object_id: Id) -> Id {
+    *game.characteristics(object_id).unwrap().abilities.keys().next().unwrap()
+}
+
+#[test]
+fn test_create_zombie() {
+    let mut game = new_two_player_game();
+    load_tokens(&mut game);
+
+    let player2_id = game.player_turn_order[1];
+    let zombie_id = create_named_token(&mut game, "Zombie", player2_id);
+
+    let zombie = &game.objects[&zombie_id];
+    assert!(zombie.is_token());
+    assert!(!zombie.is_card());
+    assert_eq!(zombie.zone, get_battlefield_id(&game));
+    assert_eq!(zombie.owner, player2_id);
+    assert_eq!(zombie.controller, player2_id);
+
+    let view = game.view_object(zombie_id).unwrap();
+    let characteristics = view.characteristics.as_ref().unwrap();
+
+    assert_eq!(characteristics.colors(), vec![Color::Black]);
+    assert!(characteristics.has_subtype("Zombie"));
+    assert_eq!((view.power(), view.toughness()), (Some(2), Some(2)));
+}
+
+#[test]
+fn test_tokens_cease_to_exist_off_the_battlefield() {
+    let mut game = new_two_player_game();
+    load_tokens(&mut game);
+
+    let player1_id = game.player_turn_order[0];
+    let graveyard_id = get_graveyard_id(&game, player1_id);
+    let hand_id = get_hand_id(&game, player1_id);
+
+    let zombie_id = create_named_token(&mut game, "Zombie", player1_id);
+    game.add_counters(zombie_id, ObjectCounter::MinusOneMinusOne, 2);
+    game.process_state_based_actions();
+
+    // The token died, and then stopped existing.
+    assert!(game.objects.is_empty());
+    assert_eq!(game.last_known_information(zombie_id).unwrap().view.toughness(), Some(0));
+
+    // Until state-based actions are checked, a token in the graveyard can't go
+    // anywhere else.
+    let zombie_id = create_named_token(&mut game, "Zombie", player1_id);
+    let dead_zombie_id = game.move_object(zombie_id, graveyard_id).unwrap();
+
+    assert_eq!(game.move_object(dead_zombie_id, hand_id), None);
+    assert_eq!(game.objects[&dead_zombie_id].zone, graveyard_id);
+
+    game.process_state_based_actions();
+    assert!(game.objects.is_empty());
+}
+
+#[test]
+fn test_token_copy() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let bears_id = create_object(&mut game, battlefield_id, player2_id, grizzly_bears());
+    game.add_counters(bears_id, ObjectCounter::PlusOnePlusOne, 1);
+    game.objects.get_mut(&bears_id).unwrap().set_tapped(true);
+
+    let copy_id = game.create_token_copy(bears_id, player1_id).unwrap();
+
+    // The copy has the same characteristics, but not the counters or status.
+    let copy = game.view_object(copy_id).unwrap();
+    assert!(copy.object.is_token());
+    assert_eq!(copy.object.owner, player1_id);
+    assert!(!copy.object.is_tapped());
+    assert_eq!(copy.characteristics.as_ref().unwrap().name, "Grizzly Bears");
+    assert_eq!((copy.power(), copy.toughness()), (Some(2), Some(2)));
+}
+
+#[test]
+fn test_treasure() {
+    let mut game = new_two_player_game();
+    load_tokens(&mut game);
+
+    let player1_id = game.player_turn_order[0];
+    let treasure_id = create_named_token(&mut game, "Treasure", player1_id);
+    let ability_id = ability_id(&game, treasure_id);
+
+    match game.ability(treasure_id, ability_id).unwrap() {
+        Ability::Mana(mana_ability) => assert!(mana_ability.cost.sacrifice),
+        _ => panic!("Treasure should have a mana ability"),
+    }
+
+    game.do_player_action(player1_id, &PlayerAction::ActivateManaAbility {
+        object_id: treasure_id,
+        ability_id,
+        mana_type: ManaType::Red,
+    }).unwrap();
+
+    assert_eq!(game.mana_pools[&player1_id].get(ManaType::Red), 1);
+    assert!(!game.objects.contains_key(&treasure_id));
+
+    game.process_state_based_actions();
+    assert!(game.objects.is_empty());
+}
+
+#[test]
+fn test_food_and_clue() {
+    let mut game = new_two_player_game();
+    load_tokens(&mut game);
+    fill_libraries(&mut game, 5);
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+    let hand_id = get_hand_id(&game, player1_id);
+
+    for _ in 0..4 {
+        let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+        add_ability(&mut game, forest_id, Ability::tap_for_mana(ManaType::Green));
+    }
+
+    let food_id = create_named_token(&mut game, "Food", player1_id);
+    let clue_id = create_named_token(&mut game, "Clue", player1_id);
+
+    for &token_id in &[food_id, clue_id] {
+        let ability_id = ability_id(&game, token_id);
+
+        game.do_player_action(player1_id, &PlayerAction::ActivateAbility {
+            object_id: token_id,
+            ability_id,
+        }).unwrap();
+
+        // Sacrificing is part of the cost, so the token is gone before the
+        // ability resolves.
+        assert!(!game.objects.contains_key(&token_id));
+
+        pass_priority_round(&mut game);
+    }
+
+    assert_eq!(game.players[&player1_id].life, 23);
+
+    let hand_size = game.objects.values()
+        .filter(|object| object.zone == hand_id)
+        .count();
+
+    assert_eq!(hand_size, 1);
+}
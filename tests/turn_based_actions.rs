@@ -0,0 +1,229 @@
+extern crate corrosion;
+
+use corrosion::{
+    Ability,
+    GamePhase,
+    GameResult,
+    GameStatus,
+    PlayerAction,
+    PlayerChoice,
+};
+
+use corrosion::utility::*;
+
+#[test]
+fn test_untap_active_player_permanents() {
+    let mut game = new_two_player_game();
+    fill_libraries(&mut game, 5);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let forest1_id = create_forest(&mut game, battlefield_id, player1_id);
+    let forest2_id = create_forest(&mut game, battlefield_id, player2_id);
+
+    for object in game.objects.values_mut() {
+        object.set_tapped(true);
+    }
+
+    // Player 2's untap step only untaps their own permanents.
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+
+    assert_eq!(game.active_player, Some(player2_id));
+    assert!(is_tapped(&game, forest1_id));
+    assert!(!is_tapped(&game, forest2_id));
+
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+
+    assert_eq!(game.active_player, Some(player1_id));
+    assert!(!is_tapped(&game, forest1_id));
+}
+
+#[test]
+fn test_phasing() {
+    let mut game = new_two_player_game();
+    fill_libraries(&mut game, 5);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+    let phasing_id = create_forest(&mut game, battlefield_id, player2_id);
+    add_ability(&mut game, phasing_id, Ability::Phasing);
+
+    game.objects.get_mut(&forest_id).unwrap().set_tapped(true);
+    assert!(game.phase_out(forest_id));
+    assert!(game.view_object(forest_id).is_none());
+
+    // Permanents with phasing phase out during their controller's untap step,
+    // and phased-out permanents stay that way until their controller's.
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+
+    assert_eq!(game.active_player, Some(player2_id));
+    assert!(game.view_object(phasing_id).is_none());
+    assert!(game.view_object(forest_id).is_none());
+    assert!(is_tapped(&game, forest_id));
+
+    // Phasing happens before untapping, so the Forest untaps as it comes back.
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+
+    assert_eq!(game.active_player, Some(player1_id));
+    assert!(game.view_object(forest_id).is_some());
+    assert!(!is_tapped(&game, forest_id));
+    assert!(game.view_object(phasing_id).is_none());
+
+    pass_priority_until(&mut game, GamePhase::Upkeep);
+
+    assert!(game.view_object(phasing_id).is_some());
+}
+
+#[test]
+fn test_draw_step() {
+    let mut game = new_two_player_game();
+
+    let player2_id = game.player_turn_order[1];
+    let library_id = get_library_id(&game, player2_id);
+    let hand_id = get_hand_id(&game, player2_id);
+
+    create_forest(&mut game, library_id, player2_id);
+    let top_card_id = create_forest(&mut game, library_id, player2_id);
+
+    pass_priority_until(&mut game, GamePhase::Draw);
+
+    assert_eq!(game.active_player, Some(player2_id));
+
+    // The drawn card became a new object in player 2's hand.
+    assert!(!game.objects.contains_key(&top_card_id));
+    assert_eq!(game.zones.get(&library_id).unwrap().order.len(), 1);
+
+    let hand_size = game.objects.values()
+        .filter(|object| object.zone == hand_id)
+        .count();
+
+    assert_eq!(hand_size, 1);
+}
+
+#[test]
+fn test_starting_player_skips_first_draw() {
+    let mut game = new_two_player_game();
+    game.current_phase = GamePhase::Upkeep;
+
+    let player1_id = game.player_turn_order[0];
+    let library_id = get_library_id(&game, player1_id);
+
+    create_forest(&mut game, library_id, player1_id);
+
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.current_phase, GamePhase::Draw);
+    assert_eq!(game.zones.get(&library_id).unwrap().order.len(), 1);
+}
+
+#[test]
+fn test_first_draw_after_player_leaves() {
+    let mut game = new_game(3);
+    game.current_phase = GamePhase::Upkeep;
+
+    let player1_id = game.player_turn_order[0];
+    let player3_id = game.player_turn_order[2];
+    let library_id = get_library_id(&game, player1_id);
+
+    create_forest(&mut game, library_id, player1_id);
+
+    game.do_player_action(player3_id, &PlayerAction::Concede).unwrap();
+
+    // Only two players are left, but it didn't start as a two-player game, so
+    // the starting player still draws.
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.current_phase, GamePhase::Draw);
+    assert!(game.zones.get(&library_id).unwrap().order.is_empty());
+}
+
+#[test]
+fn test_empty_library_draw() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    // Drawing from an empty library makes player 2 lose before they'd get
+    // priority in their draw step.
+    pass_priority_until(&mut game, GamePhase::Draw);
+
+    assert_eq!(game.current_status, GameStatus::Ended(GameResult {
+        winners: vec![player1_id],
+        losers: vec![player2_id],
+    }));
+}
+
+#[test]
+fn test_cleanup_discard() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+    let hand_id = get_hand_id(&game, player1_id);
+    let graveyard_id = get_graveyard_id(&game, player1_id);
+
+    let card_ids: Vec<_> = (0..9)
+        .map(|_| create_forest(&mut game, hand_id, player1_id))
+        .collect();
+
+    pass_priority_until(&mut game, GamePhase::End);
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.current_phase, GamePhase::Cleanup);
+    assert_eq!(game.priority_player, None);
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerChoice(PlayerChoice::Discard {
+        player_id: player1_id,
+        count: 2,
+    }));
+
+    // Only the active player can make this choice, and they have to pick the
+    // right number of cards.
+    assert!(game.do_player_action(player2_id, &PlayerAction::Discard {
+        object_ids: card_ids[..2].to_vec(),
+    }).is_err());
+
+    assert!(game.do_player_action(player1_id, &PlayerAction::Discard {
+        object_ids: card_ids[..1].to_vec(),
+    }).is_err());
+
+    assert!(game.do_player_action(player1_id, &PlayerAction::Discard {
+        object_ids: vec![card_ids[0], card_ids[0]],
+    }).is_err());
+
+    // Nobody can pass priority while the choice is pending.
+    assert!(game.do_player_action(player1_id, &PlayerAction::PassPriority).is_err());
+
+    game.do_player_action(player1_id, &PlayerAction::Discard {
+        object_ids: card_ids[..2].to_vec(),
+    }).unwrap();
+
+    assert_eq!(game.zones.get(&graveyard_id).unwrap().order.len(), 2);
+    assert_eq!(game.current_status, GameStatus::NeedsPlayerAction);
+    assert_eq!(game.current_phase, GamePhase::Upkeep);
+    assert_eq!(game.active_player, Some(player2_id));
+}
+
+#[test]
+fn test_cleanup_removes_damage() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    let battlefield_id = get_battlefield_id(&game);
+
+    let forest_id = create_forest(&mut game, battlefield_id, player1_id);
+    game.objects.get_mut(&forest_id).unwrap().damage = 2;
+
+    pass_priority_until(&mut game, GamePhase::End);
+
+    assert_eq!(game.objects.get(&forest_id).unwrap().damage, 2);
+
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.objects.get(&forest_id).unwrap().damage, 0);
+}
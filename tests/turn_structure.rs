@@ -0,0 +1,96 @@
+extern crate corrosion;
+
+use corrosion::{GamePhase, PlayerAction};
+
+use corrosion::utility::*;
+
+/// Walks through the rest of player 1's turn and into player 2's, making sure
+/// every step that grants priority is visited in order.
+#[test]
+fn test_full_turn() {
+    let mut game = new_two_player_game();
+    fill_libraries(&mut game, 1);
+
+    let player1_id = game.player_turn_order[0];
+    let player2_id = game.player_turn_order[1];
+
+    // Something has to attack for the whole combat phase to happen.
+    let bears_id = add_creature(&mut game, player1_id, "Grizzly Bears", 2, 2);
+
+    let expected_phases = [
+        GamePhase::BeginningOfCombat,
+        GamePhase::DeclareAttackers,
+        GamePhase::DeclareBlockers,
+        GamePhase::CombatDamage,
+        GamePhase::EndOfCombat,
+        GamePhase::PostcombatMain,
+        GamePhase::End,
+    ];
+
+    for &phase in &expected_phases {
+        pass_priority_round(&mut game);
+
+        if phase == GamePhase::DeclareAttackers {
+            game.do_player_action(player1_id, &PlayerAction::DeclareAttackers {
+                attackers: vec![(bears_id, player2_id)],
+            }).unwrap();
+        }
+
+        assert_eq!(game.current_phase, phase);
+        assert_eq!(game.active_player, Some(player1_id));
+        assert_eq!(game.priority_player, Some(player1_id));
+    }
+
+    // The cleanup step and the next untap step grant no priority, so we should
+    // land in player 2's upkeep.
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.current_phase, GamePhase::Upkeep);
+    assert_eq!(game.active_player, Some(player2_id));
+    assert_eq!(game.priority_player, Some(player2_id));
+
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.current_phase, GamePhase::Draw);
+
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.current_phase, GamePhase::PrecombatMain);
+    assert_eq!(game.active_player, Some(player2_id));
+}
+
+#[test]
+fn test_no_attackers() {
+    let mut game = new_two_player_game();
+
+    let player1_id = game.player_turn_order[0];
+    add_creature(&mut game, player1_id, "Grizzly Bears", 2, 2);
+
+    pass_priority_until(&mut game, GamePhase::DeclareAttackers);
+
+    game.do_player_action(player1_id, &PlayerAction::DeclareAttackers {
+        attackers: Vec::new(),
+    }).unwrap();
+
+    // With nothing attacking, the declare blockers and combat damage steps
+    // are skipped (rule 508.8).
+    pass_priority_round(&mut game);
+
+    assert_eq!(game.current_phase, GamePhase::EndOfCombat);
+    assert_eq!(game.priority_player, Some(player1_id));
+}
+
+#[test]
+fn test_phases_without_priority() {
+    assert!(!GamePhase::Untap.grants_priority());
+    assert!(!GamePhase::Cleanup.grants_priority());
+    assert!(GamePhase::Upkeep.grants_priority());
+    assert!(GamePhase::End.grants_priority());
+}
+
+#[test]
+fn test_main_phases() {
+    assert!(GamePhase::PrecombatMain.is_main_phase());
+    assert!(GamePhase::PostcombatMain.is_main_phase());
+    assert!(!GamePhase::BeginningOfCombat.is_main_phase());
+}